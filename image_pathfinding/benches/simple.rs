@@ -1,7 +1,7 @@
 use criterion::{Criterion, criterion_group, criterion_main};
 use image_pathfinding::{
-    AStar2D, AStarTemporal, Dijkstra2D, DijkstraTemporal, Fringe2D, ImagePathfinder2D, Pos2D,
-    load_images_to_volume, load_png_to_ndarray,
+    AStar2D, AStarTemporal, Dijkstra2D, DijkstraTemporal, Fringe2D, FringeTemporal,
+    ImagePathfinder2D, Pos2D, load_images_to_volume, load_png_to_ndarray,
 };
 use std::hint::black_box;
 use std::path::Path;
@@ -23,7 +23,7 @@ fn criterion_benchmark(c: &mut Criterion) {
     let array = load_png_to_ndarray(IMAGE_PATH_2D);
 
     let dji2d = Dijkstra2D {};
-    let astar2d = AStar2D {};
+    let astar2d = AStar2D::default();
     let fringe2d = Fringe2D {};
 
     c.bench_function("2D Dijkstra 600x600", |b| {
@@ -77,6 +77,7 @@ fn criterion_benchmark(c: &mut Criterion) {
 
         let dijkstra_temporal = DijkstraTemporal {};
         let astar_temporal = AStarTemporal {};
+        let fringe_temporal = FringeTemporal {};
 
         // Prepare start and end positions for temporal routing
         let starts = vec![(START_POS_3D_X, START_POS_3D_Y, 0)];
@@ -86,7 +87,7 @@ fn criterion_benchmark(c: &mut Criterion) {
             b.iter(|| {
                 dijkstra_temporal.find_route_over_time(
                     black_box(volume.view()),
-                    Some(REACH),
+                    Some(REACH.into()),
                     Some(AXIS),
                     Some(starts.clone()),
                     Some(ends.clone()),
@@ -98,7 +99,19 @@ fn criterion_benchmark(c: &mut Criterion) {
             b.iter(|| {
                 astar_temporal.find_route_over_time(
                     black_box(volume.view()),
-                    Some(REACH),
+                    Some(REACH.into()),
+                    Some(AXIS),
+                    Some(starts.clone()),
+                    Some(ends.clone()),
+                )
+            })
+        });
+
+        c.bench_function("Temporal Fringe find_route_over_time (120 frames)", |b| {
+            b.iter(|| {
+                fringe_temporal.find_route_over_time(
+                    black_box(volume.view()),
+                    Some(REACH.into()),
                     Some(AXIS),
                     Some(starts.clone()),
                     Some(ends.clone()),
@@ -1,5 +1,13 @@
+use crate::budget::CostBudget;
+use crate::deadline::SearchDeadline;
+use crate::node_limit::NodeLimit;
+use image::{DynamicImage, ImageReader, ImageResult};
 use numpy::ndarray::{Array2, ArrayView2};
-use pathfinding::prelude::{astar, dijkstra};
+use pathfinding::num_traits::{Bounded, Zero};
+use pathfinding::prelude::{astar, build_path, dijkstra, dijkstra_all, dijkstra_partial};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::ops::Add;
 
 /// A position in the image.
 pub type Pos2D = (u32, u32);
@@ -7,13 +15,401 @@ pub type Pos2D = (u32, u32);
 /// A position in the image with a cost.
 pub type Pos2DWithCost = (Pos2D, u32);
 
+/// A pixel type that can be used as a heatmap value. Pixel values are step costs
+/// and are accumulated as `u32`.
+pub trait HeatmapValue: Copy + PartialEq + Into<u32> + Send + Sync {
+    /// The number of distinct values of this type, i.e. the length of a complete cost table.
+    const VALUE_COUNT: usize;
+}
+
+impl HeatmapValue for u8 {
+    const VALUE_COUNT: usize = 1 << 8;
+}
+
+impl HeatmapValue for u16 {
+    const VALUE_COUNT: usize = 1 << 16;
+}
+
+/// Which neighbouring cells a cell is connected to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Only the 4 cardinal neighbours (up, down, left, right).
+    Four,
+    /// The 4 cardinal neighbours plus the 4 diagonal ones.
+    #[default]
+    Eight,
+}
+
+/// Which borders of the heatmap wrap around to the opposite side, e.g. for a cylindrical panorama.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Wrap {
+    /// The borders are walls.
+    #[default]
+    None,
+    /// The left and right borders are joined.
+    X,
+    /// The top and bottom borders are joined.
+    Y,
+    /// Both pairs of borders are joined, making the heatmap a torus.
+    XY,
+}
+
+impl Wrap {
+    fn wraps_x(self) -> bool {
+        matches!(self, Wrap::X | Wrap::XY)
+    }
+
+    fn wraps_y(self) -> bool {
+        matches!(self, Wrap::Y | Wrap::XY)
+    }
+
+    /// The number of steps between `from` and `to` along each axis, going across a joined
+    /// border when that is shorter.
+    fn deltas(self, from: Pos2D, to: Pos2D, (width, height): (usize, usize)) -> (u32, u32) {
+        let dx = from.0.abs_diff(to.0);
+        let dy = from.1.abs_diff(to.1);
+        (
+            if self.wraps_x() {
+                dx.min(width as u32 - dx)
+            } else {
+                dx
+            },
+            if self.wraps_y() {
+                dy.min(height as u32 - dy)
+            } else {
+                dy
+            },
+        )
+    }
+}
+
+/// Which of several equally cheap paths a search prefers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Whichever path the search happens to find first.
+    #[default]
+    None,
+    /// The path with the fewest steps.
+    FewestSteps,
+    /// The path with the shortest Euclidean length, i.e. 1 per cardinal step and √2 per
+    /// diagonal step.
+    ShortestEuclidean,
+}
+
+impl TieBreak {
+    /// The secondary cost of the step from `from` to `to`, which is accumulated along paths
+    /// to break ties between equal costs. Euclidean lengths are in thousandths of a pixel.
+    fn step(self, from: Pos2D, to: Pos2D, dim: (usize, usize), wrap: Wrap) -> u64 {
+        match self {
+            TieBreak::None => 0,
+            TieBreak::FewestSteps => 1,
+            TieBreak::ShortestEuclidean => {
+                let (dx, dy) = wrap.deltas(from, to, dim);
+                ((dx as f64).hypot(dy as f64) * 1000.0).round() as u64
+            }
+        }
+    }
+}
+
+/// Multipliers applied to the cost of steps in each direction, e.g. to make moving up harder
+/// than moving down. Up is towards smaller y, i.e. the top of an image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirectionWeights {
+    pub up: f64,
+    pub down: f64,
+    pub left: f64,
+    pub right: f64,
+}
+
+impl Default for DirectionWeights {
+    fn default() -> Self {
+        Self {
+            up: 1.0,
+            down: 1.0,
+            left: 1.0,
+            right: 1.0,
+        }
+    }
+}
+
+impl DirectionWeights {
+    /// The weight of a step by `(dx, dy)`, each -1, 0, or 1. A diagonal step takes the average
+    /// of its horizontal and vertical weights.
+    pub fn of_step(&self, (dx, dy): (i32, i32)) -> f64 {
+        let horizontal = match dx {
+            ..0 => Some(self.left),
+            0 => None,
+            1.. => Some(self.right),
+        };
+        let vertical = match dy {
+            ..0 => Some(self.up),
+            0 => None,
+            1.. => Some(self.down),
+        };
+
+        match (horizontal, vertical) {
+            (Some(h), Some(v)) => (h + v) / 2.0,
+            (Some(weight), None) | (None, Some(weight)) => weight,
+            (None, None) => 1.0,
+        }
+    }
+
+    /// Whether every direction has the same weight.
+    pub fn is_uniform(&self) -> bool {
+        self.up == self.down && self.down == self.left && self.left == self.right
+    }
+}
+
+/// Receives the events of a search as it runs, e.g. to animate the search frontier or report
+/// progress. Both methods do nothing by default, so observers only implement the events they
+/// need, and `()` observes nothing. Searches may run on another thread, so observers must be
+/// `Sync`, and use interior mutability to record events.
+pub trait SearchObserver: Sync {
+    /// Called with each cell the search takes from the frontier to expand.
+    fn on_pop(&self, _pos: Pos2D) {}
+
+    /// Called with each cell the search generates while expanding `from`, i.e. offers to the
+    /// frontier, whether or not it improves on a cheaper way already known to reach it.
+    fn on_push(&self, _from: Pos2D, _pos: Pos2D) {}
+}
+
+impl SearchObserver for () {}
+
+impl std::fmt::Debug for dyn SearchObserver + '_ {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SearchObserver")
+    }
+}
+
+/// Options controlling how a heatmap is turned into a search graph.
+#[derive(Clone, Copy, Debug)]
+pub struct SearchOptions2D<'a, T = u8> {
+    /// A value that, if provided, marks cells that can never be entered.
+    pub impassable: Option<T>,
+    /// A value that, if provided, blocks every cell whose value is strictly above it.
+    pub block_above: Option<u32>,
+    /// A value that, if provided, blocks every cell whose value is strictly below it.
+    pub block_below: Option<u32>,
+    /// Which neighbours each cell is connected to (default: 8-connected).
+    pub connectivity: Connectivity,
+    /// An optional movement kernel of `(dx, dy)` offsets, such as knight moves, that overrides
+    /// `connectivity`. The offsets must be nonzero and distinct. Steps longer than one cell
+    /// cost their Euclidean length times the average cost of the source and destination
+    /// pixels, like diagonal steps. [`JumpPointSearch2D`] and [`ThetaStar2D`] do not support it.
+    pub neighbours: Option<&'a [(i32, i32)]>,
+    /// The longest cardinal step, in cells (default: 1). Above 1, a cell may also jump straight
+    /// left, right, up, or down by up to `reach` cells in a single step, costing the cost of the
+    /// cell it lands on times its length, whatever the cells it crosses cost. Jumps can thus
+    /// skip costly barriers up to `reach - 1` cells thick, but never cross a blocked cell.
+    /// Ignored with a movement kernel. [`JumpPointSearch2D`] and [`ThetaStar2D`] do not support it.
+    pub reach: u32,
+    /// Which borders wrap around to the opposite side (default: none).
+    pub wrap: Wrap,
+    /// An optional mask with the same shape as the heatmap, where `true` marks a blocked cell.
+    pub mask: Option<ArrayView2<'a, bool>>,
+    /// An optional lookup table mapping each pixel value to its step cost. It must have an entry
+    /// for every possible pixel value. If not provided, the pixel value itself is the cost.
+    pub cost_table: Option<&'a [u32]>,
+    /// An optional speed map with the same shape as the heatmap, dividing the cost of each
+    /// cell: entering a cell costs its pixel cost divided by its speed, rounded down, so faster
+    /// regions such as roads are cheaper. Cells with a speed of 0 can never be entered.
+    /// [`JumpPointSearch2D`] does not support it.
+    pub speed: Option<ArrayView2<'a, u8>>,
+    /// An optional time budget. Once it has passed the search stops and returns no path.
+    pub deadline: Option<&'a SearchDeadline>,
+    /// An optional cap on the number of expanded cells. Once it is exceeded the search stops
+    /// and returns no path. [`JumpPointSearch2D`] also counts the cells its jumps scan.
+    pub node_limit: Option<&'a NodeLimit>,
+    /// An optional observer notified of each cell the search expands and generates.
+    pub observer: Option<&'a dyn SearchObserver>,
+    /// An optional, inclusive maximum path cost. Cells that can only be reached above it are
+    /// not expanded, and no path costing more is returned.
+    pub max_cost: Option<u32>,
+    /// An extra cost added to every step that changes the direction of travel (default: 0).
+    /// A nonzero penalty makes the search track the direction each cell was entered from,
+    /// multiplying the nodes it may expand by up to the number of neighbours. Only
+    /// [`Dijkstra2D`], [`AStar2D`], and [`Fringe2D`] take it into account.
+    pub turn_penalty: u32,
+    /// Optional multipliers of the step costs in each direction. Each step cost is multiplied
+    /// by the weight of its direction and rounded. If not provided, every direction weighs 1.
+    pub direction_weights: Option<DirectionWeights>,
+    /// Optional steps between adjacent cells that can never be taken, in either direction,
+    /// whichever order the two cells are stored in. [`JumpPointSearch2D`] cannot jump around
+    /// them, so it runs [`AStar2D`] with the [`Heuristic2D::Octile`] heuristic instead.
+    pub blocked_edges: Option<&'a HashSet<(Pos2D, Pos2D)>>,
+    /// Whether diagonal steps are forbidden when either of the two cells they pass between is
+    /// blocked, so paths never squeeze between obstacles touching at a corner (default: false).
+    /// Cells count as blocked by their value or by the mask. [`JumpPointSearch2D`] does not
+    /// support it.
+    pub no_corner_cutting: bool,
+    /// Which of several equally cheap paths to return (default: any of them). It ranks the
+    /// frontier by cost, then by the secondary criterion, so the cost found does not change.
+    /// Only [`Dijkstra2D`], [`AStar2D`], and [`Fringe2D`] take it into account, and only
+    /// without a `turn_penalty`.
+    pub tie_break: TieBreak,
+}
+
+impl<T> Default for SearchOptions2D<'_, T> {
+    fn default() -> Self {
+        Self {
+            impassable: None,
+            block_above: None,
+            block_below: None,
+            connectivity: Connectivity::default(),
+            neighbours: None,
+            reach: 1,
+            wrap: Wrap::default(),
+            mask: None,
+            cost_table: None,
+            speed: None,
+            deadline: None,
+            node_limit: None,
+            observer: None,
+            max_cost: None,
+            turn_penalty: 0,
+            direction_weights: None,
+            blocked_edges: None,
+            no_corner_cutting: false,
+            tie_break: TieBreak::None,
+        }
+    }
+}
+
+impl<T> SearchOptions2D<'_, T> {
+    /// The offsets of the cells each cell is connected to: the movement kernel if one was
+    /// given, or the neighbours under `connectivity` otherwise.
+    pub fn neighbour_offsets(&self) -> &[(i32, i32)] {
+        self.neighbours
+            .unwrap_or_else(|| neighbour_offsets(self.connectivity))
+    }
+
+    /// The offsets of the cardinal jumps of 2 to `reach` cells, none with a movement kernel.
+    fn jump_offsets(&self) -> impl Iterator<Item = (i32, i32)> + use<T> {
+        let longest = match self.neighbours {
+            Some(_) => 1,
+            None => self.reach as i32,
+        };
+        (2..=longest).flat_map(|k| [(-k, 0), (k, 0), (0, -k), (0, k)])
+    }
+
+    /// The offsets of every step a cell can take: its neighbour offsets, then its jumps.
+    pub fn step_offsets(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.neighbour_offsets()
+            .iter()
+            .copied()
+            .chain(self.jump_offsets())
+    }
+
+    /// Whether the cell at `pos` is blocked by the obstacle mask.
+    pub fn is_masked(&self, pos: Pos2D) -> bool {
+        self.mask
+            .is_some_and(|mask| mask[[pos.0 as usize, pos.1 as usize]])
+    }
+
+    /// Whether the cell at `pos` has a speed of 0 in the speed map, which blocks it.
+    pub fn is_halted(&self, pos: Pos2D) -> bool {
+        self.speed
+            .is_some_and(|speed| speed[[pos.0 as usize, pos.1 as usize]] == 0)
+    }
+
+    /// Whether the step between the adjacent cells `from` and `to` is blocked, in either direction.
+    pub fn is_blocked_edge(&self, from: Pos2D, to: Pos2D) -> bool {
+        self.blocked_edges
+            .is_some_and(|edges| edges.contains(&(from, to)) || edges.contains(&(to, from)))
+    }
+
+    /// Record an expansion against the deadline and the node limit, returning whether the
+    /// search should stop.
+    fn should_stop(&self) -> bool {
+        self.deadline.is_some_and(SearchDeadline::tick)
+            || self.node_limit.is_some_and(NodeLimit::tick)
+    }
+
+    /// Report an expanded cell to the observer, if any.
+    fn notify_expansion(&self, pos: Pos2D) {
+        if let Some(observer) = self.observer {
+            observer.on_pop(pos);
+        }
+    }
+
+    /// Report the cells generated by expanding `from` to the observer, if any.
+    fn notify_generated(&self, from: Pos2D, neighbours: impl IntoIterator<Item = Pos2D>) {
+        if let Some(observer) = self.observer {
+            for pos in neighbours {
+                observer.on_push(from, pos);
+            }
+        }
+    }
+
+    /// Whether the deadline has already been noticed to have passed, or the node limit to
+    /// have been exceeded.
+    fn was_stopped(&self) -> bool {
+        self.deadline.is_some_and(SearchDeadline::has_expired)
+            || self.node_limit.is_some_and(NodeLimit::was_exceeded)
+    }
+}
+
+impl<T: HeatmapValue> SearchOptions2D<'_, T> {
+    /// Whether cells holding `value` can never be entered, being impassable or outside the
+    /// thresholds. Pixel values are compared as they are, before any cost table.
+    pub fn is_blocked_value(&self, value: T) -> bool {
+        let level: u32 = value.into();
+        self.impassable == Some(value)
+            || self.block_above.is_some_and(|above| level > above)
+            || self.block_below.is_some_and(|below| level < below)
+    }
+
+    /// Whether a path of the given cost fits the maximum path cost, if any.
+    fn fits_budget(&self, cost: u32) -> bool {
+        self.max_cost.is_none_or(|max_cost| cost <= max_cost)
+    }
+
+    /// The step cost of entering a cell with the given pixel value.
+    pub fn cost_of(&self, value: T) -> u32 {
+        let value: u32 = value.into();
+        match self.cost_table {
+            Some(table) => table[value as usize],
+            None => value,
+        }
+    }
+
+    /// The step cost of entering the cell at `pos` of `array`: the cost of its pixel value,
+    /// divided by its speed if there is a speed map.
+    pub fn cell_cost(&self, array: ArrayView2<T>, pos: Pos2D) -> u32 {
+        let (x, y) = (pos.0 as usize, pos.1 as usize);
+        let cost = self.cost_of(array[[x, y]]);
+        match self.speed {
+            Some(speed) => cost / speed[[x, y]].max(1) as u32,
+            None => cost,
+        }
+    }
+}
+
+/// Counters describing how much work a search did.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Number of times a cell was taken from the frontier and its neighbours generated.
+    pub nodes_expanded: usize,
+    /// Number of neighbours generated across all expansions.
+    pub nodes_generated: usize,
+    /// Largest number of discovered cells that had not been expanded yet.
+    pub peak_frontier_size: usize,
+}
+
 // MARK: Helpers
 
-/// Load a PNG image and convert it to a 2D ndarray (grayscale).
-/// Returns an Array2<u8> with shape (width, height).
-pub fn load_png_to_ndarray(path: &str) -> Array2<u8> {
-    let img = image::open(path)
-        .expect(&format!("Failed to open image at {}", path))
+/// Open and decode an image in any format supported by the `image` crate. The format is
+/// detected from the file contents, so the extension does not need to match it.
+pub fn open_image(path: &str) -> ImageResult<DynamicImage> {
+    ImageReader::open(path)?.with_guessed_format()?.decode()
+}
+
+/// Load an image in any supported format (PNG, JPEG, BMP, TIFF, ...) and convert it to a
+/// 2D ndarray (grayscale). Returns an Array2<u8> with shape (width, height).
+/// Panics with the offending path if the file cannot be opened or decoded.
+pub fn load_image_to_ndarray(path: &str) -> Array2<u8> {
+    let img = open_image(path)
+        .unwrap_or_else(|err| panic!("Failed to load image at {}: {}", path, err))
         .to_luma8();
 
     let (width, height) = img.dimensions();
@@ -28,192 +424,2885 @@ pub fn load_png_to_ndarray(path: &str) -> Array2<u8> {
     array
 }
 
+/// Load a PNG image and convert it to a 2D ndarray (grayscale).
+/// Kept for compatibility: other formats load as well, see [`load_image_to_ndarray`].
+pub fn load_png_to_ndarray(path: &str) -> Array2<u8> {
+    load_image_to_ndarray(path)
+}
+
+/// Load an image and combine its grayscale value with its alpha channel into a 2D cost
+/// ndarray with shape (width, height). Alpha is read as confidence: each cost is
+/// `value / max(alpha, 1) * 255`, clamped to 255, so fully opaque pixels keep their value
+/// and more transparent pixels become more expensive. Images without alpha load unchanged.
+/// Panics with the offending path if the file cannot be opened or decoded.
+pub fn load_png_weighted(path: &str) -> Array2<u8> {
+    let img = open_image(path)
+        .unwrap_or_else(|err| panic!("Failed to load image at {}: {}", path, err))
+        .to_luma_alpha8();
+
+    let (width, height) = img.dimensions();
+    Array2::from_shape_fn((width as usize, height as usize), |(x, y)| {
+        let [value, alpha] = img.get_pixel(x as u32, y as u32).0;
+        let cost = value as u32 * 255 / (alpha as u32).max(1);
+        cost.min(255) as u8
+    })
+}
+
+/// Offsets of the neighbours of a cell, cardinal neighbours (left, right, up, down) first.
+/// The set is symmetric, so it also describes the cells a cell can be entered from.
+const NEIGHBOUR_OFFSETS: [(i32, i32); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+/// The neighbour offsets a cell is connected to under the given connectivity.
+fn neighbour_offsets(connectivity: Connectivity) -> &'static [(i32, i32)] {
+    match connectivity {
+        Connectivity::Four => &NEIGHBOUR_OFFSETS[..4],
+        Connectivity::Eight => &NEIGHBOUR_OFFSETS,
+    }
+}
+
+/// The coordinate `coord` moved back into `0..len`, or `None` if it lies outside and the
+/// axis does not wrap.
+fn wrap_coordinate(coord: i64, len: usize, wraps: bool) -> Option<u32> {
+    if wraps {
+        Some(coord.rem_euclid(len as i64) as u32)
+    } else if (0..len as i64).contains(&coord) {
+        Some(coord as u32)
+    } else {
+        None
+    }
+}
+
+/// The position at `offset` from `pos`, or `None` if it lies outside an image border that
+/// does not wrap.
+fn offset_position(
+    pos: Pos2D,
+    (dx, dy): (i32, i32),
+    (width, height): (usize, usize),
+    wrap: Wrap,
+) -> Option<Pos2D> {
+    Some((
+        wrap_coordinate(pos.0 as i64 + dx as i64, width, wrap.wraps_x())?,
+        wrap_coordinate(pos.1 as i64 + dy as i64, height, wrap.wraps_y())?,
+    ))
+}
+
+/// The cost of stepping from `from` to the adjacent cell `to`, or `None` if `to` cannot be entered.
+///
+/// A cardinal step costs the cost of the destination pixel. A diagonal step costs
+/// √2 times the average cost of the source and destination pixels, so diagonals are not
+/// unfairly cheap compared to the two cardinal steps they replace. Longer steps of a custom
+/// movement kernel likewise cost their Euclidean length times that average, while jumps of
+/// more than one cell cost their length times the cost of the cell they land on. Jumps cannot
+/// cross blocked cells.
+fn step_cost<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    from: Pos2D,
+    to: Pos2D,
+    options: &SearchOptions2D<T>,
+) -> Option<u32> {
+    let is_blocked = |(x, y): Pos2D| {
+        options.is_blocked_value(array[[x as usize, y as usize]])
+            || options.is_masked((x, y))
+            || options.is_halted((x, y))
+    };
+    if is_blocked(to) || options.is_blocked_edge(from, to) {
+        return None;
+    }
+
+    let (dx, dy) = options.wrap.deltas(from, to, array.dim());
+    let is_diagonal = dx == 1 && dy == 1;
+    // The two cells a diagonal step passes between are its corners, wrapped borders included
+    if is_diagonal
+        && options.no_corner_cutting
+        && (is_blocked((to.0, from.1)) || is_blocked((from.0, to.1)))
+    {
+        return None;
+    }
+
+    let value = options.cell_cost(array, to);
+    let is_jump = options.neighbours.is_none() && dx.min(dy) == 0 && dx.max(dy) > 1;
+    let cost = if dx + dy <= 1 {
+        value
+    } else if is_jump {
+        let (jump_x, jump_y) = step_offset(from, to, array.dim(), options)?;
+        let (unit_x, unit_y) = (jump_x.signum(), jump_y.signum());
+        let length = jump_x.abs().max(jump_y.abs());
+        for i in 1..length {
+            let crossed =
+                offset_position(from, (unit_x * i, unit_y * i), array.dim(), options.wrap)?;
+            if is_blocked(crossed) {
+                return None;
+            }
+        }
+        value.saturating_mul(length as u32)
+    } else {
+        let source = options.cell_cost(array, from);
+        let sum = source as f64 + value as f64;
+        if is_diagonal {
+            // √2 * (source + value) / 2 == (source + value) / √2
+            (sum * std::f64::consts::FRAC_1_SQRT_2).round() as u32
+        } else {
+            (sum * (dx as f64).hypot(dy as f64) / 2.0).round() as u32
+        }
+    };
+
+    match options.direction_weights {
+        Some(weights) => {
+            let offset = step_offset(from, to, array.dim(), options)?;
+            Some((cost as f64 * weights.of_step(offset)).round() as u32)
+        }
+        None => Some(cost),
+    }
+}
+
+/// The cost of the grid move from `from` to `to`, or `None` if `to` is not a neighbour of
+/// `from` or cannot be entered.
+fn grid_step_cost<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    from: Pos2D,
+    to: Pos2D,
+    options: &SearchOptions2D<T>,
+) -> Option<u32> {
+    let (dx, dy) = options.wrap.deltas(from, to, array.dim());
+    let is_neighbour = match (options.neighbours, options.connectivity) {
+        (Some(_), _) => step_offset(from, to, array.dim(), options).is_some(),
+        (None, _) if dx.min(dy) == 0 && dx.max(dy) > 1 => dx.max(dy) <= options.reach,
+        (None, Connectivity::Four) => dx + dy == 1,
+        (None, Connectivity::Eight) => dx.max(dy) == 1,
+    };
+
+    if is_neighbour {
+        step_cost(array, from, to, options)
+    } else {
+        None
+    }
+}
+
 /// Find the possible neighbours and their costs for a given pixel in a 2D ndarray.
 /// Returns a vector of tuples, where each tuple contains a position and a cost.
 ///
 /// # Arguments
 /// * `array` - The 2D ndarray to find neighbours in.
 /// * `pos` - The position to find neighbours for.
-/// * `impassable` - An optional value that, if provided, will be used to filter out neighbours that have this value.
+/// * `options` - The options describing which neighbours are reachable.
 ///
 /// # Returns
 /// * `Vec<Pos2DWithCost>` - A vector of tuples, where each tuple contains a position and a cost.
-fn find_neighbours_with_cost(
-    array: ArrayView2<u8>,
+fn find_neighbours_with_cost<T: HeatmapValue>(
+    array: ArrayView2<T>,
     pos: Pos2D,
-    impassable: Option<u8>,
+    options: &SearchOptions2D<T>,
 ) -> Vec<Pos2DWithCost> {
-    let mut neighbours = Vec::new();
+    options
+        .step_offsets()
+        .filter_map(|offset| {
+            let to = offset_position(pos, offset, array.dim(), options.wrap)?;
+            Some((to, step_cost(array, pos, to, options)?))
+        })
+        .collect()
+}
 
-    let (x, y) = pos;
-    let (width, height) = array.dim();
-    let height = height as u32;
-    let width = width as u32;
+/// Find the cells a given pixel can be entered from, together with the cost of that step.
+/// This is the reverse of [`find_neighbours_with_cost`], used by backward searches.
+fn find_predecessors_with_cost<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    pos: Pos2D,
+    options: &SearchOptions2D<T>,
+) -> Vec<Pos2DWithCost> {
+    options
+        .step_offsets()
+        .filter_map(|(dx, dy)| {
+            let from = offset_position(pos, (-dx, -dy), array.dim(), options.wrap)?;
+            Some((from, step_cost(array, from, pos, options)?))
+        })
+        .collect()
+}
 
-    // Cardinal neighbors (up, down, left, right)
-    if x > 0 {
-        neighbours.push(((x - 1, y), array[[(x - 1) as usize, y as usize]] as u32));
-    }
-    if x < width - 1 {
-        neighbours.push(((x + 1, y), array[[(x + 1) as usize, y as usize]] as u32));
+/// Records search statistics as cells are expanded.
+/// Only created when statistics are requested, so plain searches pay no bookkeeping cost.
+struct StatsTracker<'s> {
+    stats: &'s mut SearchStats,
+    discovered: HashSet<Pos2D>,
+    expanded: HashSet<Pos2D>,
+}
+
+impl<'s> StatsTracker<'s> {
+    fn new(stats: &'s mut SearchStats, start_pos: Pos2D) -> Self {
+        Self {
+            stats,
+            discovered: HashSet::from([start_pos]),
+            expanded: HashSet::new(),
+        }
     }
-    if y > 0 {
-        neighbours.push(((x, y - 1), array[[x as usize, (y - 1) as usize]] as u32));
+
+    fn record(&mut self, pos: Pos2D, neighbours: &[Pos2DWithCost]) {
+        self.stats.nodes_expanded += 1;
+        self.stats.nodes_generated += neighbours.len();
+
+        self.expanded.insert(pos);
+        self.discovered.extend(neighbours.iter().map(|&(p, _)| p));
+
+        let frontier_size = self.discovered.len() - self.expanded.len();
+        self.stats.peak_frontier_size = self.stats.peak_frontier_size.max(frontier_size);
     }
-    if y < height - 1 {
-        neighbours.push(((x, y + 1), array[[x as usize, (y + 1) as usize]] as u32));
+}
+
+/// Expand a cell into its neighbours, recording statistics if a tracker is present.
+/// Once the deadline has passed or the node limit is exceeded no neighbours are returned, so
+/// the search runs dry. Neighbours that only a path above the cost budget can reach are left out.
+fn expand<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    pos: Pos2D,
+    options: &SearchOptions2D<T>,
+    tracker: &mut Option<StatsTracker>,
+    budget: &mut Option<CostBudget<Pos2D>>,
+) -> Vec<Pos2DWithCost> {
+    if options.should_stop() {
+        return Vec::new();
     }
 
-    // Diagonal neighbors
-    if x > 0 && y > 0 {
-        neighbours.push((
-            (x - 1, y - 1),
-            array[[(x - 1) as usize, (y - 1) as usize]] as u32,
-        ));
+    options.notify_expansion(pos);
+    let mut neighbours = find_neighbours_with_cost(array, pos, options);
+    if let Some(budget) = budget {
+        budget.prune(pos, &mut neighbours);
     }
-    if x < width - 1 && y > 0 {
-        neighbours.push((
-            (x + 1, y - 1),
-            array[[(x + 1) as usize, (y - 1) as usize]] as u32,
-        ));
+    options.notify_generated(pos, neighbours.iter().map(|&(p, _)| p));
+
+    if let Some(tracker) = tracker {
+        tracker.record(pos, &neighbours);
     }
-    if x > 0 && y < height - 1 {
-        neighbours.push((
-            (x - 1, y + 1),
-            array[[(x - 1) as usize, (y + 1) as usize]] as u32,
-        ));
+
+    neighbours
+}
+
+/// A cell together with the offset of the step that entered it, `None` for the start cell.
+/// Searches that penalize turns run over these, so a cell may be expanded once per heading.
+type HeadedPos2D = (Pos2D, Option<(i32, i32)>);
+
+/// Successor function handed to the search algorithms when turns are penalized.
+type HeadedSuccessors<'s> = dyn FnMut(&HeadedPos2D) -> Vec<(HeadedPos2D, u32)> + 's;
+
+/// Like [`expand`], over cells that remember their heading. Steps that change the heading
+/// cost an extra `options.turn_penalty`.
+fn expand_headed<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    (pos, heading): HeadedPos2D,
+    options: &SearchOptions2D<T>,
+    tracker: &mut Option<StatsTracker>,
+    budget: &mut Option<CostBudget<HeadedPos2D>>,
+) -> Vec<(HeadedPos2D, u32)> {
+    if options.should_stop() {
+        return Vec::new();
     }
-    if x < width - 1 && y < height - 1 {
-        neighbours.push((
-            (x + 1, y + 1),
-            array[[(x + 1) as usize, (y + 1) as usize]] as u32,
-        ));
+
+    options.notify_expansion(pos);
+    let mut neighbours: Vec<(HeadedPos2D, u32)> = options
+        .step_offsets()
+        .filter_map(|offset| {
+            let to = offset_position(pos, offset, array.dim(), options.wrap)?;
+            let cost = step_cost(array, pos, to, options)?;
+            let offset = heading_of(offset);
+            let turn = if heading.is_some_and(|heading| heading != offset) {
+                options.turn_penalty
+            } else {
+                0
+            };
+            Some(((to, Some(offset)), cost.saturating_add(turn)))
+        })
+        .collect();
+    if let Some(budget) = budget {
+        budget.prune((pos, heading), &mut neighbours);
     }
+    options.notify_generated(pos, neighbours.iter().map(|&((p, _), _)| p));
 
-    if let Some(impassable) = impassable {
-        neighbours.retain(|(_, cost)| *cost != impassable as u32);
+    if let Some(tracker) = tracker {
+        let cells: Vec<Pos2DWithCost> = neighbours.iter().map(|&((p, _), c)| (p, c)).collect();
+        tracker.record(pos, &cells);
     }
 
     neighbours
 }
 
-// MARK: Pathfinders
-
-pub trait ImagePathfinder2D {
-    /// Find a path in a heatmap in 2D space. The heatmap must be represented by a 2D ndarray.
-    ///
-    /// # Arguments
-    ///
-    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
-    /// * `start_pos` - The start position (x, y).
-    /// * `end_pos` - The end position (x, y).
-    ///
-    /// # Returns
-    ///
-    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and the total cost, or `None` if no path was found.
-    fn find_path_in_heatmap(
-        &self,
-        array: ArrayView2<u8>,
-        start_pos: Pos2D,
-        end_pos: Pos2D,
-        impassable: Option<u8>,
-    ) -> Option<(Vec<Pos2D>, u32)>;
+/// The heading of a step by `offset`. Cardinal steps of any length share the heading of the
+/// single step in their direction, so a jump does not turn.
+fn heading_of((dx, dy): (i32, i32)) -> (i32, i32) {
+    if dx == 0 || dy == 0 {
+        (dx.signum(), dy.signum())
+    } else {
+        (dx, dy)
+    }
 }
 
-// MARK: Dijkstra
+/// Run `search` over cells that remember their heading, so that turns can be penalized,
+/// and return the cheapest path to any of the end positions.
+///
+/// `search` is given the start, the successor function, the heuristic lifted to headed cells,
+/// and the success predicate, like the searches of the `pathfinding` crate.
+fn find_path_with_turns<T, S>(
+    array: ArrayView2<T>,
+    start_pos: Pos2D,
+    end_positions: &[Pos2D],
+    options: &SearchOptions2D<T>,
+    stats: Option<&mut SearchStats>,
+    heuristic: impl Fn(Pos2D) -> u32,
+    search: S,
+) -> Option<(Vec<Pos2D>, u32)>
+where
+    T: HeatmapValue,
+    S: FnOnce(
+        &HeadedPos2D,
+        &mut HeadedSuccessors,
+        &dyn Fn(&HeadedPos2D) -> u32,
+        &mut dyn FnMut(&HeadedPos2D) -> bool,
+    ) -> Option<(Vec<HeadedPos2D>, u32)>,
+{
+    let start = (start_pos, None);
+    let mut tracker = stats.map(|stats| StatsTracker::new(stats, start_pos));
+    let mut budget = options
+        .max_cost
+        .map(|max_cost| CostBudget::new(max_cost, start));
+    let result = search(
+        &start,
+        &mut |&node| expand_headed(array, node, options, &mut tracker, &mut budget),
+        &|&(p, _)| heuristic(p),
+        &mut |(p, _)| end_positions.contains(p),
+    );
 
-/// A 2D pathfinder that uses Dijkstra's algorithm.
-pub struct Dijkstra2D {}
+    // The search may still reach an end while running dry after the deadline
+    if options.was_stopped() {
+        return None;
+    }
 
-impl ImagePathfinder2D for Dijkstra2D {
-    fn find_path_in_heatmap(
-        &self,
-        array: ArrayView2<u8>,
-        start_pos: Pos2D,
-        end_pos: Pos2D,
-        impassable: Option<u8>,
-    ) -> Option<(Vec<Pos2D>, u32)> {
-        let result = dijkstra(
-            &start_pos,
-            |&p| find_neighbours_with_cost(array, p, impassable),
-            |&p| p == end_pos,
-        );
+    result
+        .filter(|&(_, cost)| options.fits_budget(cost))
+        .map(|(path, cost)| (path.into_iter().map(|(p, _)| p).collect(), cost))
+}
 
-        if let Some((path, costs)) = result {
-            return Some((path, costs));
-        }
+/// A path cost together with the secondary cost of a [`TieBreak`], ordered by the cost first,
+/// so that the secondary cost only ranks paths of equal cost.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+struct TiedCost {
+    cost: u32,
+    secondary: u64,
+}
 
-        None
+impl Add for TiedCost {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        TiedCost {
+            cost: self.cost + other.cost,
+            secondary: self.secondary + other.secondary,
+        }
     }
 }
 
-// MARK: A*
-
-pub struct AStar2D {}
+impl Zero for TiedCost {
+    fn zero() -> Self {
+        TiedCost::default()
+    }
 
-impl AStar2D {
-    fn manhattan_distance(&self, pos: Pos2D, end_pos: Pos2D) -> u32 {
-        let (x1, y1) = pos;
-        let (x2, y2) = end_pos;
-        (x1.abs_diff(x2) + y1.abs_diff(y2)) as u32
+    fn is_zero(&self) -> bool {
+        *self == TiedCost::default()
     }
 }
 
-impl ImagePathfinder2D for AStar2D {
-    fn find_path_in_heatmap(
-        &self,
-        array: ArrayView2<u8>,
-        start_pos: Pos2D,
-        end_pos: Pos2D,
-        impassable: Option<u8>,
-    ) -> Option<(Vec<Pos2D>, u32)> {
-        let result = astar(
-            &start_pos,
-            |&p| find_neighbours_with_cost(array, p, impassable),
-            // the minumum cost is the manhattan distance
-            |&p| self.manhattan_distance(p, end_pos),
-            |&p| p == end_pos,
-        );
+impl Bounded for TiedCost {
+    fn min_value() -> Self {
+        TiedCost::default()
+    }
 
-        if let Some((path, costs)) = result {
-            return Some((path, costs));
+    fn max_value() -> Self {
+        TiedCost {
+            cost: u32::MAX,
+            secondary: u64::MAX,
         }
+    }
+}
 
-        None
+/// Successor function handed to the search algorithms when ties are broken.
+type TiedSuccessors<'s> = dyn FnMut(&Pos2D) -> Vec<(Pos2D, TiedCost)> + 's;
+
+/// Run `search` with costs extended by the secondary cost of `options.tie_break`, and return
+/// the cheapest path to any of the end positions that is preferred among the equally cheap ones.
+///
+/// `search` is given the start, the successor function, the heuristic lifted to tied costs,
+/// and the success predicate, like the searches of the `pathfinding` crate.
+fn find_path_with_tie_break<T, S>(
+    array: ArrayView2<T>,
+    start_pos: Pos2D,
+    end_positions: &[Pos2D],
+    options: &SearchOptions2D<T>,
+    stats: Option<&mut SearchStats>,
+    heuristic: impl Fn(Pos2D) -> u32,
+    search: S,
+) -> Option<(Vec<Pos2D>, u32)>
+where
+    T: HeatmapValue,
+    S: FnOnce(
+        &Pos2D,
+        &mut TiedSuccessors,
+        &dyn Fn(&Pos2D) -> TiedCost,
+        &mut dyn FnMut(&Pos2D) -> bool,
+    ) -> Option<(Vec<Pos2D>, TiedCost)>,
+{
+    let mut tracker = stats.map(|stats| StatsTracker::new(stats, start_pos));
+    let mut budget = options
+        .max_cost
+        .map(|max_cost| CostBudget::new(max_cost, start_pos));
+    let result = search(
+        &start_pos,
+        &mut |&pos| {
+            expand(array, pos, options, &mut tracker, &mut budget)
+                .into_iter()
+                .map(|(to, cost)| {
+                    let secondary = options.tie_break.step(pos, to, array.dim(), options.wrap);
+                    (to, TiedCost { cost, secondary })
+                })
+                .collect()
+        },
+        &|&p| TiedCost {
+            cost: heuristic(p),
+            secondary: 0,
+        },
+        &mut |p| end_positions.contains(p),
+    );
+
+    // The search may still reach an end while running dry after the deadline
+    if options.was_stopped() {
+        return None;
     }
+
+    result
+        .map(|(path, cost)| (path, cost.cost))
+        .filter(|&(_, cost)| options.fits_budget(cost))
 }
 
-// MARK: Fringe
+/// The offset of the grid move from `from` to `to`, or `None` if it is not a move of the
+/// movement kernel or, without one, if the cells are neither adjacent nor a jump apart.
+fn step_offset<T>(
+    from: Pos2D,
+    to: Pos2D,
+    dim: (usize, usize),
+    options: &SearchOptions2D<T>,
+) -> Option<(i32, i32)> {
+    options
+        .neighbours
+        .unwrap_or(&NEIGHBOUR_OFFSETS)
+        .iter()
+        .copied()
+        .chain(options.jump_offsets())
+        .find(|&offset| offset_position(from, offset, dim, options.wrap) == Some(to))
+}
 
-pub struct Fringe2D {}
+/// The smallest manhattan distance from `pos` to any of the `end_positions`, going across
+/// wrapped borders when that is shorter. Returns 0 when there are no end positions.
+fn min_manhattan_distance(
+    pos: Pos2D,
+    end_positions: &[Pos2D],
+    wrap: Wrap,
+    dim: (usize, usize),
+) -> u32 {
+    end_positions
+        .iter()
+        .map(|&end| {
+            let (dx, dy) = wrap.deltas(pos, end, dim);
+            dx + dy
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+// MARK: Simplification
+
+/// The distance from `point` to the line through `a` and `b`, or to `a` if both are the same.
+fn perpendicular_distance(point: Pos2D, a: Pos2D, b: Pos2D) -> f64 {
+    let (px, py) = (point.0 as f64, point.1 as f64);
+    let (ax, ay) = (a.0 as f64, a.1 as f64);
+    let (bx, by) = (b.0 as f64, b.1 as f64);
 
-impl Fringe2D {
-    fn manhattan_distance(&self, pos: Pos2D, end_pos: Pos2D) -> u32 {
-        let (x1, y1) = pos;
-        let (x2, y2) = end_pos;
-        (x1.abs_diff(x2) + y1.abs_diff(y2)) as u32
+    let length = (bx - ax).hypot(by - ay);
+    if length == 0.0 {
+        return (px - ax).hypot(py - ay);
     }
+
+    ((bx - ax) * (ay - py) - (ax - px) * (by - ay)).abs() / length
 }
 
-impl ImagePathfinder2D for Fringe2D {
-    fn find_path_in_heatmap(
-        &self,
-        array: ArrayView2<u8>,
-        start_pos: Pos2D,
-        end_pos: Pos2D,
-        impassable: Option<u8>,
-    ) -> Option<(Vec<Pos2D>, u32)> {
-        let result = pathfinding::prelude::fringe(
-            &start_pos,
-            |&p| find_neighbours_with_cost(array, p, impassable),
-            |&p| self.manhattan_distance(p, end_pos),
-            |&p| p == end_pos,
-        );
+/// Simplify a path into its corner points with the Ramer–Douglas–Peucker algorithm.
+/// Points closer than `tolerance` to the line between their surrounding corners are dropped;
+/// the first and last points are always kept. A tolerance of 0 returns the path unmodified.
+///
+/// # Arguments
+/// * `path` - The path to simplify.
+/// * `tolerance` - The largest distance, in pixels, a dropped point may lie from the simplified path.
+///
+/// # Returns
+/// * `Vec<Pos2D>` - The points of `path` that were kept, in order.
+pub fn simplify_path(path: &[Pos2D], tolerance: f64) -> Vec<Pos2D> {
+    if tolerance <= 0.0 || path.len() < 3 {
+        return path.to_vec();
+    }
 
-        if let Some((path, costs)) = result {
-            return Some((path, costs));
+    let mut keep = vec![false; path.len()];
+    keep[0] = true;
+    keep[path.len() - 1] = true;
+
+    // Split segments at their farthest point until every point is within tolerance
+    let mut segments = vec![(0, path.len() - 1)];
+    while let Some((first, last)) = segments.pop() {
+        let farthest = (first + 1..last)
+            .map(|i| (i, perpendicular_distance(path[i], path[first], path[last])))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((index, distance)) = farthest
+            && distance > tolerance
+        {
+            keep[index] = true;
+            segments.push((first, index));
+            segments.push((index, last));
         }
+    }
 
-        None
+    path.iter()
+        .zip(keep)
+        .filter_map(|(&point, kept)| kept.then_some(point))
+        .collect()
+}
+
+/// Drop the interior points of a path that lie on the straight line between their neighbours,
+/// leaving the path itself exactly as it was with fewer vertices. Unlike [`simplify_path`], no
+/// point off the path's lines is dropped, so runs of identical steps collapse into their ends
+/// while every turn is kept. Steps across a joined border of a `Wrap` are never merged.
+///
+/// # Arguments
+/// * `path` - The path to thin.
+///
+/// # Returns
+/// * `Vec<Pos2D>` - The points of `path` that were kept, in order, including the first and last.
+pub fn drop_collinear(path: &[Pos2D]) -> Vec<Pos2D> {
+    let mut kept: Vec<Pos2D> = Vec::with_capacity(path.len());
+    for &point in path {
+        // The last kept point is dropped if it lies between its predecessor and the new point
+        if let [.., a, b] = kept[..] {
+            let (abx, aby) = (b.0 as i64 - a.0 as i64, b.1 as i64 - a.1 as i64);
+            let (bcx, bcy) = (point.0 as i64 - b.0 as i64, point.1 as i64 - b.1 as i64);
+            if abx * bcy == aby * bcx && abx * bcx + aby * bcy > 0 {
+                kept.pop();
+            }
+        }
+        kept.push(point);
     }
+    kept
+}
+
+/// The geometric length of a path in pixels: the sum of the Euclidean distances between
+/// consecutive points, so 1 for a cardinal step and √2 for a diagonal one. Points may be any
+/// distance apart, e.g. after simplification. A step across a joined border of `wrap` is
+/// measured the short way around.
+///
+/// # Arguments
+/// * `path` - The path to measure.
+/// * `dim` - The shape of the heatmap the path lies in, (width, height).
+/// * `wrap` - Which borders of the heatmap wrap around.
+///
+/// # Returns
+/// * `f64` - The length of the path, 0 for an empty or single-point path.
+pub fn path_length(path: &[Pos2D], dim: (usize, usize), wrap: Wrap) -> f64 {
+    path.windows(2)
+        .map(|pair| {
+            let (dx, dy) = wrap.deltas(pair[0], pair[1], dim);
+            (dx as f64).hypot(dy as f64)
+        })
+        .sum()
+}
+
+/// Whether any point of a path lies on the first or last row or column of the heatmap, which
+/// may mean the optimal route was clipped by the image extent. Borders joined by `wrap` do not
+/// clip anything, so they are not counted.
+///
+/// # Arguments
+/// * `path` - The path to check.
+/// * `dim` - The shape of the heatmap the path lies in, (width, height).
+/// * `wrap` - Which borders of the heatmap wrap around.
+pub fn touches_border(path: &[Pos2D], (width, height): (usize, usize), wrap: Wrap) -> bool {
+    let on_edge = |coord: u32, len: usize| coord == 0 || coord as usize + 1 == len;
+    path.iter().any(|&(x, y)| {
+        (!wrap.wraps_x() && on_edge(x, width)) || (!wrap.wraps_y() && on_edge(y, height))
+    })
+}
+
+// MARK: Reachability
+
+/// Check whether the end can be reached from the start, without finding a path.
+///
+/// Runs a breadth-first search that ignores step costs and stops as soon as the end is
+/// touched, so no costs are accumulated and no path is reconstructed. Only the options that
+/// decide which cells can be entered apply: `impassable`, the block range, `mask`,
+/// `connectivity`, and `wrap`. The search is not bounded by `max_cost` or a deadline.
+///
+/// # Arguments
+/// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+/// * `start_pos` - The start position (x, y).
+/// * `end_pos` - The end position (x, y).
+/// * `options` - The options describing which cells can be entered.
+///
+/// # Returns
+/// * `bool` - Whether any path from the start to the end exists.
+pub fn is_reachable<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    start_pos: Pos2D,
+    end_pos: Pos2D,
+    options: &SearchOptions2D<T>,
+) -> bool {
+    if start_pos == end_pos {
+        return true;
+    }
+
+    let mut seen = Array2::from_elem(array.dim(), false);
+    seen[index(start_pos)] = true;
+    let mut queue = VecDeque::from([start_pos]);
+
+    while let Some(pos) = queue.pop_front() {
+        for (next, _) in find_neighbours_with_cost(array, pos, options) {
+            if next == end_pos {
+                return true;
+            }
+            if !std::mem::replace(&mut seen[index(next)], true) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    false
+}
+
+// MARK: Pathfinders
+
+/// A pathfinder over 2D heatmaps whose pixels are of type `T`.
+///
+/// Searches are deterministic: among paths of equal cost, the same one is returned on every run.
+/// Ties are broken by the order in which cells are discovered, which follows the fixed order of
+/// the neighbour offsets, or by the lowest `(x, y)` for [`Dijkstra2DBidirectional`] and [`ThetaStar2D`].
+///
+/// Paths are always ordered from the start (first point) to the end (last point), including for
+/// searches that run partly backwards from the end, like [`Dijkstra2DBidirectional`].
+pub trait ImagePathfinder2D<T: HeatmapValue = u8> {
+    /// Find a path in a heatmap in 2D space. The heatmap must be represented by a 2D ndarray.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_pos` - The end position (x, y).
+    /// * `impassable` - An optional value that, if provided, marks cells that can never be entered.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and the total cost, or `None` if no path was found.
+    fn find_path_in_heatmap(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        impassable: Option<T>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        self.find_path_in_heatmap_with_observer(array, start_pos, end_pos, impassable, &())
+    }
+
+    /// Like [`ImagePathfinder2D::find_path_in_heatmap`], notifying `observer` of each cell the
+    /// search expands and generates as it runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_pos` - The end position (x, y).
+    /// * `impassable` - An optional value that, if provided, marks cells that can never be entered.
+    /// * `observer` - The observer of the search events.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and the total cost, or `None` if no path was found.
+    fn find_path_in_heatmap_with_observer(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        impassable: Option<T>,
+        observer: &dyn SearchObserver,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        let options = SearchOptions2D {
+            impassable,
+            observer: Some(observer),
+            ..Default::default()
+        };
+        self.find_path_with_options(array, start_pos, end_pos, &options)
+    }
+
+    /// Find a path in a heatmap in 2D space, with full control over the search graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_pos` - The end position (x, y).
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and the total cost, or `None` if no path was found.
+    fn find_path_with_options(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        options: &SearchOptions2D<T>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        self.find_path_to_any(array, start_pos, &[end_pos], options, None)
+    }
+
+    /// Find the cost of the cheapest path in a heatmap, without building the path itself,
+    /// e.g. to score many candidate start and end pairs. By default the path is found and
+    /// dropped; [`Dijkstra2D`] runs a search that tracks no parents instead, and so does
+    /// [`AStar2D`] when its heuristic cannot overestimate.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_pos` - The end position (x, y).
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The cost that [`ImagePathfinder2D::find_path_with_options`] would return,
+    ///   or `None` if no path was found.
+    fn path_cost(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        options: &SearchOptions2D<T>,
+    ) -> Option<u32> {
+        self.find_path_with_options(array, start_pos, end_pos, options)
+            .map(|(_, cost)| cost)
+    }
+
+    /// Find the cheapest path from a start position to whichever end position is cheapest to reach.
+    ///
+    /// End positions are checked with a linear scan, which is intended for a modest number
+    /// of candidates.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_positions` - The candidate end positions (x, y).
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    /// * `stats` - If provided, search statistics are accumulated into it.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and the total cost, or `None` if no path was found.
+    ///   The last point of the path is the end position that was reached. If `start_pos` is itself
+    ///   an end position, the path is `[start_pos]` with a cost of 0.
+    fn find_path_to_any(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_positions: &[Pos2D],
+        options: &SearchOptions2D<T>,
+        stats: Option<&mut SearchStats>,
+    ) -> Option<(Vec<Pos2D>, u32)>;
+
+    /// Find the cheapest path connecting any of the start positions to any of the end positions.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_positions` - The candidate start positions (x, y).
+    /// * `end_positions` - The candidate end positions (x, y).
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    /// * `stats` - If provided, search statistics of every search run are accumulated into it.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and the total cost, or `None` if no path was found.
+    ///   The first and last points of the path are the start and end positions that were used.
+    fn find_path_between(
+        &self,
+        array: ArrayView2<T>,
+        start_positions: &[Pos2D],
+        end_positions: &[Pos2D],
+        options: &SearchOptions2D<T>,
+        mut stats: Option<&mut SearchStats>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        // Run the search from each start position and keep the cheapest path to any end
+        let mut best_path: Option<(Vec<Pos2D>, u32)> = None;
+
+        for &start in start_positions {
+            let result =
+                self.find_path_to_any(array, start, end_positions, options, stats.as_deref_mut());
+
+            if let Some((path, cost)) = result
+                && best_path
+                    .as_ref()
+                    .is_none_or(|(_, best_cost)| cost < *best_cost)
+            {
+                best_path = Some((path, cost));
+            }
+        }
+
+        if options.was_stopped() {
+            return None;
+        }
+
+        best_path
+    }
+
+    /// Find a path that visits each of the waypoints in order, by chaining a search per leg
+    /// between consecutive waypoints. The joint shared by two legs appears once in the path.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `waypoints` - The positions (x, y) to visit, from the start to the end.
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Vec<Pos2D>, u32), usize>` - The combined path and the summed cost of its legs,
+    ///   or the index of the first leg without a path, leg `i` going from `waypoints[i]` to `waypoints[i + 1]`.
+    fn find_path_through_waypoints(
+        &self,
+        array: ArrayView2<T>,
+        waypoints: &[Pos2D],
+        options: &SearchOptions2D<T>,
+    ) -> Result<(Vec<Pos2D>, u32), usize> {
+        let mut path: Vec<Pos2D> = waypoints.first().copied().into_iter().collect();
+        let mut total_cost = 0u32;
+
+        for (leg, pair) in waypoints.windows(2).enumerate() {
+            let (leg_path, cost) = self
+                .find_path_with_options(array, pair[0], pair[1], options)
+                .ok_or(leg)?;
+
+            // Each leg starts where the previous one ended
+            path.extend(leg_path.into_iter().skip(1));
+            total_cost = total_cost.saturating_add(cost);
+        }
+
+        Ok((path, total_cost))
+    }
+
+    /// The cost of each edge of a path returned by this pathfinder, as summed by the search.
+    /// The costs add up to the total cost of the path.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `path` - The path, as returned by this pathfinder for the same `options`.
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Vec<u32>>` - One cost per edge, so one fewer than the points of the path,
+    ///   or `None` if the path takes an edge the search graph does not have.
+    fn segment_costs(
+        &self,
+        array: ArrayView2<T>,
+        path: &[Pos2D],
+        options: &SearchOptions2D<T>,
+    ) -> Option<Vec<u32>> {
+        let mut heading = None;
+        path.windows(2)
+            .map(|pair| {
+                let cost = grid_step_cost(array, pair[0], pair[1], options)?;
+                if options.turn_penalty == 0 {
+                    return Some(cost);
+                }
+
+                let offset = heading_of(step_offset(pair[0], pair[1], array.dim(), options)?);
+                if heading
+                    .replace(offset)
+                    .is_some_and(|heading| heading != offset)
+                {
+                    Some(cost.saturating_add(options.turn_penalty))
+                } else {
+                    Some(cost)
+                }
+            })
+            .collect()
+    }
+
+    /// Find up to `k` cheap, distinct paths from a start position to an end position,
+    /// in increasing cost order, using a variant of Yen's algorithm.
+    ///
+    /// Each spur search of Yen's algorithm masks the cells of the root path and adds the steps
+    /// earlier paths took out of the spur cell to the blocked edges.
+    /// A path is only returned if it shares at most `max_shared_fraction` of its edges with each
+    /// path returned before it. Grids have many near-identical alternatives, so the search gives
+    /// up after examining [`K_PATHS_CANDIDATES_PER_PATH`] candidates per requested path, and
+    /// fewer than `k` paths are returned if not enough distinct ones were found.
+    ///
+    /// The pathfinder must return paths made of adjacent cells, so any-angle pathfinders
+    /// such as [`ThetaStar2D`] are not supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_pos` - The end position (x, y).
+    /// * `k` - The maximum number of paths to return.
+    /// * `max_shared_fraction` - The largest fraction of a path's edges it may share with any earlier path.
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(Vec<Pos2D>, u32)>` - The paths found and their total costs, cheapest first.
+    fn find_k_paths(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        k: usize,
+        max_shared_fraction: f64,
+        options: &SearchOptions2D<T>,
+    ) -> Vec<(Vec<Pos2D>, u32)> {
+        yen_k_paths(
+            self,
+            array,
+            start_pos,
+            end_pos,
+            k,
+            max_shared_fraction,
+            options,
+        )
+    }
+
+    /// Find the cheapest path from a start position to an end position that is disjoint from
+    /// an earlier path, e.g. a backup route that shares no cell or no step with the main one.
+    ///
+    /// The steps of `avoid` are blocked in both directions, and with [`Disjoint::Nodes`] so are
+    /// its cells, except `start_pos` and `end_pos` which both paths must share. A node-disjoint
+    /// path is therefore edge-disjoint too. `avoid` must be made of adjacent cells, as returned
+    /// by every pathfinder but [`ThetaStar2D`], for its steps to be blocked.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_pos` - The end position (x, y).
+    /// * `avoid` - The path the returned path must not share cells or steps with.
+    /// * `disjoint` - Whether to avoid the cells of `avoid` or only its steps.
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and the total cost, or `None` if no disjoint
+    ///   path exists.
+    fn find_disjoint_path(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        avoid: &[Pos2D],
+        disjoint: Disjoint,
+        options: &SearchOptions2D<T>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        let mut blocked_edges = options.blocked_edges.cloned().unwrap_or_default();
+        blocked_edges.extend(avoid.windows(2).map(|step| (step[0], step[1])));
+
+        let blocked = (disjoint == Disjoint::Nodes).then(|| {
+            let mut blocked = match options.mask {
+                Some(mask) => mask.to_owned(),
+                None => Array2::from_elem(array.dim(), false),
+            };
+            for &(x, y) in avoid {
+                if (x, y) != start_pos && (x, y) != end_pos {
+                    blocked[[x as usize, y as usize]] = true;
+                }
+            }
+            blocked
+        });
+
+        let disjoint_options = SearchOptions2D {
+            mask: blocked
+                .as_ref()
+                .map(|blocked| blocked.view())
+                .or(options.mask),
+            blocked_edges: Some(&blocked_edges),
+            ..*options
+        };
+        self.find_path_with_options(array, start_pos, end_pos, &disjoint_options)
+    }
+
+    /// Find an approximate path on a very large heatmap in two passes: a coarse path is searched
+    /// on a copy of the heatmap downsampled by `factor`, then the full-resolution search is run
+    /// again, restricted to a corridor around the coarse path.
+    ///
+    /// Each coarse cell costs the average cost of the free cells it covers, and is blocked only
+    /// when all of them are blocked. Averaging hides the detail inside a coarse cell, so the
+    /// path may cost more than the optimal one, and may miss a cheaper route outside the corridor
+    /// entirely. If the corridor holds no path, the full heatmap is searched instead, so a path
+    /// is found whenever one exists. With a movement kernel, the coarse path is searched with
+    /// 8-connectivity, and the full heatmap is searched too when there is no coarse path.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_pos` - The end position (x, y).
+    /// * `factor` - The side length, in pixels, of the square block each coarse cell covers.
+    /// * `corridor` - The number of coarse cells around the coarse path the refined path may use.
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and its total cost at full resolution,
+    ///   or `None` if no path was found.
+    fn find_path_hierarchical(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        factor: usize,
+        corridor: usize,
+        options: &SearchOptions2D<T>,
+    ) -> Option<(Vec<Pos2D>, u32)>
+    where
+        Self: ImagePathfinder2D<u16> + Sized,
+    {
+        hierarchical_path(self, array, start_pos, end_pos, factor, corridor, options)
+    }
+}
+
+// MARK: Hierarchical
+
+/// Downsample a heatmap by `factor`, returning the average cost of the free cells of each block
+/// and whether each block is entirely blocked.
+fn downsample<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    factor: usize,
+    options: &SearchOptions2D<T>,
+) -> (Array2<u16>, Array2<bool>) {
+    let (width, height) = array.dim();
+    let dim = (width.div_ceil(factor), height.div_ceil(factor));
+
+    let mut sums = Array2::<u64>::zeros(dim);
+    let mut counts = Array2::<u64>::zeros(dim);
+    for ((x, y), &value) in array.indexed_iter() {
+        let pos = (x as u32, y as u32);
+        if options.is_blocked_value(value) || options.is_masked(pos) || options.is_halted(pos) {
+            continue;
+        }
+        let block = [x / factor, y / factor];
+        sums[block] += options.cell_cost(array, pos) as u64;
+        counts[block] += 1;
+    }
+
+    let costs = Array2::from_shape_fn(dim, |block| {
+        let average = sums[block].checked_div(counts[block]).unwrap_or(0);
+        average.min(u16::MAX as u64) as u16
+    });
+    let blocked = counts.mapv(|count| count == 0);
+    (costs, blocked)
+}
+
+/// Block every cell outside the corridor of `corridor` coarse cells around `coarse_path`,
+/// on top of the cells blocked by `mask`.
+fn corridor_mask(
+    dim: (usize, usize),
+    coarse_path: &[Pos2D],
+    factor: usize,
+    corridor: usize,
+    mask: Option<ArrayView2<bool>>,
+) -> Array2<bool> {
+    let coarse_dim = (dim.0.div_ceil(factor), dim.1.div_ceil(factor));
+    let mut near = Array2::from_elem(coarse_dim, false);
+    for &(x, y) in coarse_path {
+        let (x, y) = (x as usize, y as usize);
+        for cx in x.saturating_sub(corridor)..=(x + corridor).min(coarse_dim.0 - 1) {
+            for cy in y.saturating_sub(corridor)..=(y + corridor).min(coarse_dim.1 - 1) {
+                near[[cx, cy]] = true;
+            }
+        }
+    }
+
+    Array2::from_shape_fn(dim, |(x, y)| {
+        !near[[x / factor, y / factor]] || mask.is_some_and(|mask| mask[[x, y]])
+    })
+}
+
+/// Search a coarse path on the downsampled heatmap, then refine it within a corridor at full
+/// resolution. See [`ImagePathfinder2D::find_path_hierarchical`].
+fn hierarchical_path<T, P>(
+    pathfinder: &P,
+    array: ArrayView2<T>,
+    start_pos: Pos2D,
+    end_pos: Pos2D,
+    factor: usize,
+    corridor: usize,
+    options: &SearchOptions2D<T>,
+) -> Option<(Vec<Pos2D>, u32)>
+where
+    T: HeatmapValue,
+    P: ImagePathfinder2D<T> + ImagePathfinder2D<u16>,
+{
+    let factor = factor.max(1) as u32;
+    let (coarse, blocked) = downsample(array, factor as usize, options);
+
+    // Coarse costs are not comparable to full-resolution ones, so no budget applies yet. A
+    // movement kernel may not connect neighbouring blocks, so the coarse pass uses 8-connectivity.
+    let coarse_options = SearchOptions2D {
+        connectivity: match options.neighbours {
+            Some(_) => Connectivity::Eight,
+            None => options.connectivity,
+        },
+        wrap: options.wrap,
+        mask: Some(blocked.view()),
+        deadline: options.deadline,
+        node_limit: options.node_limit,
+        direction_weights: options.direction_weights,
+        ..Default::default()
+    };
+    let coarse_start = (start_pos.0 / factor, start_pos.1 / factor);
+    let coarse_end = (end_pos.0 / factor, end_pos.1 / factor);
+    // A block is only blocked when all its cells are, so no coarse path means no path at all,
+    // unless the long steps of a movement kernel hop over entirely blocked blocks
+    let Some((coarse_path, _)) =
+        pathfinder.find_path_with_options(coarse.view(), coarse_start, coarse_end, &coarse_options)
+    else {
+        if options.neighbours.is_some() && !options.was_stopped() {
+            return pathfinder.find_path_with_options(array, start_pos, end_pos, options);
+        }
+        return None;
+    };
+
+    let mask = corridor_mask(
+        array.dim(),
+        &coarse_path,
+        factor as usize,
+        corridor,
+        options.mask,
+    );
+    let corridor_options = SearchOptions2D {
+        mask: Some(mask.view()),
+        ..*options
+    };
+    let result = pathfinder.find_path_with_options(array, start_pos, end_pos, &corridor_options);
+
+    // The average of a block can hide that its cells do not connect the coarse path's neighbours
+    if result.is_none() && !options.was_stopped() {
+        return pathfinder.find_path_with_options(array, start_pos, end_pos, options);
+    }
+
+    result
+}
+
+// MARK: K shortest paths
+
+/// How many candidate paths [`ImagePathfinder2D::find_k_paths`] examines per requested path
+/// before giving up on finding more distinct ones.
+pub const K_PATHS_CANDIDATES_PER_PATH: usize = 8;
+
+/// The undirected edges of a path.
+fn path_edges(path: &[Pos2D]) -> HashSet<(Pos2D, Pos2D)> {
+    path.windows(2)
+        .map(|step| (step[0].min(step[1]), step[0].max(step[1])))
+        .collect()
+}
+
+/// What a path found by [`ImagePathfinder2D::find_disjoint_path`] must not share with the
+/// path it avoids.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Disjoint {
+    /// No cell, except the start and end positions. Node-disjoint paths are also edge-disjoint.
+    #[default]
+    Nodes,
+    /// No step between two cells, in either direction. Cells may be crossed again.
+    Edges,
+}
+
+fn yen_k_paths<T: HeatmapValue, P: ImagePathfinder2D<T> + ?Sized>(
+    pathfinder: &P,
+    array: ArrayView2<T>,
+    start_pos: Pos2D,
+    end_pos: Pos2D,
+    k: usize,
+    max_shared_fraction: f64,
+    options: &SearchOptions2D<T>,
+) -> Vec<(Vec<Pos2D>, u32)> {
+    let mut accepted: Vec<(Vec<Pos2D>, u32)> = Vec::new();
+    if k == 0 {
+        return accepted;
+    }
+
+    let Some(first) = pathfinder.find_path_with_options(array, start_pos, end_pos, options) else {
+        return accepted;
+    };
+
+    // Yen's list of shortest paths, including those rejected for overlapping too much
+    let mut shortest = vec![first.clone()];
+    let mut candidates: Vec<(Vec<Pos2D>, u32)> = Vec::new();
+    let mut seen = HashSet::from([first.0]);
+    let mut accepted_edges: Vec<HashSet<(Pos2D, Pos2D)>> = Vec::new();
+
+    // Spur searches block the root cells by marking them in a copy of the mask, and the steps
+    // out of the spur taken before by adding them to a copy of the blocked edges, both restored
+    // after each search
+    let mut blocked = match options.mask {
+        Some(mask) => mask.to_owned(),
+        None => Array2::from_elem(array.dim(), false),
+    };
+    let mut blocked_edges = options.blocked_edges.cloned().unwrap_or_default();
+
+    while let Some((path, cost)) = shortest.last().cloned() {
+        let edges = path_edges(&path);
+        let is_distinct = accepted_edges.iter().all(|other| {
+            let shared = edges.intersection(other).count();
+            shared as f64 <= max_shared_fraction * edges.len() as f64
+        });
+
+        if is_distinct {
+            accepted.push((path.clone(), cost));
+            accepted_edges.push(edges);
+
+            if accepted.len() == k {
+                break;
+            }
+        }
+
+        if shortest.len() >= k * K_PATHS_CANDIDATES_PER_PATH {
+            break;
+        }
+
+        let mut root_cost = 0;
+        for i in 0..path.len() - 1 {
+            let spur = path[i];
+            if i > 0 {
+                root_cost += step_cost(array, path[i - 1], spur, options).unwrap_or(0);
+            }
+
+            // The root must not be revisited, and the spur must leave it differently than before
+            let root = &path[..i];
+            let previous: Vec<bool> = root
+                .iter()
+                .map(|&(x, y)| std::mem::replace(&mut blocked[[x as usize, y as usize]], true))
+                .collect();
+            let deviations: Vec<(Pos2D, Pos2D)> = shortest
+                .iter()
+                .filter(|(other, _)| other.len() > i + 1 && other[..=i] == path[..=i])
+                .map(|(other, _)| (spur, other[i + 1]))
+                .filter(|&(from, to)| !options.is_blocked_edge(from, to))
+                .collect();
+            blocked_edges.extend(deviations.iter().copied());
+
+            let spur_options = SearchOptions2D {
+                mask: Some(blocked.view()),
+                blocked_edges: Some(&blocked_edges),
+                ..*options
+            };
+            let spur_path = pathfinder.find_path_with_options(array, spur, end_pos, &spur_options);
+
+            for (&(x, y), &value) in root.iter().zip(&previous) {
+                blocked[[x as usize, y as usize]] = value;
+            }
+            for edge in &deviations {
+                blocked_edges.remove(edge);
+            }
+
+            if let Some((spur_path, spur_cost)) = spur_path {
+                let mut candidate = path[..i].to_vec();
+                candidate.extend(spur_path);
+
+                if seen.insert(candidate.clone()) {
+                    candidates.push((candidate, root_cost + spur_cost));
+                }
+            }
+        }
+
+        let Some(next) = candidates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (candidate, cost))| (*cost, candidate.len()))
+            .map(|(index, _)| index)
+        else {
+            break;
+        };
+
+        shortest.push(candidates.swap_remove(next));
+    }
+
+    accepted
+}
+
+// MARK: Dijkstra
+
+/// A 2D pathfinder that uses Dijkstra's algorithm.
+pub struct Dijkstra2D {}
+
+impl<T: HeatmapValue> ImagePathfinder2D<T> for Dijkstra2D {
+    fn find_path_to_any(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_positions: &[Pos2D],
+        options: &SearchOptions2D<T>,
+        stats: Option<&mut SearchStats>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        if options.turn_penalty > 0 {
+            return find_path_with_turns(
+                array,
+                start_pos,
+                end_positions,
+                options,
+                stats,
+                |_| 0,
+                |start, successors, _, success| dijkstra(start, successors, success),
+            );
+        }
+        if options.tie_break != TieBreak::None {
+            return find_path_with_tie_break(
+                array,
+                start_pos,
+                end_positions,
+                options,
+                stats,
+                |_| 0,
+                |start, successors, _, success| dijkstra(start, successors, success),
+            );
+        }
+
+        let mut tracker = stats.map(|stats| StatsTracker::new(stats, start_pos));
+        let mut budget = options
+            .max_cost
+            .map(|max_cost| CostBudget::new(max_cost, start_pos));
+        let result = dijkstra(
+            &start_pos,
+            |&p| expand(array, p, options, &mut tracker, &mut budget),
+            |p| end_positions.contains(p),
+        );
+
+        // The search may still reach an end while running dry after the deadline
+        if options.was_stopped() {
+            return None;
+        }
+
+        if let Some((path, costs)) = result
+            && options.fits_budget(costs)
+        {
+            return Some((path, costs));
+        }
+
+        None
+    }
+
+    fn path_cost(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        options: &SearchOptions2D<T>,
+    ) -> Option<u32> {
+        if options.turn_penalty > 0 {
+            return self
+                .find_path_with_options(array, start_pos, end_pos, options)
+                .map(|(_, cost)| cost);
+        }
+
+        cheapest_cost(array, start_pos, end_pos, options, |_| 0)
+    }
+}
+
+/// The cost of the cheapest path from `start_pos` to `end_pos`, found by a best-first search
+/// ranked by cost plus `heuristic`, like A*, that keeps the best cost of each cell in a dense
+/// array and no parents, so no path is built. Turn penalties are not taken into account.
+fn cheapest_cost<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    start_pos: Pos2D,
+    end_pos: Pos2D,
+    options: &SearchOptions2D<T>,
+    heuristic: impl Fn(Pos2D) -> u32,
+) -> Option<u32> {
+    let mut best = Array2::from_elem(array.dim(), u32::MAX);
+    best[index(start_pos)] = 0;
+    let mut open = BinaryHeap::from([Reverse((heuristic(start_pos), 0, start_pos))]);
+
+    while let Some(Reverse((_, cost, pos))) = open.pop() {
+        if pos == end_pos {
+            // The search may still reach the end while running dry after the deadline
+            return (!options.was_stopped() && options.fits_budget(cost)).then_some(cost);
+        }
+        // A cheaper way to this cell was found after it was queued
+        if cost > best[index(pos)] {
+            continue;
+        }
+
+        for (next, step) in expand(array, pos, options, &mut None, &mut None) {
+            let next_cost = cost.saturating_add(step);
+            if options.fits_budget(next_cost) && next_cost < best[index(next)] {
+                best[index(next)] = next_cost;
+                open.push(Reverse((
+                    next_cost.saturating_add(heuristic(next)),
+                    next_cost,
+                    next,
+                )));
+            }
+        }
+    }
+
+    None
+}
+
+/// A path cost over a float cost surface, totally ordered so that it can rank the frontier.
+/// Costs are never NaN, so the order of [`f64::total_cmp`] is the numeric one.
+#[derive(Clone, Copy, Debug, Default)]
+struct FloatCost(f64);
+
+impl PartialEq for FloatCost {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FloatCost {}
+
+impl PartialOrd for FloatCost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FloatCost {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Add for FloatCost {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        FloatCost(self.0 + other.0)
+    }
+}
+
+impl Zero for FloatCost {
+    fn zero() -> Self {
+        FloatCost(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0.0
+    }
+}
+
+/// The cost of stepping from `from` to the adjacent cell `to` of a float cost surface, or
+/// `None` if `to` cannot be entered. Steps cost as in [`step_cost`], without rounding.
+fn float_step_cost(
+    array: ArrayView2<f32>,
+    from: Pos2D,
+    to: Pos2D,
+    options: &SearchOptions2D<f32>,
+) -> Option<FloatCost> {
+    let value = array[[to.0 as usize, to.1 as usize]];
+    if value == f32::INFINITY || options.is_masked(to) || options.is_blocked_edge(from, to) {
+        return None;
+    }
+
+    let value = value as f64;
+    if from.0 != to.0 && from.1 != to.1 {
+        let source = array[[from.0 as usize, from.1 as usize]] as f64;
+        Some(FloatCost(
+            (source + value) * std::f64::consts::FRAC_1_SQRT_2,
+        ))
+    } else {
+        Some(FloatCost(value))
+    }
+}
+
+/// The value of unreachable cells in a distance field.
+pub const UNREACHABLE: u32 = u32::MAX;
+
+/// The value of the goal and of unreachable cells in a flow field.
+pub const NO_DIRECTION: u8 = u8::MAX;
+
+impl Dijkstra2D {
+    /// Find the cheapest path from a start position to any cell of a goal region. The search
+    /// stops as soon as the first goal cell is taken from the frontier, so only cells cheaper
+    /// to reach than the nearest goal are expanded, however large the region is.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `goal` - A mask with the same shape as `array`, where `true` marks a goal cell.
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    /// * `stats` - If provided, search statistics are accumulated into it.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and the total cost, or `None` if no goal
+    ///   cell can be reached. The last point of the path is the goal cell that was reached.
+    pub fn find_path_to_region<T: HeatmapValue>(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        goal: ArrayView2<bool>,
+        options: &SearchOptions2D<T>,
+        stats: Option<&mut SearchStats>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        let mut tracker = stats.map(|stats| StatsTracker::new(stats, start_pos));
+        let mut budget = options
+            .max_cost
+            .map(|max_cost| CostBudget::new(max_cost, start_pos));
+        let result = dijkstra(
+            &start_pos,
+            |&p| expand(array, p, options, &mut tracker, &mut budget),
+            |&(x, y)| goal[[x as usize, y as usize]],
+        );
+
+        // The search may still reach a goal while running dry after the deadline
+        if options.was_stopped() {
+            return None;
+        }
+
+        result.filter(|&(_, cost)| options.fits_budget(cost))
+    }
+
+    /// Find the cheapest path from a start position to the explored cell closest to the end
+    /// positions, e.g. to show how far a failed search got. Each start explores every cell it
+    /// can reach within the cost budget, stopping early only if it reaches an end, whose full
+    /// path is then returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_positions` - The candidate start positions (x, y).
+    /// * `end_positions` - The end positions (x, y) the path heads towards.
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    /// * `heuristic` - The distance by which explored cells are ranked, going across wrapped
+    ///   borders when that is shorter.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path to the explored cell closest to any end and its
+    ///   cost, the cheapest one among equally close cells, or `None` if there are no start
+    ///   positions, the deadline passed, or the node limit was exceeded. If no cell is closer
+    ///   than the start itself, the path is `[start]` with a cost of 0.
+    pub fn find_partial_path<T: HeatmapValue>(
+        &self,
+        array: ArrayView2<T>,
+        start_positions: &[Pos2D],
+        end_positions: &[Pos2D],
+        options: &SearchOptions2D<T>,
+        heuristic: Heuristic2D,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        let distance = |pos| heuristic.min_distance(pos, end_positions, options.wrap, array.dim());
+
+        let mut best: Option<(u32, u32, Vec<Pos2D>)> = None;
+        for &start in start_positions {
+            let mut budget = options
+                .max_cost
+                .map(|max_cost| CostBudget::new(max_cost, start));
+            let (parents, reached) = dijkstra_partial(
+                &start,
+                |&p| expand(array, p, options, &mut None, &mut budget),
+                |p| end_positions.contains(p),
+            );
+
+            // The explored cells, and the start which has no parent, ranked by (distance, cost)
+            let closest = match reached {
+                Some(end) => (0, parents.get(&end).map_or(0, |&(_, cost)| cost), end),
+                None => parents
+                    .iter()
+                    .map(|(&pos, &(_, cost))| (distance(pos), cost, pos))
+                    .chain([(distance(start), 0, start)])
+                    .min()
+                    .expect("the start is always a candidate"),
+            };
+
+            let (closest_distance, cost, pos) = closest;
+            if best
+                .as_ref()
+                .is_none_or(|&(d, c, _)| (closest_distance, cost) < (d, c))
+            {
+                best = Some((closest_distance, cost, build_path(&pos, &parents)));
+            }
+        }
+
+        // The search may still reach a cell while running dry after the deadline
+        if options.was_stopped() {
+            return None;
+        }
+
+        best.map(|(_, cost, path)| (path, cost))
+    }
+
+    /// Compute the minimum accumulated cost from `source` to every cell of the heatmap,
+    /// by running Dijkstra's algorithm to completion instead of stopping at a goal.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `source` - The position (x, y) the costs are measured from.
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Array2<u32>` - The cost of reaching each cell, with the same shape as `array`.
+    ///   The source costs 0 and cells that cannot be reached hold [`UNREACHABLE`].
+    pub fn distance_field<T: HeatmapValue>(
+        &self,
+        array: ArrayView2<T>,
+        source: Pos2D,
+        options: &SearchOptions2D<T>,
+    ) -> Array2<u32> {
+        let reachable = dijkstra_all(&source, |&p| find_neighbours_with_cost(array, p, options));
+
+        let mut field = Array2::from_elem(array.dim(), UNREACHABLE);
+        field[[source.0 as usize, source.1 as usize]] = 0;
+        for ((x, y), (_, cost)) in reachable {
+            field[[x as usize, y as usize]] = cost;
+        }
+
+        field
+    }
+
+    /// Compute, for every cell of the heatmap, the first step of its cheapest path to `goal`,
+    /// by running Dijkstra's algorithm backwards from the goal to completion. Agents heading
+    /// to the same goal can then follow the field instead of searching a path each.
+    ///
+    /// Steps are encoded as direction codes: 0 is left (x - 1), 1 is right (x + 1), 2 is up
+    /// (y - 1) and 3 is down (y + 1). With 8-connectivity, 4 to 7 are the diagonal steps
+    /// (-1, -1), (1, -1), (-1, 1) and (1, 1). Steps across wrapped borders keep their direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `goal` - The position (x, y) every cell is routed to.
+    /// * `options` - The options describing how the heatmap is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Array2<u8>` - The direction code of each cell, with the same shape as `array`.
+    ///   The goal and cells that cannot reach it hold [`NO_DIRECTION`].
+    ///
+    /// # Panics
+    ///
+    /// If `options.neighbours` is set or `options.reach` is above 1, as the steps of a movement
+    /// kernel and jumps have no direction code.
+    pub fn flow_field<T: HeatmapValue>(
+        &self,
+        array: ArrayView2<T>,
+        goal: Pos2D,
+        options: &SearchOptions2D<T>,
+    ) -> Array2<u8> {
+        assert!(
+            options.neighbours.is_none(),
+            "flow fields only encode steps to adjacent cells, not a movement kernel"
+        );
+        assert!(
+            options.reach <= 1,
+            "flow fields only encode single steps, got a reach of {}",
+            options.reach
+        );
+        let reaching = dijkstra_all(&goal, |&p| find_predecessors_with_cost(array, p, options));
+
+        let mut field = Array2::from_elem(array.dim(), NO_DIRECTION);
+        for (pos, (next, _)) in reaching {
+            let code = step_offset(pos, next, array.dim(), options)
+                .and_then(|offset| NEIGHBOUR_OFFSETS.iter().position(|&o| o == offset));
+            if let Some(code) = code {
+                field[[pos.0 as usize, pos.1 as usize]] = code as u8;
+            }
+        }
+
+        field
+    }
+
+    /// Find the cheapest path over a continuous cost surface, such as probabilities in
+    /// `[0, 1]`, without quantizing it to integers. Steps cost as over integer heatmaps, but
+    /// the costs are summed as `f64`. Cells holding positive infinity can never be entered.
+    ///
+    /// Only the `connectivity`, `wrap`, `mask`, `deadline`, `node_limit`, `observer`, and
+    /// `blocked_edges` fields of `options` are used.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The cost surface as a 2D ndarray with shape (width, height). Its values must
+    ///   not be NaN or negative, or the path found may not be the cheapest.
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_pos` - The end position (x, y).
+    /// * `options` - The options describing how the cost surface is turned into a search graph.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, f64)>` - The path found and the total cost, or `None` if the end
+    ///   cannot be reached, the deadline passed, or the node limit was exceeded.
+    pub fn find_path_float(
+        &self,
+        array: ArrayView2<f32>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        options: &SearchOptions2D<f32>,
+    ) -> Option<(Vec<Pos2D>, f64)> {
+        let successors = |&pos: &Pos2D| -> Vec<(Pos2D, FloatCost)> {
+            if options.should_stop() {
+                return Vec::new();
+            }
+
+            options.notify_expansion(pos);
+            let neighbours: Vec<(Pos2D, FloatCost)> = neighbour_offsets(options.connectivity)
+                .iter()
+                .filter_map(|&offset| {
+                    let to = offset_position(pos, offset, array.dim(), options.wrap)?;
+                    Some((to, float_step_cost(array, pos, to, options)?))
+                })
+                .collect();
+            options.notify_generated(pos, neighbours.iter().map(|&(p, _)| p));
+            neighbours
+        };
+        let result = dijkstra(&start_pos, successors, |&p| p == end_pos);
+
+        // The search may still reach the end while running dry after the deadline
+        if options.was_stopped() {
+            return None;
+        }
+
+        result.map(|(path, FloatCost(cost))| (path, cost))
+    }
+}
+
+// MARK: A*
+
+/// The distance estimate A* uses to rank cells, counting one cost unit per grid step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Heuristic2D {
+    /// `dx + dy`. Overestimates diagonal moves, so it is only admissible with 4-connectivity.
+    #[default]
+    Manhattan,
+    /// The straight-line distance, rounded down.
+    Euclidean,
+    /// `max(dx, dy)`, the number of steps with 8-connectivity.
+    Chebyshev,
+    /// `max(dx, dy) + (√2 - 1) * min(dx, dy)`, the length of the shortest path with
+    /// 8-connectivity, rounded down. [`AStar2D`] prices its straight and diagonal steps at the
+    /// cheapest ones on the heatmap, so it stays admissible whatever the cell costs, as long as
+    /// there is no movement kernel and no `reach` above 1.
+    Octile,
+    /// Always 0, which turns A* into Dijkstra's algorithm.
+    Zero,
+}
+
+impl Heuristic2D {
+    /// The estimated distance between two positions.
+    pub fn distance(self, from: Pos2D, to: Pos2D) -> u32 {
+        self.estimate(from.0.abs_diff(to.0), from.1.abs_diff(to.1))
+    }
+
+    /// The estimated distance covering `dx` steps along x and `dy` steps along y.
+    fn estimate(self, dx: u32, dy: u32) -> u32 {
+        match self {
+            Heuristic2D::Manhattan => dx + dy,
+            Heuristic2D::Euclidean => (dx as f64).hypot(dy as f64) as u32,
+            Heuristic2D::Chebyshev => dx.max(dy),
+            Heuristic2D::Octile => {
+                (dx.max(dy) as f64 + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dy) as f64) as u32
+            }
+            Heuristic2D::Zero => 0,
+        }
+    }
+
+    /// The estimated distance from `pos` to the closest of `end_positions`, going across
+    /// wrapped borders when that is shorter.
+    fn min_distance(
+        self,
+        pos: Pos2D,
+        end_positions: &[Pos2D],
+        wrap: Wrap,
+        dim: (usize, usize),
+    ) -> u32 {
+        end_positions
+            .iter()
+            .map(|&end| {
+                let (dx, dy) = wrap.deltas(pos, end, dim);
+                self.estimate(dx, dy)
+            })
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+/// The costs of the cheapest straight and diagonal steps anywhere on the heatmap: the cost of
+/// its cheapest free cell, and of a diagonal step between two such cells, scaled by the
+/// lightest direction weight. No path can take cheaper steps.
+fn cheapest_step_costs<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    options: &SearchOptions2D<T>,
+) -> (u32, u32) {
+    let cheapest = array
+        .indexed_iter()
+        .map(|((x, y), &value)| (value, (x as u32, y as u32)))
+        .filter(|&(value, pos)| {
+            !options.is_blocked_value(value) && !options.is_masked(pos) && !options.is_halted(pos)
+        })
+        .map(|(_, pos)| options.cell_cost(array, pos))
+        .min()
+        .unwrap_or(0);
+    let diagonal = (cheapest as f64 * 2.0 * std::f64::consts::FRAC_1_SQRT_2).round() as u32;
+
+    match options.direction_weights {
+        Some(weights) => {
+            let lightest = [weights.up, weights.down, weights.left, weights.right]
+                .into_iter()
+                .fold(f64::INFINITY, f64::min)
+                .max(0.0);
+            let scale = |cost: u32| (cost as f64 * lightest).round() as u32;
+            (scale(cheapest), scale(diagonal))
+        }
+        None => (cheapest, diagonal),
+    }
+}
+
+/// A 2D pathfinder that uses the A* algorithm.
+pub struct AStar2D {
+    /// Factor the heuristic is multiplied by (weighted A*). A weight of 1.0 gives optimal paths;
+    /// larger weights expand fewer nodes, returning paths at most `weight` times the optimal cost.
+    pub weight: f64,
+    /// The distance estimate used to rank cells.
+    pub heuristic: Heuristic2D,
+}
+
+impl Default for AStar2D {
+    fn default() -> Self {
+        Self {
+            weight: 1.0,
+            heuristic: Heuristic2D::default(),
+        }
+    }
+}
+
+impl AStar2D {
+    /// The estimated cost from a cell to the closest of `end_positions`, inflated by the weight.
+    /// [`Heuristic2D::Octile`] prices its steps with [`cheapest_step_costs`].
+    fn estimator<'e, T: HeatmapValue>(
+        &self,
+        array: ArrayView2<'e, T>,
+        end_positions: &'e [Pos2D],
+        options: &'e SearchOptions2D<T>,
+    ) -> impl Fn(Pos2D) -> u32 + 'e {
+        let (heuristic, weight) = (self.heuristic, self.weight);
+        let step_costs =
+            (heuristic == Heuristic2D::Octile).then(|| cheapest_step_costs(array, options));
+
+        move |p| {
+            let distance = match step_costs {
+                Some((straight, diagonal)) => end_positions
+                    .iter()
+                    .map(|&end| {
+                        let (dx, dy) = options.wrap.deltas(p, end, array.dim());
+                        straight
+                            .saturating_mul(dx.abs_diff(dy))
+                            .saturating_add(diagonal.saturating_mul(dx.min(dy)))
+                    })
+                    .min()
+                    .unwrap_or(0),
+                None => heuristic.min_distance(p, end_positions, options.wrap, array.dim()),
+            };
+            (distance as f64 * weight) as u32
+        }
+    }
+}
+
+impl<T: HeatmapValue> ImagePathfinder2D<T> for AStar2D {
+    fn find_path_to_any(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_positions: &[Pos2D],
+        options: &SearchOptions2D<T>,
+        stats: Option<&mut SearchStats>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        let heuristic = self.estimator(array, end_positions, options);
+
+        if options.turn_penalty > 0 {
+            return find_path_with_turns(
+                array,
+                start_pos,
+                end_positions,
+                options,
+                stats,
+                heuristic,
+                |start, successors, heuristic, success| {
+                    astar(start, successors, heuristic, success)
+                },
+            );
+        }
+        if options.tie_break != TieBreak::None {
+            return find_path_with_tie_break(
+                array,
+                start_pos,
+                end_positions,
+                options,
+                stats,
+                heuristic,
+                |start, successors, heuristic, success| {
+                    astar(start, successors, heuristic, success)
+                },
+            );
+        }
+
+        let mut tracker = stats.map(|stats| StatsTracker::new(stats, start_pos));
+        let mut budget = options
+            .max_cost
+            .map(|max_cost| CostBudget::new(max_cost, start_pos));
+        let result = astar(
+            &start_pos,
+            |&p| expand(array, p, options, &mut tracker, &mut budget),
+            |&p| heuristic(p),
+            |p| end_positions.contains(p),
+        );
+
+        // The search may still reach an end while running dry after the deadline
+        if options.was_stopped() {
+            return None;
+        }
+
+        if let Some((path, costs)) = result
+            && options.fits_budget(costs)
+        {
+            return Some((path, costs));
+        }
+
+        None
+    }
+
+    fn path_cost(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        options: &SearchOptions2D<T>,
+    ) -> Option<u32> {
+        // An overestimating heuristic makes the cost depend on the order cells are expanded in,
+        // which only the full search reproduces
+        let is_admissible = match self.heuristic {
+            Heuristic2D::Zero => true,
+            Heuristic2D::Octile => {
+                self.weight <= 1.0 && options.neighbours.is_none() && options.reach <= 1
+            }
+            _ => false,
+        };
+        if options.turn_penalty > 0 || !is_admissible {
+            return self
+                .find_path_with_options(array, start_pos, end_pos, options)
+                .map(|(_, cost)| cost);
+        }
+
+        let end_positions = [end_pos];
+        cheapest_cost(
+            array,
+            start_pos,
+            end_pos,
+            options,
+            self.estimator(array, &end_positions, options),
+        )
+    }
+}
+
+// MARK: Fringe
+
+pub struct Fringe2D {}
+
+impl<T: HeatmapValue> ImagePathfinder2D<T> for Fringe2D {
+    fn find_path_to_any(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_positions: &[Pos2D],
+        options: &SearchOptions2D<T>,
+        stats: Option<&mut SearchStats>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        let heuristic = |p| min_manhattan_distance(p, end_positions, options.wrap, array.dim());
+
+        if options.turn_penalty > 0 {
+            return find_path_with_turns(
+                array,
+                start_pos,
+                end_positions,
+                options,
+                stats,
+                heuristic,
+                |start, successors, heuristic, success| {
+                    pathfinding::prelude::fringe(start, successors, heuristic, success)
+                },
+            );
+        }
+        if options.tie_break != TieBreak::None {
+            return find_path_with_tie_break(
+                array,
+                start_pos,
+                end_positions,
+                options,
+                stats,
+                heuristic,
+                |start, successors, heuristic, success| {
+                    pathfinding::prelude::fringe(start, successors, heuristic, success)
+                },
+            );
+        }
+
+        let mut tracker = stats.map(|stats| StatsTracker::new(stats, start_pos));
+        let mut budget = options
+            .max_cost
+            .map(|max_cost| CostBudget::new(max_cost, start_pos));
+        let result = pathfinding::prelude::fringe(
+            &start_pos,
+            |&p| expand(array, p, options, &mut tracker, &mut budget),
+            |&p| heuristic(p),
+            |p| end_positions.contains(p),
+        );
+
+        // The search may still reach an end while running dry after the deadline
+        if options.was_stopped() {
+            return None;
+        }
+
+        if let Some((path, costs)) = result
+            && options.fits_budget(costs)
+        {
+            return Some((path, costs));
+        }
+
+        None
+    }
+}
+
+// MARK: Bidirectional Dijkstra
+
+/// A 2D pathfinder that runs Dijkstra's algorithm from the start and, over reversed edges,
+/// from the end positions at the same time, stopping once the two searches meet.
+/// It returns the same optimal cost as [`Dijkstra2D`] while usually expanding fewer cells.
+pub struct Dijkstra2DBidirectional {}
+
+/// One direction of a bidirectional search.
+struct SearchFrontier {
+    heap: BinaryHeap<Reverse<(u32, Pos2D)>>,
+    costs: HashMap<Pos2D, u32>,
+    parents: HashMap<Pos2D, Pos2D>,
+}
+
+impl SearchFrontier {
+    fn new(roots: impl IntoIterator<Item = Pos2D>) -> Self {
+        let mut frontier = Self {
+            heap: BinaryHeap::new(),
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+        };
+
+        for root in roots {
+            frontier.costs.insert(root, 0);
+            frontier.heap.push(Reverse((0, root)));
+        }
+
+        frontier
+    }
+
+    /// The smallest cost still waiting to be expanded, if any.
+    fn peek_cost(&self) -> Option<u32> {
+        self.heap.peek().map(|&Reverse((cost, _))| cost)
+    }
+
+    /// Pop the cheapest cell, skipping entries that were superseded by a cheaper cost.
+    fn pop(&mut self) -> Option<(u32, Pos2D)> {
+        while let Some(Reverse((cost, pos))) = self.heap.pop() {
+            if self.costs.get(&pos) == Some(&cost) {
+                return Some((cost, pos));
+            }
+        }
+
+        None
+    }
+
+    /// Expand `pos` over `edges`, updating `best` whenever an edge reaches a cell
+    /// already labelled by the `other` direction.
+    fn relax(
+        &mut self,
+        pos: Pos2D,
+        cost: u32,
+        edges: &[Pos2DWithCost],
+        other: &SearchFrontier,
+        best: &mut Option<(u32, Pos2D)>,
+    ) {
+        for &(next, step) in edges {
+            let next_cost = cost + step;
+
+            if let Some(&other_cost) = other.costs.get(&next)
+                && best.is_none_or(|(best_cost, _)| next_cost + other_cost < best_cost)
+            {
+                *best = Some((next_cost + other_cost, next));
+            }
+
+            if self.costs.get(&next).is_none_or(|&known| next_cost < known) {
+                self.costs.insert(next, next_cost);
+                self.parents.insert(next, pos);
+                self.heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    /// The chain of parents from `pos` back to the root it was reached from.
+    fn chain_from(&self, pos: Pos2D) -> Vec<Pos2D> {
+        let mut chain = vec![pos];
+        while let Some(&parent) = self.parents.get(chain.last().unwrap()) {
+            chain.push(parent);
+        }
+
+        chain
+    }
+}
+
+impl<T: HeatmapValue> ImagePathfinder2D<T> for Dijkstra2DBidirectional {
+    fn find_path_to_any(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_positions: &[Pos2D],
+        options: &SearchOptions2D<T>,
+        stats: Option<&mut SearchStats>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        if end_positions.contains(&start_pos) {
+            return Some((vec![start_pos], 0));
+        }
+
+        let mut tracker = stats.map(|stats| StatsTracker::new(stats, start_pos));
+        if let Some(tracker) = &mut tracker {
+            tracker.discovered.extend(end_positions);
+        }
+
+        let mut forward = SearchFrontier::new([start_pos]);
+        let mut backward = SearchFrontier::new(end_positions.iter().copied());
+        let mut best: Option<(u32, Pos2D)> = None;
+
+        // Once the two frontiers together cost at least as much as the best meeting point,
+        // no cheaper path can be found. An exhausted frontier means every path is known.
+        while let (Some(forward_cost), Some(backward_cost)) =
+            (forward.peek_cost(), backward.peek_cost())
+        {
+            if options.was_stopped() {
+                return None;
+            }
+
+            if best.is_some_and(|(best_cost, _)| forward_cost + backward_cost >= best_cost) {
+                break;
+            }
+
+            // Every path still to be found costs at least as much as the two frontiers together
+            if !options.fits_budget(forward_cost.saturating_add(backward_cost)) {
+                break;
+            }
+
+            if forward_cost <= backward_cost {
+                let Some((cost, pos)) = forward.pop() else {
+                    continue;
+                };
+                let edges = expand(array, pos, options, &mut tracker, &mut None);
+                forward.relax(pos, cost, &edges, &backward, &mut best);
+            } else {
+                let Some((cost, pos)) = backward.pop() else {
+                    continue;
+                };
+                if options.should_stop() {
+                    return None;
+                }
+                options.notify_expansion(pos);
+                let edges = find_predecessors_with_cost(array, pos, options);
+                options.notify_generated(pos, edges.iter().map(|&(p, _)| p));
+                if let Some(tracker) = &mut tracker {
+                    tracker.record(pos, &edges);
+                }
+                backward.relax(pos, cost, &edges, &forward, &mut best);
+            }
+        }
+
+        let (cost, meeting) = best.filter(|&(cost, _)| options.fits_budget(cost))?;
+        let mut path = forward.chain_from(meeting);
+        path.reverse();
+        path.extend(backward.chain_from(meeting).into_iter().skip(1));
+
+        Some((path, cost))
+    }
+}
+
+// MARK: Jump Point Search
+
+/// A 2D pathfinder that uses Jump Point Search, an A* variant that skips over the many
+/// equivalent paths of a uniform-cost grid by jumping straight to the cells where
+/// the path may need to turn.
+///
+/// **Precondition:** the search must be 8-connected and every free cell (neither impassable
+/// nor masked) must have the same cost. On other grids the returned path may be suboptimal
+/// and its cost wrong; use [`JumpPointSearch2D::supports`] to check first, or [`AStar2D`] instead.
+/// Blocked edges, e.g. those of [`ImagePathfinder2D::find_disjoint_path`], are honoured by
+/// running [`AStar2D`] with the [`Heuristic2D::Octile`] heuristic, which finds the same cost.
+pub struct JumpPointSearch2D {}
+
+/// A direction of travel, `(0, 0)` for the start cell which has no direction yet.
+type Direction = (i32, i32);
+
+impl JumpPointSearch2D {
+    /// Whether the search is 8-connected without a movement kernel, jumps, speed map, wrapping
+    /// borders, turn penalty, direction weights, blocked edges, or corner cutting prevention and
+    /// all free cells share the same cost, which Jump Point Search requires to find optimal paths.
+    pub fn supports<T: HeatmapValue>(array: ArrayView2<T>, options: &SearchOptions2D<T>) -> bool {
+        if options.connectivity != Connectivity::Eight
+            || options.neighbours.is_some()
+            || options.reach > 1
+            || options.speed.is_some()
+            || options.wrap != Wrap::None
+            || options.turn_penalty > 0
+            || options.no_corner_cutting
+            || options.blocked_edges.is_some_and(|edges| !edges.is_empty())
+            || options
+                .direction_weights
+                .is_some_and(|weights| !weights.is_uniform())
+        {
+            return false;
+        }
+
+        let mut free_costs = array
+            .indexed_iter()
+            .filter(|&((x, y), &value)| {
+                !options.is_blocked_value(value) && !options.is_masked((x as u32, y as u32))
+            })
+            .map(|(_, &value)| options.cost_of(value));
+
+        match free_costs.next() {
+            Some(cost) => free_costs.all(|other| other == cost),
+            None => true,
+        }
+    }
+}
+
+/// Whether the cell at `(x, y)` lies inside the heatmap and can be entered.
+fn is_walkable<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    (x, y): (i64, i64),
+    options: &SearchOptions2D<T>,
+) -> bool {
+    let (width, height) = array.dim();
+    if x < 0 || y < 0 || x >= width as i64 || y >= height as i64 {
+        return false;
+    }
+
+    let pos = (x as u32, y as u32);
+    !options.is_blocked_value(array[[x as usize, y as usize]]) && !options.is_masked(pos)
+}
+
+/// The directions worth exploring from `pos` when it was reached moving along `dir`:
+/// the natural neighbours plus any forced neighbours created by adjacent obstacles.
+fn pruned_directions<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    pos: Pos2D,
+    (dx, dy): Direction,
+    options: &SearchOptions2D<T>,
+) -> Vec<Direction> {
+    if (dx, dy) == (0, 0) {
+        return NEIGHBOUR_OFFSETS.to_vec();
+    }
+
+    let (x, y) = (pos.0 as i64, pos.1 as i64);
+    let (ix, iy) = (dx as i64, dy as i64);
+    let walkable = |cell| is_walkable(array, cell, options);
+    let mut directions = vec![(dx, dy)];
+
+    if dx != 0 && dy != 0 {
+        directions.extend([(dx, 0), (0, dy)]);
+
+        if !walkable((x - ix, y)) && walkable((x - ix, y + iy)) {
+            directions.push((-dx, dy));
+        }
+        if !walkable((x, y - iy)) && walkable((x + ix, y - iy)) {
+            directions.push((dx, -dy));
+        }
+    } else if dx != 0 {
+        for side in [-1, 1] {
+            if !walkable((x, y + side)) && walkable((x + ix, y + side)) {
+                directions.push((dx, side as i32));
+            }
+        }
+    } else {
+        for side in [-1, 1] {
+            if !walkable((x + side, y)) && walkable((x + side, y + iy)) {
+                directions.push((side as i32, dy));
+            }
+        }
+    }
+
+    directions
+}
+
+/// Whether `pos`, reached moving along `dir`, has a forced neighbour.
+fn has_forced_neighbour<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    pos: Pos2D,
+    dir: Direction,
+    options: &SearchOptions2D<T>,
+) -> bool {
+    let natural = if dir.0 != 0 && dir.1 != 0 { 3 } else { 1 };
+    pruned_directions(array, pos, dir, options).len() > natural
+}
+
+/// Move from `pos` along `dir` until reaching a jump point: an end position, a cell with a
+/// forced neighbour, or (when moving diagonally) a cell from which a straight jump succeeds.
+/// Returns `None` if an obstacle or the border is hit first.
+fn jump<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    pos: Pos2D,
+    (dx, dy): Direction,
+    end_positions: &[Pos2D],
+    options: &SearchOptions2D<T>,
+) -> Option<Pos2D> {
+    let mut current = pos;
+
+    loop {
+        // Jumps can scan long runs of cells, so each one counts against the deadline and node limit
+        let next = (current.0 as i64 + dx as i64, current.1 as i64 + dy as i64);
+        if !is_walkable(array, next, options) || options.should_stop() {
+            return None;
+        }
+
+        current = (next.0 as u32, next.1 as u32);
+        if end_positions.contains(&current)
+            || has_forced_neighbour(array, current, (dx, dy), options)
+        {
+            return Some(current);
+        }
+
+        if dx != 0
+            && dy != 0
+            && (jump(array, current, (dx, 0), end_positions, options).is_some()
+                || jump(array, current, (0, dy), end_positions, options).is_some())
+        {
+            return Some(current);
+        }
+    }
+}
+
+/// The costs of a straight and a diagonal step on a uniform grid. Every free cell costs the
+/// same, so any free `cell` gives them, and every direction weighs the same, so they are scaled
+/// by that weight and rounded as [`step_cost`] does.
+fn uniform_step_costs<T: HeatmapValue>(
+    array: ArrayView2<T>,
+    (x, y): Pos2D,
+    options: &SearchOptions2D<T>,
+) -> (u32, u32) {
+    let straight = options.cost_of(array[[x as usize, y as usize]]);
+    let diagonal = ((straight as f64 * 2.0) * std::f64::consts::FRAC_1_SQRT_2).round() as u32;
+    match options.direction_weights {
+        Some(weights) => {
+            let weigh = |cost: u32| (cost as f64 * weights.up).round() as u32;
+            (weigh(straight), weigh(diagonal))
+        }
+        None => (straight, diagonal),
+    }
+}
+
+impl<T: HeatmapValue> ImagePathfinder2D<T> for JumpPointSearch2D {
+    fn find_path_to_any(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_positions: &[Pos2D],
+        options: &SearchOptions2D<T>,
+        stats: Option<&mut SearchStats>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        // Jumps scan past cells without taking single steps, so they cannot avoid an edge
+        if options.blocked_edges.is_some_and(|edges| !edges.is_empty()) {
+            let astar = AStar2D {
+                heuristic: Heuristic2D::Octile,
+                ..Default::default()
+            };
+            return astar.find_path_to_any(array, start_pos, end_positions, options, stats);
+        }
+
+        let (straight, diagonal) = uniform_step_costs(array, *end_positions.first()?, options);
+
+        // the octile distance is the cost of the shortest unobstructed path
+        let heuristic = |&((x1, y1), _): &(Pos2D, Direction)| {
+            end_positions
+                .iter()
+                .map(|&(x2, y2)| {
+                    let (dx, dy) = (x1.abs_diff(x2), y1.abs_diff(y2));
+                    diagonal * dx.min(dy) + straight * dx.abs_diff(dy)
+                })
+                .min()
+                .unwrap_or(0)
+        };
+
+        let mut tracker = stats.map(|stats| StatsTracker::new(stats, start_pos));
+        let successors = |&(pos, dir): &(Pos2D, Direction)| {
+            if options.should_stop() {
+                return Vec::new();
+            }
+            options.notify_expansion(pos);
+
+            let jump_points: Vec<((Pos2D, Direction), u32)> =
+                pruned_directions(array, pos, dir, options)
+                    .into_iter()
+                    .filter_map(|next_dir| {
+                        let target = jump(array, pos, next_dir, end_positions, options)?;
+                        let steps = pos.0.abs_diff(target.0).max(pos.1.abs_diff(target.1));
+                        let step = if next_dir.0 != 0 && next_dir.1 != 0 {
+                            diagonal
+                        } else {
+                            straight
+                        };
+                        Some(((target, next_dir), steps * step))
+                    })
+                    .collect();
+            options.notify_generated(pos, jump_points.iter().map(|&((p, _), _)| p));
+
+            if let Some(tracker) = &mut tracker {
+                let positions: Vec<Pos2DWithCost> =
+                    jump_points.iter().map(|&((p, _), c)| (p, c)).collect();
+                tracker.record(pos, &positions);
+            }
+
+            jump_points
+        };
+
+        let (jump_points, cost) = astar(&(start_pos, (0, 0)), successors, heuristic, |(p, _)| {
+            end_positions.contains(p)
+        })?;
+
+        if options.was_stopped() || !options.fits_budget(cost) {
+            return None;
+        }
+
+        // Fill in the straight and diagonal runs between consecutive jump points
+        let mut path = vec![start_pos];
+        for &(target, (dx, dy)) in &jump_points[1..] {
+            while *path.last().unwrap() != target {
+                let &(x, y) = path.last().unwrap();
+                path.push(((x as i64 + dx as i64) as u32, (y as i64 + dy as i64) as u32));
+            }
+        }
+
+        Some((path, cost))
+    }
+
+    /// Every step costs the same as on the uniform grid the search assumes, whatever the
+    /// value of the start cell.
+    fn segment_costs(
+        &self,
+        array: ArrayView2<T>,
+        path: &[Pos2D],
+        options: &SearchOptions2D<T>,
+    ) -> Option<Vec<u32>> {
+        let Some(&end) = path.last() else {
+            return Some(Vec::new());
+        };
+        let (straight, diagonal) = uniform_step_costs(array, end, options);
+
+        path.windows(2)
+            .map(|pair| {
+                let (dx, dy) = options.wrap.deltas(pair[0], pair[1], array.dim());
+                match (dx, dy) {
+                    (1, 0) | (0, 1) => Some(straight),
+                    (1, 1) => Some(diagonal),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+// MARK: Theta*
+
+/// A 2D any-angle pathfinder that uses the Theta* algorithm.
+///
+/// Unlike A*, a cell may take its parent's parent as its own parent when the two can see
+/// each other, so the returned path is a list of waypoints joined by straight segments
+/// rather than a list of adjacent cells.
+///
+/// Line of sight is checked with a Bresenham traversal over the heatmap. The segment is
+/// blocked by cells that cannot be entered and by any cell whose value is above
+/// `block_threshold`, if set. A segment costs the sum of the grid steps along its traversal;
+/// with 4-connectivity each diagonal step of the traversal is split into two cardinal steps.
+#[derive(Default)]
+pub struct ThetaStar2D {
+    /// Pixel values above this threshold occlude line of sight, without blocking grid moves.
+    pub block_threshold: Option<u32>,
+}
+
+impl ThetaStar2D {
+    /// The cost of the straight segment from `from` to `to`, or `None` if it is occluded.
+    fn line_of_sight_cost<T: HeatmapValue>(
+        &self,
+        array: ArrayView2<T>,
+        from: Pos2D,
+        to: Pos2D,
+        options: &SearchOptions2D<T>,
+    ) -> Option<u32> {
+        let (mut x, mut y) = (from.0 as i64, from.1 as i64);
+        let (x1, y1) = (to.0 as i64, to.1 as i64);
+        let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+        let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+        let mut error = dx + dy;
+        let mut cost = 0;
+
+        let mut step = |from: (i64, i64), to: (i64, i64)| {
+            let to_pos = (to.0 as u32, to.1 as u32);
+            if self
+                .block_threshold
+                .is_some_and(|threshold| array[[to.0 as usize, to.1 as usize]].into() > threshold)
+            {
+                return None;
+            }
+
+            cost += step_cost(array, (from.0 as u32, from.1 as u32), to_pos, options)?;
+            Some(())
+        };
+
+        while (x, y) != (x1, y1) {
+            let doubled = 2 * error;
+            let (mut nx, mut ny) = (x, y);
+            if doubled >= dy {
+                error += dy;
+                nx += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                ny += sy;
+            }
+
+            if nx != x && ny != y && options.connectivity == Connectivity::Four {
+                step((x, y), (nx, y))?;
+                step((nx, y), (nx, ny))?;
+            } else {
+                step((x, y), (nx, ny))?;
+            }
+
+            (x, y) = (nx, ny);
+        }
+
+        Some(cost)
+    }
+}
+
+impl<T: HeatmapValue> ImagePathfinder2D<T> for ThetaStar2D {
+    fn find_path_to_any(
+        &self,
+        array: ArrayView2<T>,
+        start_pos: Pos2D,
+        end_positions: &[Pos2D],
+        options: &SearchOptions2D<T>,
+        stats: Option<&mut SearchStats>,
+    ) -> Option<(Vec<Pos2D>, u32)> {
+        let mut tracker = stats.map(|stats| StatsTracker::new(stats, start_pos));
+        let mut open = BinaryHeap::from([Reverse((0, start_pos))]);
+        let mut costs = HashMap::from([(start_pos, 0)]);
+        let mut parents = HashMap::from([(start_pos, start_pos)]);
+        let mut closed = HashSet::new();
+
+        while let Some(Reverse((_, pos))) = open.pop() {
+            if options.was_stopped() {
+                return None;
+            }
+
+            if !closed.insert(pos) {
+                continue;
+            }
+
+            if end_positions.contains(&pos) {
+                let mut path = vec![pos];
+                while let Some(&parent) = parents.get(path.last().unwrap())
+                    && parent != *path.last().unwrap()
+                {
+                    path.push(parent);
+                }
+                path.reverse();
+
+                return Some((path, costs[&pos]));
+            }
+
+            let cost = costs[&pos];
+            let parent = parents[&pos];
+
+            for (next, step) in expand(array, pos, options, &mut tracker, &mut None) {
+                if closed.contains(&next) {
+                    continue;
+                }
+
+                // Skip the current cell when the parent can see the neighbour at no extra cost
+                let mut candidate = (cost + step, pos);
+                if parent != pos
+                    && let Some(segment) = self.line_of_sight_cost(array, parent, next, options)
+                    && costs[&parent] + segment <= candidate.0
+                {
+                    candidate = (costs[&parent] + segment, parent);
+                }
+
+                if options.fits_budget(candidate.0)
+                    && costs.get(&next).is_none_or(|&known| candidate.0 < known)
+                {
+                    costs.insert(next, candidate.0);
+                    parents.insert(next, candidate.1);
+
+                    // the minumum cost is the manhattan distance
+                    let estimate = candidate.0
+                        + min_manhattan_distance(next, end_positions, options.wrap, array.dim());
+                    open.push(Reverse((estimate, next)));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Each segment costs the cheaper of the grid move and the line of sight between its
+    /// waypoints, whichever of the two exist, as the search only keeps the cheaper one.
+    fn segment_costs(
+        &self,
+        array: ArrayView2<T>,
+        path: &[Pos2D],
+        options: &SearchOptions2D<T>,
+    ) -> Option<Vec<u32>> {
+        path.windows(2)
+            .map(|pair| {
+                let step = grid_step_cost(array, pair[0], pair[1], options);
+                let segment = self.line_of_sight_cost(array, pair[0], pair[1], options);
+                step.into_iter().chain(segment).min()
+            })
+            .collect()
+    }
+}
+
+// MARK: D* Lite
+
+/// An incremental 2D pathfinder for heatmaps that change between queries, using D* Lite.
+///
+/// The search runs backwards from the goal and keeps the cost to the goal of every cell it
+/// settled. After [`DStarLite2D::update_cell`] changes a few cells, [`DStarLite2D::replan`]
+/// only repairs the costs that the change invalidated, instead of searching from scratch.
+/// The start may move between queries too, e.g. as an agent walks along the path.
+///
+/// **Consistency:** every `replan` returns a path that is optimal for the heatmap as it is at
+/// that moment, with the same cost a fresh [`Dijkstra2D`] search would find. No heuristic is
+/// used, as pixel costs may be 0, so the first plan expands every cell that is cheaper to
+/// reach from the goal than the start is. Ties in cost are broken by the number of steps, so
+/// cells on zero-cost plateaus never hold on to each other's outdated costs.
+pub struct DStarLite2D<T: HeatmapValue = u8> {
+    array: Array2<T>,
+    impassable: Option<T>,
+    connectivity: Connectivity,
+    wrap: Wrap,
+    start: Pos2D,
+    goal: Pos2D,
+    /// The settled distance from each cell to the goal, [`NO_DISTANCE`] if unknown.
+    g: Array2<GoalDistance>,
+    /// The distance from each cell to the goal through its best neighbour, according to `g`.
+    rhs: Array2<GoalDistance>,
+    /// Cells whose `g` and `rhs` differ, keyed by the smaller of the two. Entries that went
+    /// stale are skipped when popped.
+    queue: BinaryHeap<Reverse<(GoalDistance, Pos2D)>>,
+    nodes_expanded: usize,
+}
+
+impl<T: HeatmapValue> DStarLite2D<T> {
+    /// Create a replanner over its own copy of a heatmap. No search runs until
+    /// [`DStarLite2D::replan`] is called.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The heatmap as a 2D ndarray with shape (width, height).
+    /// * `start_pos` - The start position (x, y).
+    /// * `end_pos` - The goal position (x, y).
+    /// * `impassable` - A pixel value that cannot be entered, if any.
+    /// * `connectivity` - Which neighbours a cell is connected to.
+    /// * `wrap` - Which image borders wrap around.
+    pub fn new(
+        array: Array2<T>,
+        start_pos: Pos2D,
+        end_pos: Pos2D,
+        impassable: Option<T>,
+        connectivity: Connectivity,
+        wrap: Wrap,
+    ) -> Self {
+        let dim = array.dim();
+        let mut planner = Self {
+            array,
+            impassable,
+            connectivity,
+            wrap,
+            start: start_pos,
+            goal: end_pos,
+            g: Array2::from_elem(dim, NO_DISTANCE),
+            rhs: Array2::from_elem(dim, NO_DISTANCE),
+            queue: BinaryHeap::new(),
+            nodes_expanded: 0,
+        };
+        planner.rhs[index(end_pos)] = (0, 0);
+        planner.queue.push(Reverse(((0, 0), end_pos)));
+        planner
+    }
+
+    /// The heatmap as it is after all updates so far.
+    pub fn array(&self) -> ArrayView2<'_, T> {
+        self.array.view()
+    }
+
+    /// Move the start, e.g. to the cell an agent has reached. The costs settled so far stay
+    /// valid, as they are measured from the goal.
+    pub fn set_start(&mut self, start_pos: Pos2D) {
+        self.start = start_pos;
+    }
+
+    /// Change the value of one cell. Only the cell and its neighbours are marked for repair;
+    /// the search itself runs on the next [`DStarLite2D::replan`].
+    pub fn update_cell(&mut self, pos: Pos2D, value: T) {
+        if self.array[index(pos)] == value {
+            return;
+        }
+        self.array[index(pos)] = value;
+
+        // The cell's value is part of every step into it and, for diagonals, out of it, so
+        // both the cell and every neighbour may now have a different best step.
+        self.update_vertex(pos);
+        for &offset in neighbour_offsets(self.connectivity) {
+            if let Some(next) = offset_position(pos, offset, self.array.dim(), self.wrap) {
+                self.update_vertex(next);
+            }
+        }
+    }
+
+    /// Bring the settled costs up to date with the updates so far and return the cheapest
+    /// path from the start to the goal.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos2D>, u32)>` - The path found and the total cost, or `None` if the
+    ///   goal cannot be reached.
+    pub fn replan(&mut self) -> Option<(Vec<Pos2D>, u32)> {
+        self.nodes_expanded = 0;
+        self.compute_shortest_path();
+
+        let (cost, _) = self.g[index(self.start)];
+        if cost == UNREACHABLE {
+            return None;
+        }
+        Some((self.extract_path(), cost))
+    }
+
+    /// The number of cells the last [`DStarLite2D::replan`] expanded.
+    pub fn nodes_expanded(&self) -> usize {
+        self.nodes_expanded
+    }
+
+    fn options(&self) -> SearchOptions2D<'static, T> {
+        SearchOptions2D {
+            impassable: self.impassable,
+            connectivity: self.connectivity,
+            wrap: self.wrap,
+            ..Default::default()
+        }
+    }
+
+    fn key(&self, pos: Pos2D) -> GoalDistance {
+        self.g[index(pos)].min(self.rhs[index(pos)])
+    }
+
+    /// Recompute the cost of a cell through its best neighbour and queue it if that no
+    /// longer matches its settled cost.
+    fn update_vertex(&mut self, pos: Pos2D) {
+        if pos != self.goal {
+            self.rhs[index(pos)] =
+                find_neighbours_with_cost(self.array.view(), pos, &self.options())
+                    .into_iter()
+                    .map(|(next, cost)| step_towards_goal(self.g[index(next)], cost))
+                    .min()
+                    .unwrap_or(NO_DISTANCE);
+        }
+        if self.g[index(pos)] != self.rhs[index(pos)] {
+            self.queue.push(Reverse((self.key(pos), pos)));
+        }
+    }
+
+    fn compute_shortest_path(&mut self) {
+        while let Some(&Reverse((key, pos))) = self.queue.peek() {
+            let (g, rhs) = (self.g[index(pos)], self.rhs[index(pos)]);
+            if g == rhs || key != g.min(rhs) {
+                self.queue.pop();
+                continue;
+            }
+            let start = index(self.start);
+            if key >= self.key(self.start) && self.g[start] == self.rhs[start] {
+                break;
+            }
+
+            self.queue.pop();
+            self.nodes_expanded += 1;
+            if g > rhs {
+                self.g[index(pos)] = rhs;
+            } else {
+                self.g[index(pos)] = NO_DISTANCE;
+                self.update_vertex(pos);
+            }
+            for (prev, _) in find_predecessors_with_cost(self.array.view(), pos, &self.options()) {
+                self.update_vertex(prev);
+            }
+        }
+    }
+
+    /// Follow steps whose cost matches the settled distances from the start to the goal.
+    /// Every such step is one step closer to the goal, so the walk always arrives.
+    fn extract_path(&self) -> Vec<Pos2D> {
+        let options = self.options();
+        let mut path = vec![self.start];
+        let mut pos = self.start;
+
+        while pos != self.goal {
+            let distance = self.g[index(pos)];
+            pos = find_neighbours_with_cost(self.array.view(), pos, &options)
+                .into_iter()
+                .find(|&(next, step)| step_towards_goal(self.g[index(next)], step) == distance)
+                .map(|(next, _)| next)
+                .expect("a settled cell has a neighbour one step closer to the goal");
+            path.push(pos);
+        }
+        path
+    }
+}
+
+/// The cost from a cell to the goal, then the number of steps as a tie-break. Every step
+/// adds a step, so even zero-cost steps lead strictly closer to the goal.
+type GoalDistance = (u32, u32);
+
+/// The distance of a cell with no known path to the goal.
+const NO_DISTANCE: GoalDistance = (UNREACHABLE, UNREACHABLE);
+
+/// The distance to the goal when stepping into a cell that is `distance` away, at `cost`.
+fn step_towards_goal((cost, steps): GoalDistance, step: u32) -> GoalDistance {
+    match cost.checked_add(step) {
+        Some(total) if total != UNREACHABLE => (total, steps + 1),
+        _ => NO_DISTANCE,
+    }
+}
+
+/// The array index of a position.
+fn index(pos: Pos2D) -> [usize; 2] {
+    [pos.0 as usize, pos.1 as usize]
 }
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Prunes neighbours that can only be reached above a maximum path cost.
+///
+/// The cheapest known cost of every generated node is tracked, so a neighbour is dropped as
+/// soon as reaching it through the expanded node already exceeds `max_cost`. Heuristics are
+/// not taken into account, as they are not guaranteed to underestimate on every heatmap, and
+/// pruning on an overestimate could reject a path that fits the budget.
+pub(crate) struct CostBudget<P> {
+    max_cost: u32,
+    costs: HashMap<P, u32>,
+}
+
+impl<P: Copy + Eq + Hash> CostBudget<P> {
+    /// A budget of `max_cost` for a search starting at `start`.
+    pub(crate) fn new(max_cost: u32, start: P) -> Self {
+        Self {
+            max_cost,
+            costs: HashMap::from([(start, 0)]),
+        }
+    }
+
+    /// Remove the neighbours of `pos` that cannot be reached within the budget, and record
+    /// the costs of the remaining ones.
+    pub(crate) fn prune(&mut self, pos: P, neighbours: &mut Vec<(P, u32)>) {
+        let cost = self.costs.get(&pos).copied().unwrap_or(0);
+
+        neighbours.retain(|&(next, step)| {
+            let next_cost = cost.saturating_add(step);
+            if next_cost > self.max_cost {
+                return false;
+            }
+
+            let known = self.costs.entry(next).or_insert(next_cost);
+            *known = (*known).min(next_cost);
+            true
+        });
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Number of expansions between two checks of the clock.
+pub const DEADLINE_CHECK_INTERVAL: usize = 4096;
+
+/// A time budget for a search. Searches given a deadline stop expanding nodes once it has
+/// passed and return no result; check [`SearchDeadline::has_expired`] afterwards to tell a
+/// timeout apart from a search that found no path.
+///
+/// The clock is only read every [`DEADLINE_CHECK_INTERVAL`] expansions, so the overhead is
+/// negligible, and a search may overrun its budget by the time those expansions take.
+#[derive(Debug)]
+pub struct SearchDeadline {
+    at: Instant,
+    expansions: AtomicUsize,
+    expired: AtomicBool,
+}
+
+impl SearchDeadline {
+    /// A deadline `budget` from now.
+    pub fn after(budget: Duration) -> Self {
+        Self {
+            at: Instant::now() + budget,
+            expansions: AtomicUsize::new(0),
+            expired: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether a search noticed that the deadline has passed.
+    pub fn has_expired(&self) -> bool {
+        self.expired.load(Ordering::Relaxed)
+    }
+
+    /// Record an expansion, checking the clock every [`DEADLINE_CHECK_INTERVAL`] expansions.
+    /// Returns whether the search should stop.
+    pub(crate) fn tick(&self) -> bool {
+        if self.has_expired() {
+            return true;
+        }
+
+        let expansions = self.expansions.fetch_add(1, Ordering::Relaxed) + 1;
+        if expansions.is_multiple_of(DEADLINE_CHECK_INTERVAL) && Instant::now() >= self.at {
+            self.expired.store(true, Ordering::Relaxed);
+            return true;
+        }
+
+        false
+    }
+}
@@ -1,7 +1,30 @@
 pub mod bidimensional;
+mod budget;
+pub mod deadline;
+pub mod node_limit;
+pub mod overlay;
 pub mod temporal;
 
+/// The version of this crate, as set in its `Cargo.toml`.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 pub use bidimensional::{
-    AStar2D, Dijkstra2D, Fringe2D, ImagePathfinder2D, Pos2D, Pos2DWithCost, load_png_to_ndarray,
+    AStar2D, Connectivity, DStarLite2D, Dijkstra2D, Dijkstra2DBidirectional, DirectionWeights,
+    Disjoint, Fringe2D, HeatmapValue, Heuristic2D, ImagePathfinder2D, JumpPointSearch2D,
+    K_PATHS_CANDIDATES_PER_PATH, NO_DIRECTION, Pos2D, Pos2DWithCost, SearchObserver,
+    SearchOptions2D, SearchStats, ThetaStar2D, TieBreak, UNREACHABLE, Wrap, drop_collinear,
+    is_reachable, load_image_to_ndarray, load_png_to_ndarray, load_png_weighted, open_image,
+    path_length, simplify_path, touches_border,
+};
+pub use deadline::{DEADLINE_CHECK_INTERVAL, SearchDeadline};
+pub use node_limit::NodeLimit;
+pub use overlay::{
+    Colormap, PathStyle, draw_circle, draw_line, draw_path, render_field_2d, save_field_2d,
+    save_overlay_2d,
+};
+pub use temporal::{
+    AStarTemporal, AxisStep, DijkstraTemporal, FringeTemporal, Pos3D, Pos3DWithCost, Pos4D,
+    Pos4DWithCost, ProgressReporter, Reach, Reach4D, RouteFailure, SearchOptionsTemporal,
+    TemporalProgress, frame_costs, interpolate_route, is_16_bit, load_images_to_volume,
+    load_images_to_volume_u16,
 };
-pub use temporal::{AStarTemporal, DijkstraTemporal, Pos3D, Pos3DWithCost, load_images_to_volume};
@@ -0,0 +1,47 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// A cap on the number of nodes a search may expand, bounding the memory its frontier and
+/// visited sets can take on pathological inputs. Searches given a limit stop expanding nodes
+/// once it is exceeded and return no result; check [`NodeLimit::was_exceeded`] afterwards to
+/// tell an exceeded limit apart from a search that found no path.
+///
+/// Unlike the clock of a [`SearchDeadline`](crate::SearchDeadline), the count is compared on
+/// every expansion, so a search never expands more than `max_nodes` nodes.
+#[derive(Debug)]
+pub struct NodeLimit {
+    max_nodes: usize,
+    expansions: AtomicUsize,
+    exceeded: AtomicBool,
+}
+
+impl NodeLimit {
+    /// A limit of `max_nodes` expansions.
+    pub fn new(max_nodes: usize) -> Self {
+        Self {
+            max_nodes,
+            expansions: AtomicUsize::new(0),
+            exceeded: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether a search tried to expand more nodes than the limit allows.
+    pub fn was_exceeded(&self) -> bool {
+        self.exceeded.load(Ordering::Relaxed)
+    }
+
+    /// Record an expansion. Returns whether the search should stop, i.e. whether this
+    /// expansion is one more than the limit allows.
+    pub(crate) fn tick(&self) -> bool {
+        if self.was_exceeded() {
+            return true;
+        }
+
+        let expansions = self.expansions.fetch_add(1, Ordering::Relaxed) + 1;
+        if expansions > self.max_nodes {
+            self.exceeded.store(true, Ordering::Relaxed);
+            return true;
+        }
+
+        false
+    }
+}
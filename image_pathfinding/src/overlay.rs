@@ -0,0 +1,207 @@
+use crate::bidimensional::{Pos2D, UNREACHABLE, open_image};
+use image::{ImageResult, Rgb, RgbImage, Rgba, RgbaImage};
+use numpy::ndarray::ArrayView2;
+
+/// Draw a filled circle on the image at the given position
+pub fn draw_circle(img: &mut RgbImage, center_x: u32, center_y: u32, radius: u32, color: Rgb<u8>) {
+    let width = img.width();
+    let height = img.height();
+
+    for dy in -(radius as i32)..=(radius as i32) {
+        for dx in -(radius as i32)..=(radius as i32) {
+            let distance_squared = (dx * dx + dy * dy) as f32;
+            if distance_squared <= (radius * radius) as f32 {
+                let x = center_x as i32 + dx;
+                let y = center_y as i32 + dy;
+
+                if x >= 0 && x < width as i32 && y >= 0 && y < height as i32 {
+                    img.put_pixel(x as u32, y as u32, color);
+                }
+            }
+        }
+    }
+}
+
+/// Draw a line segment between two points by stamping circles along its Bresenham line
+pub fn draw_line(img: &mut RgbImage, from: Pos2D, to: Pos2D, radius: u32, color: Rgb<u8>) {
+    let (mut x, mut y) = (from.0 as i64, from.1 as i64);
+    let (end_x, end_y) = (to.0 as i64, to.1 as i64);
+    let dx = (end_x - x).abs();
+    let dy = -(end_y - y).abs();
+    let step_x = if x < end_x { 1 } else { -1 };
+    let step_y = if y < end_y { 1 } else { -1 };
+    let mut error = dx + dy;
+
+    loop {
+        draw_circle(img, x as u32, y as u32, radius, color);
+        if x == end_x && y == end_y {
+            break;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+/// How path points are rendered onto the output images
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathStyle {
+    /// A filled circle at each path point
+    #[default]
+    Dots,
+    /// Line segments connecting consecutive path points
+    Line,
+}
+
+/// Draw the points of a path with the given style and thickness (circle radius or line half-width)
+pub fn draw_path(
+    img: &mut RgbImage,
+    points: &[Pos2D],
+    style: PathStyle,
+    thickness: u32,
+    color: Rgb<u8>,
+) {
+    match style {
+        PathStyle::Dots => {
+            for &(x, y) in points {
+                draw_circle(img, x, y, thickness, color);
+            }
+        }
+        PathStyle::Line => {
+            if let [single] = points {
+                draw_circle(img, single.0, single.1, thickness, color);
+            }
+            for segment in points.windows(2) {
+                draw_line(img, segment[0], segment[1], thickness, color);
+            }
+        }
+    }
+}
+
+/// Draw a path onto a copy of an image and save it, as the CLI does for its overlays.
+/// The input may be in any format [`open_image`] decodes; the output format follows the
+/// extension of `out_path`.
+///
+/// # Arguments
+/// * `image_path` - The image to draw on.
+/// * `path` - The path points, in (x, y) pixel coordinates.
+/// * `out_path` - Where to save the overlay.
+/// * `style` - Whether to draw dots or connecting lines.
+/// * `thickness` - The dot radius, or line half-width, in pixels.
+/// * `color` - The color of the path.
+pub fn save_overlay_2d(
+    image_path: &str,
+    path: &[Pos2D],
+    out_path: &str,
+    style: PathStyle,
+    thickness: u32,
+    color: Rgb<u8>,
+) -> ImageResult<()> {
+    let mut img = open_image(image_path)?.to_rgb8();
+    draw_path(&mut img, path, style, thickness, color);
+    img.save(out_path)
+}
+
+/// The color scale a cost or distance field is rendered with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Colormap {
+    /// Perceptually uniform, from dark purple (cheapest) through teal to yellow (costliest)
+    #[default]
+    Viridis,
+    /// From black (cheapest) to white (costliest)
+    Grayscale,
+}
+
+/// Samples of viridis at evenly spaced positions, linearly interpolated in between
+const VIRIDIS: [[u8; 3]; 9] = [
+    [68, 1, 84],
+    [71, 44, 122],
+    [59, 81, 139],
+    [44, 113, 142],
+    [33, 144, 141],
+    [39, 173, 129],
+    [92, 200, 99],
+    [170, 220, 50],
+    [253, 231, 37],
+];
+
+impl Colormap {
+    /// The color at position `t` of the scale, clamped to 0.0 (cheapest) to 1.0 (costliest)
+    pub fn color(self, t: f64) -> Rgb<u8> {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Colormap::Viridis => {
+                let scaled = t * (VIRIDIS.len() - 1) as f64;
+                let index = (scaled as usize).min(VIRIDIS.len() - 2);
+                let (low, high) = (VIRIDIS[index], VIRIDIS[index + 1]);
+                let fraction = scaled - index as f64;
+                Rgb(std::array::from_fn(|c| {
+                    (low[c] as f64 + (high[c] as f64 - low[c] as f64) * fraction).round() as u8
+                }))
+            }
+            Colormap::Grayscale => {
+                let level = (t * 255.0).round() as u8;
+                Rgb([level; 3])
+            }
+        }
+    }
+}
+
+/// Render a cost or distance field, such as [`Dijkstra2D::distance_field`], as a color image.
+/// The reachable costs are normalized from their minimum to their maximum across the colormap,
+/// and cells holding [`UNREACHABLE`] are drawn in `unreachable` or, if it is `None`, left
+/// transparent.
+///
+/// [`Dijkstra2D::distance_field`]: crate::bidimensional::Dijkstra2D::distance_field
+pub fn render_field_2d(
+    field: ArrayView2<u32>,
+    colormap: Colormap,
+    unreachable: Option<Rgb<u8>>,
+) -> RgbaImage {
+    let reachable = || field.iter().copied().filter(|&cost| cost != UNREACHABLE);
+    let min = reachable().min().unwrap_or(0);
+    let range = reachable().max().unwrap_or(0) - min;
+
+    let (width, height) = field.dim();
+    RgbaImage::from_fn(width as u32, height as u32, |x, y| {
+        let cost = field[[x as usize, y as usize]];
+        if cost == UNREACHABLE {
+            return match unreachable {
+                Some(Rgb([r, g, b])) => Rgba([r, g, b, 255]),
+                None => Rgba([0, 0, 0, 0]),
+            };
+        }
+
+        let t = if range == 0 {
+            0.0
+        } else {
+            (cost - min) as f64 / range as f64
+        };
+        let Rgb([r, g, b]) = colormap.color(t);
+        Rgba([r, g, b, 255])
+    })
+}
+
+/// Render a cost or distance field with [`render_field_2d`] and save it. The image has an
+/// alpha channel, so the format, which follows the extension of `out_path`, should support
+/// transparency, such as PNG.
+///
+/// # Arguments
+/// * `field` - The cost of each cell, with shape (width, height).
+/// * `out_path` - Where to save the image.
+/// * `colormap` - The color scale of the costs.
+/// * `unreachable` - The color of unreachable cells, or `None` to leave them transparent.
+pub fn save_field_2d(
+    field: ArrayView2<u32>,
+    out_path: &str,
+    colormap: Colormap,
+    unreachable: Option<Rgb<u8>>,
+) -> ImageResult<()> {
+    render_field_2d(field, colormap, unreachable).save(out_path)
+}
@@ -1,5 +1,14 @@
-use numpy::ndarray::{Array3, ArrayView3};
-use pathfinding::prelude::{astar, dijkstra};
+use crate::bidimensional::{HeatmapValue, open_image};
+use crate::budget::CostBudget;
+use crate::deadline::SearchDeadline;
+use crate::node_limit::NodeLimit;
+use image::{DynamicImage, GenericImageView, ImageBuffer, Luma, Primitive};
+use numpy::ndarray::{Array3, ArrayView3, ArrayView4, Axis};
+use pathfinding::prelude::{astar, dijkstra, fringe};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::ControlFlow;
 
 /// A position in the temporal volume (x, y, t).
 pub type Pos3D = (u32, u32, u32);
@@ -7,34 +16,145 @@ pub type Pos3D = (u32, u32, u32);
 /// A position in the temporal volume with a cost.
 pub type Pos3DWithCost = (Pos3D, u32);
 
+/// A position in a 4D volume, in the order of the volume's axes, e.g. (x, y, z, t).
+pub type Pos4D = (u32, u32, u32, u32);
+
+/// A position in a 4D volume with a cost.
+pub type Pos4DWithCost = (Pos4D, u32);
+
+/// Number of elements a route may skip per step along each of the two non-axis dimensions,
+/// in axis order, e.g. `(x, y)` when routing along time.
+///
+/// When routing along x or y, time still advances by exactly one frame per step, so only
+/// the first value applies, to the remaining spatial dimension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reach(pub usize, pub usize);
+
+impl Default for Reach {
+    fn default() -> Self {
+        Reach(1, 1)
+    }
+}
+
+impl From<usize> for Reach {
+    /// The same reach along both non-axis dimensions.
+    fn from(reach: usize) -> Self {
+        Reach(reach, reach)
+    }
+}
+
+impl From<(usize, usize)> for Reach {
+    fn from((reach_dim0, reach_dim1): (usize, usize)) -> Self {
+        Reach(reach_dim0, reach_dim1)
+    }
+}
+
+/// Number of elements a route through a 4D volume may skip per step along each of the three
+/// non-axis dimensions, in axis order, e.g. `(x, y, z)` when routing along t.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reach4D(pub usize, pub usize, pub usize);
+
+impl Default for Reach4D {
+    fn default() -> Self {
+        Reach4D(1, 1, 1)
+    }
+}
+
+impl From<usize> for Reach4D {
+    /// The same reach along all three non-axis dimensions.
+    fn from(reach: usize) -> Self {
+        Reach4D(reach, reach, reach)
+    }
+}
+
+impl From<(usize, usize, usize)> for Reach4D {
+    fn from((reach_dim0, reach_dim1, reach_dim2): (usize, usize, usize)) -> Self {
+        Reach4D(reach_dim0, reach_dim1, reach_dim2)
+    }
+}
+
+/// The range of indices a route may advance along the routing axis in one step, e.g.
+/// `AxisStep { min: 1, max: 3 }` to skip up to two frames at once. Skipped indices are not
+/// visited and cost nothing. `min` must be at least 1 and at most `max`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AxisStep {
+    /// The smallest advance per step.
+    pub min: u32,
+    /// The largest advance per step.
+    pub max: u32,
+}
+
+impl Default for AxisStep {
+    /// Advance exactly one index per step.
+    fn default() -> Self {
+        AxisStep { min: 1, max: 1 }
+    }
+}
+
 // MARK: Helpers
 
+/// Whether an image stores more than 8 bits per channel, e.g. a 16-bit PNG or TIFF.
+/// Such images are loaded with [`load_images_to_volume_u16`] to keep their full precision.
+pub fn is_16_bit(img: &DynamicImage) -> bool {
+    let color = img.color();
+    color.bits_per_pixel() / color.channel_count() as u16 > 8
+}
+
 /// Load a list of grayscale images into a temporal volume (Width, Height, Time).
 /// Note: Internally ndarray uses (x, y, t) indexing, so [x, y, t].
+/// Images with more than 8 bits per channel are downcast; see [`load_images_to_volume_u16`].
 pub fn load_images_to_volume(paths: &[String]) -> Array3<u8> {
+    load_volume(paths, DynamicImage::into_luma8)
+}
+
+/// Load a list of grayscale images into a 16-bit temporal volume (Width, Height, Time),
+/// keeping the full precision of 16-bit PNG and TIFF frames.
+/// 8-bit frames are scaled up to the 16-bit range.
+pub fn load_images_to_volume_u16(paths: &[String]) -> Array3<u16> {
+    load_volume(paths, DynamicImage::into_luma16)
+}
+
+/// Load the images into a volume, converting each with `to_luma`. All images must share the
+/// dimensions and the bit depth of the first one.
+fn load_volume<T: Primitive>(
+    paths: &[String],
+    to_luma: fn(DynamicImage) -> ImageBuffer<Luma<T>, Vec<T>>,
+) -> Array3<T> {
     if paths.is_empty() {
-        return Array3::zeros((0, 0, 0));
+        return Array3::from_elem((0, 0, 0), T::DEFAULT_MIN_VALUE);
     }
 
-    // Load first image to get dimensions
-    let first_img = image::open(&paths[0])
-        .expect("Failed to open first image")
-        .to_luma8();
+    // Load first image to get dimensions and bit depth
+    let first_img = open_image(&paths[0])
+        .unwrap_or_else(|err| panic!("Failed to load image at {}: {}", paths[0], err));
     let (width, height) = first_img.dimensions();
+    let first_is_16_bit = is_16_bit(&first_img);
     let depth = paths.len(); // Time dimension
 
-    let mut volume = Array3::zeros((width as usize, height as usize, depth));
+    let mut volume = Array3::from_elem(
+        (width as usize, height as usize, depth),
+        T::DEFAULT_MIN_VALUE,
+    );
 
     for (t, path) in paths.iter().enumerate() {
-        let img = image::open(path)
-            .unwrap_or_else(|_| panic!("Failed to open image at {}", path))
-            .to_luma8();
+        let img = open_image(path)
+            .unwrap_or_else(|err| panic!("Failed to load image at {}: {}", path, err));
 
         if img.dimensions() != (width, height) {
             panic!("All images must have the same dimensions");
         }
+        if is_16_bit(&img) != first_is_16_bit {
+            panic!(
+                "All images must have the same bit depth: {} is {}-bit, but {} is {}-bit",
+                paths[0],
+                if first_is_16_bit { 16 } else { 8 },
+                path,
+                if first_is_16_bit { 8 } else { 16 }
+            );
+        }
 
         // Copy pixels
+        let img = to_luma(img);
         for y in 0..height {
             for x in 0..width {
                 volume[[x as usize, y as usize, t]] = img.get_pixel(x, y)[0];
@@ -45,14 +165,19 @@ pub fn load_images_to_volume(paths: &[String]) -> Array3<u8> {
     volume
 }
 
-/// Find neighbours with reach constraint: always move +1 along axis, can move within reach in other dimensions.
+/// Find neighbours with reach constraint: always move forward along axis by an advance within
+/// `axis_step`, can move within reach in other dimensions.
 /// For temporal routing: axis=2 (time) is default, reach limits movement in x and y dimensions.
-fn find_neighbours_with_reach(
-    volume: ArrayView3<u8>,
+/// With `scale_diagonal`, steps moving along both non-axis dimensions cost √2 times more.
+fn find_neighbours_with_reach<T: HeatmapValue>(
+    volume: ArrayView3<T>,
     pos: Pos3D,
     axis: usize,
-    reach: usize,
+    reach: Reach,
+    axis_step: AxisStep,
+    scale_diagonal: bool,
 ) -> Vec<Pos3DWithCost> {
+    let Reach(reach_dim0, reach_dim1) = reach;
     let (x, y, t) = pos;
     let (width, height, depth) = volume.dim(); // (x, y, t)
 
@@ -64,42 +189,51 @@ fn find_neighbours_with_reach(
 
     // Generate offsets for non-axis dimensions
     // For temporal routing with axis=2 (time), we generate offsets for x and y
-    // Offset shape: (2*reach+1)^(ndim-1) = (2*reach+1)^2 for 3D
+    // Offset shape: (2*reach_dim0+1) * (2*reach_dim1+1) for 3D
     let mut offsets = Vec::new();
 
+    // Advances past the end of the axis are dropped by the bounds check below
+    let advances = axis_step.min as i32..=axis_step.max as i32;
+
     match axis {
         0 => {
-            // Moving along x axis (always +1), generate offsets for y and t
-            // But we always move +1 in x, so offsets are (1, dy, dt) where dt=1
+            // Moving forward along x axis, generate offsets for y and t
+            // Time always advances by one frame, so offsets are (dx, dy, dt) where dt=1
             if x as usize >= width - 1 {
                 return neighbours;
             }
-            for dy in -(reach as i32)..=(reach as i32) {
-                if (t as usize) < depth - 1 {
-                    offsets.push((1i32, dy, 1u32));
+            for dx in advances {
+                for dy in -(reach_dim0 as i32)..=(reach_dim0 as i32) {
+                    if (t as usize) < depth - 1 {
+                        offsets.push((dx, dy, 1u32));
+                    }
                 }
             }
         }
         1 => {
-            // Moving along y axis (always +1), generate offsets for x and t
-            // But we always move +1 in y, so offsets are (dx, 1, dt) where dt=1
+            // Moving forward along y axis, generate offsets for x and t
+            // Time always advances by one frame, so offsets are (dx, dy, dt) where dt=1
             if y as usize >= height - 1 {
                 return neighbours;
             }
-            for dx in -(reach as i32)..=(reach as i32) {
-                if (t as usize) < depth - 1 {
-                    offsets.push((dx, 1i32, 1u32));
+            for dy in advances {
+                for dx in -(reach_dim0 as i32)..=(reach_dim0 as i32) {
+                    if (t as usize) < depth - 1 {
+                        offsets.push((dx, dy, 1u32));
+                    }
                 }
             }
         }
         2 => {
-            // Moving along t axis (time, always +1), generate offsets for x and y
+            // Moving forward along t axis (time), generate offsets for x and y
             if t as usize >= depth - 1 {
                 return neighbours;
             }
-            for dx in -(reach as i32)..=(reach as i32) {
-                for dy in -(reach as i32)..=(reach as i32) {
-                    offsets.push((dx, dy, 1u32));
+            for dt in advances {
+                for dx in -(reach_dim0 as i32)..=(reach_dim0 as i32) {
+                    for dy in -(reach_dim1 as i32)..=(reach_dim1 as i32) {
+                        offsets.push((dx, dy, dt as u32));
+                    }
                 }
             }
         }
@@ -114,12 +248,8 @@ fn find_neighbours_with_reach(
 
         // Check bounds
         if nx >= 0 && nx < width as i32 && ny >= 0 && ny < height as i32 && nt < depth as u32 {
-            let nx_u = nx as u32;
-            let ny_u = ny as u32;
-
-            // Cost is the value at the *destination* node
-            let cost = volume[[nx_u as usize, ny_u as usize, nt as usize]] as u32;
-            neighbours.push(((nx_u, ny_u, nt), cost));
+            let next = (nx as u32, ny as u32, nt);
+            neighbours.push((next, step_cost(volume, pos, next, scale_diagonal)));
         }
     }
 
@@ -128,8 +258,8 @@ fn find_neighbours_with_reach(
 
 /// Generate all positions at a specific axis index.
 /// For temporal routing: if axis=2 and index=0, returns all (x, y, 0) positions.
-fn generate_positions_at_axis_index(
-    volume: ArrayView3<u8>,
+fn generate_positions_at_axis_index<T: HeatmapValue>(
+    volume: ArrayView3<T>,
     axis: usize,
     index: usize,
 ) -> Vec<Pos3D> {
@@ -137,33 +267,27 @@ fn generate_positions_at_axis_index(
     let mut positions = Vec::new();
 
     match axis {
-        0 => {
-            // All positions with x = index
-            if index < width {
-                for y in 0..height {
-                    for t in 0..depth {
-                        positions.push((index as u32, y as u32, t as u32));
-                    }
+        // All positions with x = index
+        0 if index < width => {
+            for y in 0..height {
+                for t in 0..depth {
+                    positions.push((index as u32, y as u32, t as u32));
                 }
             }
         }
-        1 => {
-            // All positions with y = index
-            if index < height {
-                for x in 0..width {
-                    for t in 0..depth {
-                        positions.push((x as u32, index as u32, t as u32));
-                    }
+        // All positions with y = index
+        1 if index < height => {
+            for x in 0..width {
+                for t in 0..depth {
+                    positions.push((x as u32, index as u32, t as u32));
                 }
             }
         }
-        2 => {
-            // All positions with t = index
-            if index < depth {
-                for x in 0..width {
-                    for y in 0..height {
-                        positions.push((x as u32, y as u32, index as u32));
-                    }
+        // All positions with t = index
+        2 if index < depth => {
+            for x in 0..width {
+                for y in 0..height {
+                    positions.push((x as u32, y as u32, index as u32));
                 }
             }
         }
@@ -174,7 +298,11 @@ fn generate_positions_at_axis_index(
 }
 
 /// Generate default start positions (all positions at axis=0) or end positions (all positions at axis=-1).
-fn generate_default_starts_ends(volume: ArrayView3<u8>, axis: usize, is_start: bool) -> Vec<Pos3D> {
+fn generate_default_starts_ends<T: HeatmapValue>(
+    volume: ArrayView3<T>,
+    axis: usize,
+    is_start: bool,
+) -> Vec<Pos3D> {
     let (width, height, depth) = volume.dim(); // (x, y, t)
     let axis = if axis >= 3 { 2 } else { axis };
 
@@ -192,6 +320,620 @@ fn generate_default_starts_ends(volume: ArrayView3<u8>, axis: usize, is_start: b
     }
 }
 
+/// The cost of the step from `from` to `to`: the value at the *destination* node, times √2 with
+/// `scale_diagonal` if the step moves along both non-axis dimensions.
+fn step_cost<T: HeatmapValue>(
+    volume: ArrayView3<T>,
+    (x, y, t): Pos3D,
+    (nx, ny, nt): Pos3D,
+    scale_diagonal: bool,
+) -> u32 {
+    let cost = volume[[nx as usize, ny as usize, nt as usize]].into();
+
+    // A step always moves along the axis, so it is diagonal when all three coordinates change
+    if scale_diagonal && x != nx && y != ny && t != nt {
+        (cost as f64 * std::f64::consts::SQRT_2).round() as u32
+    } else {
+        cost
+    }
+}
+
+/// Split the cost of a route by the index along `axis` at which it was incurred: each step's cost
+/// is counted at the slice it enters, so the start slice and skipped slices hold 0. The returned
+/// costs hold one entry per index along `axis` and sum to the cost of the route.
+///
+/// # Arguments
+///
+/// * `volume` - The temporal volume (Width, Height, Time) i.e. (x, y, t) the route was found in
+/// * `route` - The route, as returned by the temporal routers
+/// * `axis` - The axis the route was found along (default: 2 for time)
+/// * `scale_diagonal` - Whether the route was found with [`SearchOptionsTemporal::scale_diagonal`]
+pub fn frame_costs<T: HeatmapValue>(
+    volume: ArrayView3<T>,
+    route: &[Pos3D],
+    axis: Option<usize>,
+    scale_diagonal: bool,
+) -> Vec<u32> {
+    let axis = axis.unwrap_or(2);
+    let mut costs = vec![0; volume.len_of(Axis(axis.min(2)))];
+
+    for step in route.windows(2) {
+        let frame = axis_coordinate(step[1], axis) as usize;
+        costs[frame] += step_cost(volume, step[0], step[1], scale_diagonal);
+    }
+
+    costs
+}
+
+/// Fill the indices along `axis` that a route skips, e.g. with an [`AxisStep`] maximum above 1,
+/// so that it holds one point per index between its start and end. The other two coordinates
+/// of each filled point are linearly interpolated between the points around it and rounded.
+///
+/// # Arguments
+///
+/// * `route` - The route, as returned by the temporal routers
+/// * `axis` - The axis the route was found along (default: 2 for time)
+pub fn interpolate_route(route: &[Pos3D], axis: Option<usize>) -> Vec<Pos3D> {
+    let axis = axis.unwrap_or(2).min(2);
+    let mut filled = Vec::with_capacity(route.len());
+
+    for step in route.windows(2) {
+        let (from, to) = (<[u32; 3]>::from(step[0]), <[u32; 3]>::from(step[1]));
+        filled.push(step[0]);
+
+        let skipped = to[axis].saturating_sub(from[axis]);
+        for i in 1..skipped {
+            let fraction = i as f64 / skipped as f64;
+            let [x, y, t] = std::array::from_fn(|k| {
+                let offset = (to[k] as f64 - from[k] as f64) * fraction;
+                (from[k] as f64 + offset).round() as u32
+            });
+            filled.push((x, y, t));
+        }
+    }
+    filled.extend(route.last());
+
+    filled
+}
+
+/// The coordinate of `pos` along `axis`.
+fn axis_coordinate((x, y, t): Pos3D, axis: usize) -> u32 {
+    match axis {
+        0 => x,
+        1 => y,
+        _ => t,
+    }
+}
+
+/// Minimum distance to any end position (for multi-end heuristic)
+fn min_distance_to_ends(pos: Pos3D, ends: &[Pos3D], axis: usize) -> u32 {
+    let (x, y, t) = pos;
+    let mut min_dist = u32::MAX;
+
+    for &(ex, ey, et) in ends {
+        match axis {
+            0 => {
+                // Moving along x axis
+                let spatial_dist = y.abs_diff(ey) + t.abs_diff(et);
+                if x <= ex {
+                    min_dist = min_dist.min(spatial_dist);
+                }
+            }
+            1 => {
+                // Moving along y axis
+                let spatial_dist = x.abs_diff(ex) + t.abs_diff(et);
+                if y <= ey {
+                    min_dist = min_dist.min(spatial_dist);
+                }
+            }
+            2 => {
+                // Moving along t axis (time)
+                let spatial_dist = x.abs_diff(ex) + y.abs_diff(ey);
+                if t <= et {
+                    min_dist = min_dist.min(spatial_dist);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    min_dist
+}
+
+// MARK: Progress
+
+/// A snapshot of a running temporal search, passed to progress callbacks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TemporalProgress {
+    /// Number of nodes expanded so far, across all start positions.
+    pub nodes_expanded: usize,
+    /// The cost of the cheapest complete route found so far, if any.
+    /// Routes are only complete once the search from a start position finishes.
+    pub best_cost: Option<u32>,
+}
+
+/// Reports the progress of a temporal search every `interval` expanded nodes.
+/// Reporting is throttled this way so the callback can be expensive (e.g. call into Python)
+/// without slowing the search down.
+pub struct ProgressReporter<'a> {
+    /// Number of expanded nodes between two calls to `callback`.
+    pub interval: usize,
+    /// Called with the current progress. Returning [`ControlFlow::Break`] aborts the search.
+    pub callback: &'a mut dyn FnMut(TemporalProgress) -> ControlFlow<()>,
+}
+
+/// Options controlling a temporal search, beyond the shape of the search graph.
+#[derive(Default)]
+pub struct SearchOptionsTemporal<'a> {
+    /// Receives the progress of the search, and may abort it.
+    pub progress: Option<ProgressReporter<'a>>,
+    /// A time budget after which the search is aborted.
+    pub deadline: Option<&'a SearchDeadline>,
+    /// A cap on the number of expanded nodes, across all start positions, after which the
+    /// search is aborted.
+    pub node_limit: Option<&'a NodeLimit>,
+    /// An inclusive maximum route cost. Nodes that can only be reached above it are not
+    /// expanded, and no route costing more is returned.
+    pub max_cost: Option<u32>,
+    /// Whether steps moving along both non-axis dimensions at once cost √2 times the value
+    /// they enter, like diagonal steps in 2D. When routing along x or y, time advances with
+    /// every step, so this applies to every step that also moves in the remaining dimension.
+    pub scale_diagonal: bool,
+    /// How far each step advances along the routing axis (default: exactly one index).
+    pub axis_step: AxisStep,
+}
+
+/// Counts expanded nodes and forwards them to the reporter, remembering whether the
+/// reporter aborted the search, the deadline passed, or the node limit was exceeded.
+struct ProgressTracker<'r, 'd> {
+    reporter: Option<ProgressReporter<'r>>,
+    deadline: Option<&'d SearchDeadline>,
+    node_limit: Option<&'d NodeLimit>,
+    progress: TemporalProgress,
+    aborted: bool,
+}
+
+impl ProgressTracker<'_, '_> {
+    fn record_expansion(&mut self) {
+        self.progress.nodes_expanded += 1;
+
+        if self.deadline.is_some_and(SearchDeadline::tick)
+            || self.node_limit.is_some_and(NodeLimit::tick)
+        {
+            self.aborted = true;
+        }
+
+        if let Some(reporter) = &mut self.reporter
+            && self
+                .progress
+                .nodes_expanded
+                .is_multiple_of(reporter.interval.max(1))
+            && (reporter.callback)(self.progress).is_break()
+        {
+            self.aborted = true;
+        }
+    }
+}
+
+/// Why a temporal search found no route.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouteFailure {
+    /// There were no start or no end positions, e.g. because the volume is empty.
+    NoEndpoints,
+    /// Every end lies before every start along the routing axis, which routes only move
+    /// forward along.
+    EndsBehindStarts,
+    /// Some ends lie ahead of a start along the axis, but all of them are further away
+    /// across the axis than the reach allows in that many steps, or cannot be landed on
+    /// with the advances the axis step range allows.
+    OutOfReach,
+    /// Routes exist, but none of them fits the maximum cost.
+    OverBudget,
+    /// The progress reporter aborted the search, the deadline passed, or the node limit was
+    /// exceeded.
+    Aborted,
+}
+
+impl std::fmt::Display for RouteFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RouteFailure::NoEndpoints => "there are no start or no end positions to route between",
+            RouteFailure::EndsBehindStarts => {
+                "start not reachable along axis: every end lies before every start"
+            }
+            RouteFailure::OutOfReach => {
+                "goal outside reachable set: every end is further from the starts than the reach and axis step allow"
+            }
+            RouteFailure::OverBudget => "no route fits within the maximum cost",
+            RouteFailure::Aborted => "the search was aborted",
+        })
+    }
+}
+
+impl std::error::Error for RouteFailure {}
+
+/// Whether a route from `start` can reach `end` at all: each step advances along the axis by
+/// an amount within `axis_step`, shifting by at most the reach in the other dimensions, and
+/// time advances by one frame with every step when routing along x or y.
+fn can_reach(start: Pos3D, end: Pos3D, axis: usize, reach: Reach, axis_step: AxisStep) -> bool {
+    let Reach(reach_dim0, reach_dim1) = reach;
+    let Some(distance) = axis_coordinate(end, axis).checked_sub(axis_coordinate(start, axis))
+    else {
+        return false;
+    };
+    // Whether `steps` steps can cover the distance along the axis
+    let covers = |steps: u32| {
+        let steps = steps as u64;
+        steps * axis_step.min as u64 <= distance as u64
+            && distance as u64 <= steps * axis_step.max as u64
+    };
+    let within = |from: u32, to: u32, reach: usize, steps: u32| {
+        from.abs_diff(to) as u64 <= steps as u64 * reach as u64
+    };
+
+    let ((x, y, t), (ex, ey, et)) = (start, end);
+    match axis {
+        0 => et
+            .checked_sub(t)
+            .is_some_and(|steps| covers(steps) && within(y, ey, reach_dim0, steps)),
+        1 => et
+            .checked_sub(t)
+            .is_some_and(|steps| covers(steps) && within(x, ex, reach_dim0, steps)),
+        _ => {
+            // The most steps that fit leave the most room to move across the axis
+            let steps = distance / axis_step.min;
+            covers(steps) && within(x, ex, reach_dim0, steps) && within(y, ey, reach_dim1, steps)
+        }
+    }
+}
+
+/// Explain why no route was found between `starts` and `ends` by a search that was not aborted.
+fn diagnose_failure<R: RouteSpace>(space: &R, starts: &[R::Pos], ends: &[R::Pos]) -> RouteFailure {
+    let pairs = || {
+        starts
+            .iter()
+            .flat_map(|&start| ends.iter().map(move |&end| (start, end)))
+    };
+
+    if pairs().any(|(start, end)| space.can_reach(start, end)) {
+        // Routes never hit a wall, so a reachable end is only missed for its cost
+        RouteFailure::OverBudget
+    } else if pairs().any(|(start, end)| space.axis_coordinate(end) > space.axis_coordinate(start))
+    {
+        RouteFailure::OutOfReach
+    } else {
+        RouteFailure::EndsBehindStarts
+    }
+}
+
+/// The search graph of a route through a volume, shared by the 3D and 4D routers.
+trait RouteSpace {
+    /// A position in the volume.
+    type Pos: Copy + Eq + Hash;
+
+    /// The coordinate of `pos` along the routing axis.
+    fn axis_coordinate(&self, pos: Self::Pos) -> u32;
+
+    /// The positions one step after `pos`, with the cost of moving to each.
+    fn neighbours(&self, pos: Self::Pos) -> Vec<(Self::Pos, u32)>;
+
+    /// Every position on the first (`is_start`) or last slice along the routing axis.
+    fn default_endpoints(&self, is_start: bool) -> Vec<Self::Pos>;
+
+    /// The distance across the axis from `pos` to the closest end ahead of it, used as heuristic.
+    fn distance_to_ends(&self, pos: Self::Pos, ends: &[Self::Pos]) -> u32;
+
+    /// Whether a route from `start` can reach `end` at all, ignoring costs.
+    fn can_reach(&self, start: Self::Pos, end: Self::Pos) -> bool;
+}
+
+/// A temporal volume (x, y, t) routed along one of its axes.
+struct RouteSpace3D<'v, T> {
+    volume: ArrayView3<'v, T>,
+    axis: usize,
+    reach: Reach,
+    axis_step: AxisStep,
+    scale_diagonal: bool,
+}
+
+impl<'v, T> RouteSpace3D<'v, T> {
+    /// The search graph of `volume`, with the default reach and axis filled in.
+    fn new(
+        volume: ArrayView3<'v, T>,
+        reach: Option<Reach>,
+        axis: Option<usize>,
+        options: &SearchOptionsTemporal,
+    ) -> Self {
+        RouteSpace3D {
+            volume,
+            axis: axis.unwrap_or(2), // Default to time axis
+            reach: reach.unwrap_or_default(),
+            axis_step: options.axis_step,
+            scale_diagonal: options.scale_diagonal,
+        }
+    }
+}
+
+impl<T: HeatmapValue> RouteSpace for RouteSpace3D<'_, T> {
+    type Pos = Pos3D;
+
+    fn axis_coordinate(&self, pos: Pos3D) -> u32 {
+        axis_coordinate(pos, self.axis)
+    }
+
+    fn neighbours(&self, pos: Pos3D) -> Vec<Pos3DWithCost> {
+        find_neighbours_with_reach(
+            self.volume,
+            pos,
+            self.axis,
+            self.reach,
+            self.axis_step,
+            self.scale_diagonal,
+        )
+    }
+
+    fn default_endpoints(&self, is_start: bool) -> Vec<Pos3D> {
+        generate_default_starts_ends(self.volume, self.axis, is_start)
+    }
+
+    fn distance_to_ends(&self, pos: Pos3D, ends: &[Pos3D]) -> u32 {
+        min_distance_to_ends(pos, ends, self.axis)
+    }
+
+    fn can_reach(&self, start: Pos3D, end: Pos3D) -> bool {
+        can_reach(start, end, self.axis, self.reach, self.axis_step)
+    }
+}
+
+/// A 4D volume routed along one of its axes. Unlike the temporal volume, whose time axis
+/// advances with every step, a 4D route only advances along its routing axis, and moves within
+/// the reach along the three others.
+struct RouteSpace4D<'v, T> {
+    volume: ArrayView4<'v, T>,
+    axis: usize,
+    reach: [usize; 3],
+    axis_step: AxisStep,
+    scale_diagonal: bool,
+}
+
+impl<'v, T> RouteSpace4D<'v, T> {
+    /// The search graph of `volume`, with the default reach and axis filled in.
+    fn new(
+        volume: ArrayView4<'v, T>,
+        reach: Option<Reach4D>,
+        axis: Option<usize>,
+        options: &SearchOptionsTemporal,
+    ) -> Self {
+        let axis = axis.unwrap_or(3); // Default to the last axis, like time in 3D
+        assert!(
+            axis < 4,
+            "the axis of a 4D volume must be 0 to 3, got {}",
+            axis
+        );
+        let Reach4D(reach_dim0, reach_dim1, reach_dim2) = reach.unwrap_or_default();
+
+        RouteSpace4D {
+            volume,
+            axis,
+            reach: [reach_dim0, reach_dim1, reach_dim2],
+            axis_step: options.axis_step,
+            scale_diagonal: options.scale_diagonal,
+        }
+    }
+
+    /// The three non-axis dimensions, in axis order.
+    fn across_axis(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..4).filter(move |&dim| dim != self.axis)
+    }
+}
+
+impl<T: HeatmapValue> RouteSpace for RouteSpace4D<'_, T> {
+    type Pos = Pos4D;
+
+    fn axis_coordinate(&self, pos: Pos4D) -> u32 {
+        <[u32; 4]>::from(pos)[self.axis]
+    }
+
+    fn neighbours(&self, pos: Pos4D) -> Vec<Pos4DWithCost> {
+        let shape = self.volume.shape();
+        let pos = <[u32; 4]>::from(pos);
+        let mut neighbours = Vec::new();
+
+        // Every combination of offsets within the reach across the axis
+        let mut offsets = vec![[0i64; 3]];
+        for (i, reach) in self.reach.into_iter().enumerate() {
+            let reach = reach as i64;
+            offsets = offsets
+                .into_iter()
+                .flat_map(|offset| {
+                    (-reach..=reach).map(move |delta| {
+                        let mut offset = offset;
+                        offset[i] = delta;
+                        offset
+                    })
+                })
+                .collect();
+        }
+
+        for advance in self.axis_step.min..=self.axis_step.max {
+            let mut next = pos;
+            next[self.axis] += advance;
+            if next[self.axis] as usize >= shape[self.axis] {
+                break;
+            }
+
+            'offsets: for offset in &offsets {
+                for (dim, delta) in self.across_axis().zip(offset) {
+                    let coordinate = pos[dim] as i64 + delta;
+                    if coordinate < 0 || coordinate >= shape[dim] as i64 {
+                        continue 'offsets;
+                    }
+                    next[dim] = coordinate as u32;
+                }
+
+                // Cost is the value at the *destination* node
+                let [a, b, c, d] = next.map(|coordinate| coordinate as usize);
+                let mut cost = self.volume[[a, b, c, d]].into();
+
+                // A step moving along n >= 2 dimensions across the axis is √n times as long
+                let moved = offset.iter().filter(|&&delta| delta != 0).count();
+                if self.scale_diagonal && moved >= 2 {
+                    cost = (cost as f64 * (moved as f64).sqrt()).round() as u32;
+                }
+                neighbours.push((next.into(), cost));
+            }
+        }
+
+        neighbours
+    }
+
+    fn default_endpoints(&self, is_start: bool) -> Vec<Pos4D> {
+        let Some(last) = self.volume.shape()[self.axis].checked_sub(1) else {
+            return Vec::new();
+        };
+        let index = if is_start { 0 } else { last };
+
+        self.volume
+            .indexed_iter()
+            .map(|((a, b, c, d), _)| (a as u32, b as u32, c as u32, d as u32))
+            .filter(|&pos| self.axis_coordinate(pos) as usize == index)
+            .collect()
+    }
+
+    fn distance_to_ends(&self, pos: Pos4D, ends: &[Pos4D]) -> u32 {
+        let coordinates = <[u32; 4]>::from(pos);
+
+        ends.iter()
+            .filter(|&&end| self.axis_coordinate(pos) <= self.axis_coordinate(end))
+            .map(|&end| {
+                let end = <[u32; 4]>::from(end);
+                self.across_axis()
+                    .map(|dim| coordinates[dim].abs_diff(end[dim]))
+                    .sum()
+            })
+            .min()
+            .unwrap_or(u32::MAX)
+    }
+
+    fn can_reach(&self, start: Pos4D, end: Pos4D) -> bool {
+        let Some(distance) = self
+            .axis_coordinate(end)
+            .checked_sub(self.axis_coordinate(start))
+        else {
+            return false;
+        };
+
+        // The most steps that fit leave the most room to move across the axis
+        let steps = (distance / self.axis_step.min) as u64;
+        let (start, end) = (<[u32; 4]>::from(start), <[u32; 4]>::from(end));
+        steps * self.axis_step.max as u64 >= distance as u64
+            && self
+                .across_axis()
+                .zip(self.reach)
+                .all(|(dim, reach)| start[dim].abs_diff(end[dim]) as u64 <= steps * reach as u64)
+    }
+}
+
+/// Successor function handed to the search algorithms.
+type Successors<'s, P> = dyn FnMut(&P) -> Vec<(P, u32)> + 's;
+
+/// Run `search` from each start position and keep the cheapest route to any end.
+///
+/// `search` is given the start, the successor function, a heuristic towards the ends, and the
+/// success predicate. When the reporter aborts or the deadline passes, the successors dry up
+/// and the predicate accepts the next node, so the search returns right away and its result
+/// is discarded.
+fn route_from_each_start<R: RouteSpace, S>(
+    space: &R,
+    starts: Option<Vec<R::Pos>>,
+    ends: Option<Vec<R::Pos>>,
+    options: SearchOptionsTemporal,
+    search: S,
+) -> Result<(Vec<R::Pos>, u32), RouteFailure>
+where
+    S: Fn(
+        &R::Pos,
+        &mut Successors<R::Pos>,
+        &dyn Fn(&R::Pos) -> u32,
+        &mut dyn FnMut(&R::Pos) -> bool,
+    ) -> Option<(Vec<R::Pos>, u32)>,
+{
+    let axis_step = options.axis_step;
+    assert!(
+        1 <= axis_step.min && axis_step.min <= axis_step.max,
+        "the axis step range must satisfy 1 <= min <= max, got {:?}",
+        axis_step
+    );
+
+    let starts = starts.unwrap_or_else(|| space.default_endpoints(true));
+    let ends = ends.unwrap_or_else(|| space.default_endpoints(false));
+
+    if starts.is_empty() || ends.is_empty() {
+        return Err(RouteFailure::NoEndpoints);
+    }
+
+    // Collect all end positions into a set for fast lookup
+    let ends_set: HashSet<R::Pos> = ends.iter().cloned().collect();
+
+    // Routes only move forward along the axis, so cells past the last end can never reach one
+    let last_end = ends
+        .iter()
+        .map(|&end| space.axis_coordinate(end))
+        .max()
+        .unwrap_or(0);
+
+    let tracker = RefCell::new(ProgressTracker {
+        reporter: options.progress,
+        deadline: options.deadline,
+        node_limit: options.node_limit,
+        progress: TemporalProgress::default(),
+        aborted: false,
+    });
+
+    // Run the search from each start position and find the minimum cost path to any end
+    let mut best_path: Option<(Vec<R::Pos>, u32)> = None;
+    let mut best_cost = u32::MAX;
+
+    for &start in &starts {
+        let mut budget = options
+            .max_cost
+            .map(|max_cost| CostBudget::new(max_cost, start));
+        let result = search(
+            &start,
+            &mut |&p| {
+                let mut tracker = tracker.borrow_mut();
+                tracker.record_expansion();
+                if tracker.aborted {
+                    return Vec::new();
+                }
+                let mut neighbours = space.neighbours(p);
+                neighbours.retain(|&(next, _)| space.axis_coordinate(next) <= last_end);
+                if let Some(budget) = &mut budget {
+                    budget.prune(p, &mut neighbours);
+                }
+                neighbours
+            },
+            &|&p| space.distance_to_ends(p, &ends),
+            &mut |p| tracker.borrow().aborted || ends_set.contains(p),
+        );
+
+        if tracker.borrow().aborted {
+            return Err(RouteFailure::Aborted);
+        }
+
+        if let Some((path, cost)) = result
+            && cost < best_cost
+            && options.max_cost.is_none_or(|max_cost| cost <= max_cost)
+        {
+            best_cost = cost;
+            best_path = Some((path, cost));
+            tracker.borrow_mut().progress.best_cost = Some(cost);
+        }
+    }
+
+    best_path.ok_or_else(|| diagnose_failure(space, &starts, &ends))
+}
+
 // MARK: Temporal Routers
 
 // MARK: Dijkstra
@@ -204,7 +946,8 @@ impl DijkstraTemporal {
     /// # Arguments
     ///
     /// * `volume` - The temporal volume (Width, Height, Time) i.e. (x, y, t)
-    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1)
+    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1).
+    ///   See [`Reach`] for a different reach per dimension
     /// * `axis` - The axis along which the path must always move forward (default: 2 for time)
     /// * `starts` - Optional start positions. If None, uses all positions at axis=0
     /// * `ends` - Optional end positions. If None, uses all positions at axis=-1
@@ -212,47 +955,143 @@ impl DijkstraTemporal {
     /// # Returns
     ///
     /// * `Option<(Vec<Pos3D>, u32)>` - The route found and the total cost, or None if no route was found
-    pub fn find_route_over_time(
+    pub fn find_route_over_time<T: HeatmapValue>(
         &self,
-        volume: ArrayView3<u8>,
-        reach: Option<usize>,
+        volume: ArrayView3<T>,
+        reach: Option<Reach>,
         axis: Option<usize>,
         starts: Option<Vec<Pos3D>>,
         ends: Option<Vec<Pos3D>>,
     ) -> Option<(Vec<Pos3D>, u32)> {
-        let reach = reach.unwrap_or(1);
-        let axis = axis.unwrap_or(2); // Default to time axis
-
-        let starts = starts.unwrap_or_else(|| generate_default_starts_ends(volume, axis, true));
-        let ends = ends.unwrap_or_else(|| generate_default_starts_ends(volume, axis, false));
-
-        if starts.is_empty() || ends.is_empty() {
-            return None;
-        }
-
-        // Collect all end positions into a set for fast lookup
-        let ends_set: std::collections::HashSet<Pos3D> = ends.iter().cloned().collect();
+        self.find_route_over_time_with_options(
+            volume,
+            reach,
+            axis,
+            starts,
+            ends,
+            SearchOptionsTemporal::default(),
+        )
+    }
 
-        // Run Dijkstra from each start position and find the minimum cost path to any end
-        let mut best_path: Option<(Vec<Pos3D>, u32)> = None;
-        let mut best_cost = u32::MAX;
+    /// Find the shortest route through a temporal volume, with control over progress reporting,
+    /// timeouts and the cost budget. See [`ProgressReporter`] for how often progress is reported
+    /// and how to abort the search, and [`SearchDeadline`] for how to detect a timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - The temporal volume (Width, Height, Time) i.e. (x, y, t)
+    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1).
+    ///   See [`Reach`] for a different reach per dimension
+    /// * `axis` - The axis along which the path must always move forward (default: 2 for time)
+    /// * `starts` - Optional start positions. If None, uses all positions at axis=0
+    /// * `ends` - Optional end positions. If None, uses all positions at axis=-1
+    /// * `options` - The progress reporter, deadline and cost budget of the search
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos3D>, u32)>` - The route found and the total cost, or None if no route was
+    ///   found within the cost budget or the search was aborted
+    pub fn find_route_over_time_with_options<T: HeatmapValue>(
+        &self,
+        volume: ArrayView3<T>,
+        reach: Option<Reach>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos3D>>,
+        ends: Option<Vec<Pos3D>>,
+        options: SearchOptionsTemporal,
+    ) -> Option<(Vec<Pos3D>, u32)> {
+        self.try_find_route_over_time(volume, reach, axis, starts, ends, options)
+            .ok()
+    }
 
-        for &start in &starts {
-            let result = dijkstra(
-                &start,
-                |&p| find_neighbours_with_reach(volume, p, axis, reach),
-                |&p| ends_set.contains(&p),
-            );
+    /// Like [`Self::find_route_over_time_with_options`], but reports why no route was found.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Vec<Pos3D>, u32), RouteFailure>` - The route found and the total cost, or the
+    ///   reason no route was found
+    pub fn try_find_route_over_time<T: HeatmapValue>(
+        &self,
+        volume: ArrayView3<T>,
+        reach: Option<Reach>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos3D>>,
+        ends: Option<Vec<Pos3D>>,
+        options: SearchOptionsTemporal,
+    ) -> Result<(Vec<Pos3D>, u32), RouteFailure> {
+        // Dijkstra ignores the heuristic
+        route_from_each_start(
+            &RouteSpace3D::new(volume, reach, axis, &options),
+            starts,
+            ends,
+            options,
+            |start, successors, _, success| dijkstra(start, successors, success),
+        )
+    }
 
-            if let Some((path, cost)) = result {
-                if cost < best_cost {
-                    best_cost = cost;
-                    best_path = Some((path, cost));
-                }
-            }
-        }
+    /// Find the shortest route through a 4D volume, e.g. (x, y, z, t), advancing along one axis
+    /// while moving freely within the reach along the three others. Every axis is treated
+    /// alike: unlike [`Self::find_route_over_time`], no axis advances implicitly.
+    ///
+    /// The search graph grows with the fourth dimension: each node has up to
+    /// `(2 * reach + 1)^3` neighbours per axis advance, and a search may visit every cell ahead
+    /// of its start. With the default endpoints, a search runs from every cell of the first
+    /// slice, so prefer passing explicit `starts` on large volumes.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - The 4D volume, indexed in the same order as the positions
+    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1).
+    ///   See [`Reach4D`] for a different reach per dimension
+    /// * `axis` - The axis along which the path must always move forward (default: 3)
+    /// * `starts` - Optional start positions. If None, uses all positions at axis=0
+    /// * `ends` - Optional end positions. If None, uses all positions at axis=-1
+    /// * `options` - The progress reporter, deadline and cost budget of the search
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos4D>, u32)>` - The route found and the total cost, or None if no route was
+    ///   found within the cost budget or the search was aborted
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not 0 to 3.
+    pub fn find_route_4d<T: HeatmapValue>(
+        &self,
+        volume: ArrayView4<T>,
+        reach: Option<Reach4D>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos4D>>,
+        ends: Option<Vec<Pos4D>>,
+        options: SearchOptionsTemporal,
+    ) -> Option<(Vec<Pos4D>, u32)> {
+        self.try_find_route_4d(volume, reach, axis, starts, ends, options)
+            .ok()
+    }
 
-        best_path
+    /// Like [`Self::find_route_4d`], but reports why no route was found.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Vec<Pos4D>, u32), RouteFailure>` - The route found and the total cost, or the
+    ///   reason no route was found
+    pub fn try_find_route_4d<T: HeatmapValue>(
+        &self,
+        volume: ArrayView4<T>,
+        reach: Option<Reach4D>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos4D>>,
+        ends: Option<Vec<Pos4D>>,
+        options: SearchOptionsTemporal,
+    ) -> Result<(Vec<Pos4D>, u32), RouteFailure> {
+        // Dijkstra ignores the heuristic
+        route_from_each_start(
+            &RouteSpace4D::new(volume, reach, axis, &options),
+            starts,
+            ends,
+            options,
+            |start, successors, _, success| dijkstra(start, successors, success),
+        )
     }
 }
 
@@ -261,47 +1100,170 @@ impl DijkstraTemporal {
 pub struct AStarTemporal {}
 
 impl AStarTemporal {
-    /// Minimum distance to any end position (for multi-end heuristic)
-    fn min_distance_to_ends(&self, pos: Pos3D, ends: &[Pos3D], axis: usize) -> u32 {
-        let (x, y, t) = pos;
-        let mut min_dist = u32::MAX;
-
-        for &(ex, ey, et) in ends {
-            match axis {
-                0 => {
-                    // Moving along x axis
-                    let spatial_dist = (y.abs_diff(ey) + t.abs_diff(et)) as u32;
-                    if x <= ex {
-                        min_dist = min_dist.min(spatial_dist);
-                    }
-                }
-                1 => {
-                    // Moving along y axis
-                    let spatial_dist = (x.abs_diff(ex) + t.abs_diff(et)) as u32;
-                    if y <= ey {
-                        min_dist = min_dist.min(spatial_dist);
-                    }
-                }
-                2 => {
-                    // Moving along t axis (time)
-                    let spatial_dist = (x.abs_diff(ex) + y.abs_diff(ey)) as u32;
-                    if t <= et {
-                        min_dist = min_dist.min(spatial_dist);
-                    }
-                }
-                _ => {}
-            }
-        }
+    /// Find the shortest route through a temporal volume from one side to another.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - The temporal volume (Width, Height, Time) i.e. (x, y, t)
+    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1).
+    ///   See [`Reach`] for a different reach per dimension
+    /// * `axis` - The axis along which the path must always move forward (default: 2 for time)
+    /// * `starts` - Optional start positions. If None, uses all positions at axis=0
+    /// * `ends` - Optional end positions. If None, uses all positions at axis=-1
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos3D>, u32)>` - The route found and the total cost, or None if no route was found
+    pub fn find_route_over_time<T: HeatmapValue>(
+        &self,
+        volume: ArrayView3<T>,
+        reach: Option<Reach>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos3D>>,
+        ends: Option<Vec<Pos3D>>,
+    ) -> Option<(Vec<Pos3D>, u32)> {
+        self.find_route_over_time_with_options(
+            volume,
+            reach,
+            axis,
+            starts,
+            ends,
+            SearchOptionsTemporal::default(),
+        )
+    }
+
+    /// Find the shortest route through a temporal volume, with control over progress reporting,
+    /// timeouts and the cost budget. See [`ProgressReporter`] for how often progress is reported
+    /// and how to abort the search, and [`SearchDeadline`] for how to detect a timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - The temporal volume (Width, Height, Time) i.e. (x, y, t)
+    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1).
+    ///   See [`Reach`] for a different reach per dimension
+    /// * `axis` - The axis along which the path must always move forward (default: 2 for time)
+    /// * `starts` - Optional start positions. If None, uses all positions at axis=0
+    /// * `ends` - Optional end positions. If None, uses all positions at axis=-1
+    /// * `options` - The progress reporter, deadline and cost budget of the search
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos3D>, u32)>` - The route found and the total cost, or None if no route was
+    ///   found within the cost budget or the search was aborted
+    pub fn find_route_over_time_with_options<T: HeatmapValue>(
+        &self,
+        volume: ArrayView3<T>,
+        reach: Option<Reach>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos3D>>,
+        ends: Option<Vec<Pos3D>>,
+        options: SearchOptionsTemporal,
+    ) -> Option<(Vec<Pos3D>, u32)> {
+        self.try_find_route_over_time(volume, reach, axis, starts, ends, options)
+            .ok()
+    }
+
+    /// Like [`Self::find_route_over_time_with_options`], but reports why no route was found.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Vec<Pos3D>, u32), RouteFailure>` - The route found and the total cost, or the
+    ///   reason no route was found
+    pub fn try_find_route_over_time<T: HeatmapValue>(
+        &self,
+        volume: ArrayView3<T>,
+        reach: Option<Reach>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos3D>>,
+        ends: Option<Vec<Pos3D>>,
+        options: SearchOptionsTemporal,
+    ) -> Result<(Vec<Pos3D>, u32), RouteFailure> {
+        route_from_each_start(
+            &RouteSpace3D::new(volume, reach, axis, &options),
+            starts,
+            ends,
+            options,
+            |start, successors, heuristic, success| astar(start, successors, heuristic, success),
+        )
+    }
+
+    /// Find the shortest route through a 4D volume, e.g. (x, y, z, t), advancing along one axis
+    /// while moving freely within the reach along the three others. Every axis is treated
+    /// alike: unlike [`Self::find_route_over_time`], no axis advances implicitly.
+    ///
+    /// The search graph grows with the fourth dimension: each node has up to
+    /// `(2 * reach + 1)^3` neighbours per axis advance, and a search may visit every cell ahead
+    /// of its start. With the default endpoints, a search runs from every cell of the first
+    /// slice, so prefer passing explicit `starts` on large volumes.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - The 4D volume, indexed in the same order as the positions
+    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1).
+    ///   See [`Reach4D`] for a different reach per dimension
+    /// * `axis` - The axis along which the path must always move forward (default: 3)
+    /// * `starts` - Optional start positions. If None, uses all positions at axis=0
+    /// * `ends` - Optional end positions. If None, uses all positions at axis=-1
+    /// * `options` - The progress reporter, deadline and cost budget of the search
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos4D>, u32)>` - The route found and the total cost, or None if no route was
+    ///   found within the cost budget or the search was aborted
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not 0 to 3.
+    pub fn find_route_4d<T: HeatmapValue>(
+        &self,
+        volume: ArrayView4<T>,
+        reach: Option<Reach4D>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos4D>>,
+        ends: Option<Vec<Pos4D>>,
+        options: SearchOptionsTemporal,
+    ) -> Option<(Vec<Pos4D>, u32)> {
+        self.try_find_route_4d(volume, reach, axis, starts, ends, options)
+            .ok()
+    }
 
-        min_dist
+    /// Like [`Self::find_route_4d`], but reports why no route was found.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Vec<Pos4D>, u32), RouteFailure>` - The route found and the total cost, or the
+    ///   reason no route was found
+    pub fn try_find_route_4d<T: HeatmapValue>(
+        &self,
+        volume: ArrayView4<T>,
+        reach: Option<Reach4D>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos4D>>,
+        ends: Option<Vec<Pos4D>>,
+        options: SearchOptionsTemporal,
+    ) -> Result<(Vec<Pos4D>, u32), RouteFailure> {
+        route_from_each_start(
+            &RouteSpace4D::new(volume, reach, axis, &options),
+            starts,
+            ends,
+            options,
+            |start, successors, heuristic, success| astar(start, successors, heuristic, success),
+        )
     }
+}
+
+// MARK: Fringe
 
+pub struct FringeTemporal {}
+
+impl FringeTemporal {
     /// Find the shortest route through a temporal volume from one side to another.
     ///
     /// # Arguments
     ///
     /// * `volume` - The temporal volume (Width, Height, Time) i.e. (x, y, t)
-    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1)
+    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1).
+    ///   See [`Reach`] for a different reach per dimension
     /// * `axis` - The axis along which the path must always move forward (default: 2 for time)
     /// * `starts` - Optional start positions. If None, uses all positions at axis=0
     /// * `ends` - Optional end positions. If None, uses all positions at axis=-1
@@ -309,49 +1271,140 @@ impl AStarTemporal {
     /// # Returns
     ///
     /// * `Option<(Vec<Pos3D>, u32)>` - The route found and the total cost, or None if no route was found
-    pub fn find_route_over_time(
+    pub fn find_route_over_time<T: HeatmapValue>(
         &self,
-        volume: ArrayView3<u8>,
-        reach: Option<usize>,
+        volume: ArrayView3<T>,
+        reach: Option<Reach>,
         axis: Option<usize>,
         starts: Option<Vec<Pos3D>>,
         ends: Option<Vec<Pos3D>>,
     ) -> Option<(Vec<Pos3D>, u32)> {
-        let reach = reach.unwrap_or(1);
-        let axis = axis.unwrap_or(2); // Default to time axis
-
-        let starts = starts.unwrap_or_else(|| generate_default_starts_ends(volume, axis, true));
-        let ends = ends.unwrap_or_else(|| generate_default_starts_ends(volume, axis, false));
+        self.find_route_over_time_with_options(
+            volume,
+            reach,
+            axis,
+            starts,
+            ends,
+            SearchOptionsTemporal::default(),
+        )
+    }
 
-        if starts.is_empty() || ends.is_empty() {
-            return None;
-        }
+    /// Find the shortest route through a temporal volume, with control over progress reporting,
+    /// timeouts and the cost budget. See [`ProgressReporter`] for how often progress is reported
+    /// and how to abort the search, and [`SearchDeadline`] for how to detect a timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - The temporal volume (Width, Height, Time) i.e. (x, y, t)
+    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1).
+    ///   See [`Reach`] for a different reach per dimension
+    /// * `axis` - The axis along which the path must always move forward (default: 2 for time)
+    /// * `starts` - Optional start positions. If None, uses all positions at axis=0
+    /// * `ends` - Optional end positions. If None, uses all positions at axis=-1
+    /// * `options` - The progress reporter, deadline and cost budget of the search
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos3D>, u32)>` - The route found and the total cost, or None if no route was
+    ///   found within the cost budget or the search was aborted
+    pub fn find_route_over_time_with_options<T: HeatmapValue>(
+        &self,
+        volume: ArrayView3<T>,
+        reach: Option<Reach>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos3D>>,
+        ends: Option<Vec<Pos3D>>,
+        options: SearchOptionsTemporal,
+    ) -> Option<(Vec<Pos3D>, u32)> {
+        self.try_find_route_over_time(volume, reach, axis, starts, ends, options)
+            .ok()
+    }
 
-        // Collect all end positions into a set for fast lookup
-        let ends_set: std::collections::HashSet<Pos3D> = ends.iter().cloned().collect();
-        let ends_vec = ends;
-
-        // Run A* from each start position and find the minimum cost path to any end
-        let mut best_path: Option<(Vec<Pos3D>, u32)> = None;
-        let mut best_cost = u32::MAX;
-
-        for &start in &starts {
-            let ends_vec_clone = ends_vec.clone();
-            let result = astar(
-                &start,
-                |&p| find_neighbours_with_reach(volume, p, axis, reach),
-                |&p| self.min_distance_to_ends(p, &ends_vec_clone, axis),
-                |&p| ends_set.contains(&p),
-            );
+    /// Like [`Self::find_route_over_time_with_options`], but reports why no route was found.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Vec<Pos3D>, u32), RouteFailure>` - The route found and the total cost, or the
+    ///   reason no route was found
+    pub fn try_find_route_over_time<T: HeatmapValue>(
+        &self,
+        volume: ArrayView3<T>,
+        reach: Option<Reach>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos3D>>,
+        ends: Option<Vec<Pos3D>>,
+        options: SearchOptionsTemporal,
+    ) -> Result<(Vec<Pos3D>, u32), RouteFailure> {
+        route_from_each_start(
+            &RouteSpace3D::new(volume, reach, axis, &options),
+            starts,
+            ends,
+            options,
+            |start, successors, heuristic, success| fringe(start, successors, heuristic, success),
+        )
+    }
 
-            if let Some((path, cost)) = result {
-                if cost < best_cost {
-                    best_cost = cost;
-                    best_path = Some((path, cost));
-                }
-            }
-        }
+    /// Find the shortest route through a 4D volume, e.g. (x, y, z, t), advancing along one axis
+    /// while moving freely within the reach along the three others. Every axis is treated
+    /// alike: unlike [`Self::find_route_over_time`], no axis advances implicitly.
+    ///
+    /// The search graph grows with the fourth dimension: each node has up to
+    /// `(2 * reach + 1)^3` neighbours per axis advance, and a search may visit every cell ahead
+    /// of its start. With the default endpoints, a search runs from every cell of the first
+    /// slice, so prefer passing explicit `starts` on large volumes.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - The 4D volume, indexed in the same order as the positions
+    /// * `reach` - Number of elements that can be skipped along each non-axis dimension (default: 1).
+    ///   See [`Reach4D`] for a different reach per dimension
+    /// * `axis` - The axis along which the path must always move forward (default: 3)
+    /// * `starts` - Optional start positions. If None, uses all positions at axis=0
+    /// * `ends` - Optional end positions. If None, uses all positions at axis=-1
+    /// * `options` - The progress reporter, deadline and cost budget of the search
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Vec<Pos4D>, u32)>` - The route found and the total cost, or None if no route was
+    ///   found within the cost budget or the search was aborted
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not 0 to 3.
+    pub fn find_route_4d<T: HeatmapValue>(
+        &self,
+        volume: ArrayView4<T>,
+        reach: Option<Reach4D>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos4D>>,
+        ends: Option<Vec<Pos4D>>,
+        options: SearchOptionsTemporal,
+    ) -> Option<(Vec<Pos4D>, u32)> {
+        self.try_find_route_4d(volume, reach, axis, starts, ends, options)
+            .ok()
+    }
 
-        best_path
+    /// Like [`Self::find_route_4d`], but reports why no route was found.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Vec<Pos4D>, u32), RouteFailure>` - The route found and the total cost, or the
+    ///   reason no route was found
+    pub fn try_find_route_4d<T: HeatmapValue>(
+        &self,
+        volume: ArrayView4<T>,
+        reach: Option<Reach4D>,
+        axis: Option<usize>,
+        starts: Option<Vec<Pos4D>>,
+        ends: Option<Vec<Pos4D>>,
+        options: SearchOptionsTemporal,
+    ) -> Result<(Vec<Pos4D>, u32), RouteFailure> {
+        route_from_each_start(
+            &RouteSpace4D::new(volume, reach, axis, &options),
+            starts,
+            ends,
+            options,
+            |start, successors, heuristic, success| fringe(start, successors, heuristic, success),
+        )
     }
 }
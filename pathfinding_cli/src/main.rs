@@ -1,58 +1,484 @@
 use anyhow::{Context, Result};
 use clap::{Parser, ValueEnum};
-use image::{Rgb, RgbImage};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, Rgb, RgbImage, buffer::ConvertBuffer};
 use image_pathfinding::{
-    AStar2D, AStarTemporal, Dijkstra2D, DijkstraTemporal, Fringe2D, ImagePathfinder2D,
-    load_images_to_volume, load_png_to_ndarray,
+    AStar2D, AStarTemporal, Dijkstra2D, DijkstraTemporal, Fringe2D, FringeTemporal, HeatmapValue,
+    ImagePathfinder2D, PathStyle, Pos2D, Pos3D, ProgressReporter, SearchObserver,
+    SearchOptionsTemporal, TemporalProgress, draw_path, is_16_bit, load_image_to_ndarray,
+    load_images_to_volume, load_images_to_volume_u16, load_png_weighted, open_image,
 };
+use indicatif::{ProgressBar, ProgressStyle};
+use numpy::ndarray::{Array2, Array3, ArrayView3, Axis, s};
+use rayon::prelude::*;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-
-/// Draw a filled circle on the image at the given position
-fn draw_circle(img: &mut RgbImage, center_x: u32, center_y: u32, radius: u32, color: Rgb<u8>) {
-    let width = img.width();
-    let height = img.height();
-
-    for dy in -(radius as i32)..=(radius as i32) {
-        for dx in -(radius as i32)..=(radius as i32) {
-            let distance_squared = (dx * dx + dy * dy) as f32;
-            if distance_squared <= (radius * radius) as f32 {
-                let x = center_x as i32 + dx;
-                let y = center_y as i32 + dy;
-
-                if x >= 0 && x < width as i32 && y >= 0 && y < height as i32 {
-                    img.put_pixel(x as u32, y as u32, color);
-                }
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of expanded nodes between two updates of the search progress bar
+const PROGRESS_INTERVAL: usize = 10_000;
+
+/// Exit code when the search completes but finds no path or route. Errors such as unreadable
+/// inputs exit with 1 instead.
+const NO_PATH_EXIT_CODE: u8 = 2;
+
+/// Records the cells a search expands, in order, for the `--animate` GIF
+#[derive(Default)]
+struct ExpansionRecorder(Mutex<Vec<Pos2D>>);
+
+impl SearchObserver for ExpansionRecorder {
+    fn on_pop(&self, pos: Pos2D) {
+        self.0.lock().unwrap().push(pos);
+    }
+}
+
+/// Write an animated GIF of a 2D search: the expanded cells spreading over the image in frames
+/// of `every` expansions each, followed by a held frame with the final path (if one was found).
+fn write_search_gif(
+    base: &RgbImage,
+    expanded: &[Pos2D],
+    path: Option<&[Pos2D]>,
+    every: usize,
+    style: PathStyle,
+    thickness: u32,
+    out_path: &Path,
+) -> Result<()> {
+    let file = fs::File::create(out_path).context("Failed to create GIF file")?;
+    let mut encoder = GifEncoder::new_with_speed(file, 10);
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .context("Failed to configure GIF encoder")?;
+
+    let frame_delay = Delay::from_numer_denom_ms(50, 1);
+    let mut canvas = base.clone();
+    let frontier = Rgb([0, 120, 255]);
+
+    for chunk in expanded.chunks(every.max(1)) {
+        for &(x, y) in chunk {
+            // Blend expanded cells towards blue, so the underlying heatmap stays visible
+            let pixel = canvas.get_pixel_mut(x, y);
+            for (channel, target) in pixel.0.iter_mut().zip(frontier.0) {
+                *channel = ((*channel as u16 + target as u16) / 2) as u8;
+            }
+        }
+
+        encoder
+            .encode_frame(Frame::from_parts(canvas.convert(), 0, 0, frame_delay))
+            .context("Failed to encode GIF frame")?;
+    }
+
+    if let Some(points) = path {
+        draw_path(&mut canvas, points, style, thickness, Rgb([255, 0, 0]));
+    }
+
+    // Hold the final frame so the result can be seen before the animation loops
+    let final_delay = Delay::from_numer_denom_ms(3000, 1);
+    encoder
+        .encode_frame(Frame::from_parts(canvas.convert(), 0, 0, final_delay))
+        .context("Failed to encode GIF frame")?;
+
+    Ok(())
+}
+
+/// The temporal positions at `axis_coord` along `axis`: the single position with the two other
+/// coordinates `across`, in x, y, t order, or every position of that slice if `across` is `None`.
+fn slice_points(
+    (width, height, depth): (usize, usize, usize),
+    axis: usize,
+    across: Option<&[u32]>,
+    axis_coord: u32,
+) -> Result<Vec<Pos3D>> {
+    let (len0, len1) = match axis {
+        0 => (height, depth),
+        1 => (width, depth),
+        _ => (width, height),
+    };
+
+    let pairs: Vec<(u32, u32)> = match across {
+        Some(&[a, b]) if (a as usize) < len0 && (b as usize) < len1 => vec![(a, b)],
+        Some(across) => anyhow::bail!(
+            "Position {:?} is out of bounds for a slice of shape {}x{} across axis {}",
+            across,
+            len0,
+            len1,
+            axis
+        ),
+        None => (0..len0 as u32)
+            .flat_map(|a| (0..len1 as u32).map(move |b| (a, b)))
+            .collect(),
+    };
+
+    Ok(pairs
+        .into_iter()
+        .map(|(a, b)| match axis {
+            0 => (axis_coord, a, b),
+            1 => (a, axis_coord, b),
+            _ => (a, b, axis_coord),
+        })
+        .collect())
+}
+
+/// Parse a routing axis of the volume, counting negative axes from the last one like NumPy
+fn parse_axis(value: &str) -> Result<usize, String> {
+    let axis: i64 = value.parse().map_err(|err| format!("{}", err))?;
+    let normalized = if axis < 0 { axis + 3 } else { axis };
+    usize::try_from(normalized)
+        .ok()
+        .filter(|&axis| axis < 3)
+        .ok_or_else(|| {
+            format!(
+                "axis must be 0, 1 or 2, or -3 to -1 counting from the last axis, got {}",
+                axis
+            )
+        })
+}
+
+/// Read a list of image paths, one per line, skipping blank lines and `#` comments.
+fn read_image_list(path: &Path) -> Result<Vec<String>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read image list {:?}", path))?;
+
+    let images: Vec<String> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect();
+
+    if images.is_empty() {
+        anyhow::bail!("Image list {:?} does not contain any image paths", path);
+    }
+
+    Ok(images)
+}
+
+/// A temporal volume, loaded with the bit depth of its frames
+enum Volume {
+    U8(Array3<u8>),
+    U16(Array3<u16>),
+}
+
+impl Volume {
+    /// Load the frames as 16-bit if the first one has more than 8 bits per channel, else as
+    /// 8-bit. Every frame must have the same bit depth as the first.
+    fn load(paths: &[String]) -> Result<Self> {
+        let first = open_image(&paths[0])
+            .with_context(|| format!("Failed to load image at {}", paths[0]))?;
+
+        Ok(if is_16_bit(&first) {
+            Volume::U16(load_images_to_volume_u16(paths))
+        } else {
+            Volume::U8(load_images_to_volume(paths))
+        })
+    }
+
+    fn dim(&self) -> (usize, usize, usize) {
+        match self {
+            Volume::U8(volume) => volume.dim(),
+            Volume::U16(volume) => volume.dim(),
+        }
+    }
+
+    fn bit_depth(&self) -> u32 {
+        match self {
+            Volume::U8(_) => 8,
+            Volume::U16(_) => 16,
+        }
+    }
+
+    /// Nearest-neighbour downsample the x and y axes of every frame by `factor`, keeping every
+    /// `factor`-th pixel. The frames themselves are all kept
+    fn downsample(&self, factor: u32) -> Self {
+        let step = factor as isize;
+        match self {
+            Volume::U8(volume) => Volume::U8(volume.slice(s![..;step, ..;step, ..]).to_owned()),
+            Volume::U16(volume) => Volume::U16(volume.slice(s![..;step, ..;step, ..]).to_owned()),
+        }
+    }
+
+    /// Collapse the volume along `axis` to its maximum-intensity projection, as a grayscale
+    /// image over the two remaining axes in x, y, t order. 16-bit values are scaled to 8 bits
+    fn max_projection(&self, axis: usize) -> RgbImage {
+        match self {
+            Volume::U8(volume) => max_projection(volume.view(), axis),
+            Volume::U16(volume) => max_projection(volume.view(), axis),
+        }
+    }
+}
+
+/// The maximum-intensity projection of `volume` along `axis`, see [`Volume::max_projection`]
+fn max_projection<T: HeatmapValue>(volume: ArrayView3<T>, axis: usize) -> RgbImage {
+    let projection = volume.fold_axis(Axis(axis), 0u32, |&max, &value| max.max(value.into()));
+    let max_value = (T::VALUE_COUNT - 1) as u32;
+    let (width, height) = projection.dim();
+
+    RgbImage::from_fn(width as u32, height as u32, |x, y| {
+        let level = (projection[[x as usize, y as usize]] * 255 / max_value) as u8;
+        Rgb([level, level, level])
+    })
+}
+
+/// Nearest-neighbour downsample an image by `factor`, keeping every `factor`-th pixel, so that
+/// pixel `(x, y)` of the result is pixel `(x * factor, y * factor)` of the original
+fn downsample_2d(array: Array2<u8>, factor: u32) -> Array2<u8> {
+    if factor == 1 {
+        return array;
+    }
+    let step = factor as isize;
+    array.slice(s![..;step, ..;step]).to_owned()
+}
+
+/// The cost reported for a search at 1/`factor` resolution: rescaled by `factor` with
+/// `--rescale-cost`, as each downsampled step spans `factor` pixels, or as found otherwise
+fn report_cost(cost: u32, cli: &Cli) -> u32 {
+    if cli.rescale_cost {
+        cost.saturating_mul(cli.downsample)
+    } else {
+        cost
+    }
+}
+
+/// Print how the costs relate to the input resolution, when the search ran downsampled
+fn print_downsample_note(cli: &Cli) {
+    if cli.downsample == 1 {
+        return;
+    }
+    if cli.rescale_cost {
+        println!(
+            "Searched at 1/{} resolution; costs are rescaled by {} to the input resolution",
+            cli.downsample, cli.downsample
+        );
+    } else {
+        println!(
+            "Searched at 1/{} resolution; costs are in downsampled space",
+            cli.downsample
+        );
+    }
+}
+
+/// Drop the coordinate along `axis` from each point of a temporal route, giving its points on
+/// the projection along that axis
+fn project_route(points: &[Pos3D], axis: usize) -> Vec<Pos2D> {
+    points
+        .iter()
+        .map(|&(x, y, t)| match axis {
+            0 => (y, t),
+            1 => (x, t),
+            _ => (x, y),
+        })
+        .collect()
+}
+
+/// Create a progress bar from `template`, or a hidden one with `--quiet`. The bars are drawn
+/// on stderr and only while it is a terminal, so they never mix with the output on stdout
+fn progress_bar(quiet: bool, len: Option<u64>, template: &str) -> ProgressBar {
+    if quiet {
+        return ProgressBar::hidden();
+    }
+
+    let bar = match len {
+        Some(len) => ProgressBar::new(len),
+        None => ProgressBar::new_spinner(),
+    };
+    bar.set_style(ProgressStyle::with_template(template).expect("the template is valid"));
+    bar.enable_steady_tick(Duration::from_millis(100));
+    bar
+}
+
+/// Route through `volume` with each of `algorithms`, as for [`compare_algorithms`].
+/// The number of nodes to expand is not known in advance, so the search progress bar counts
+/// them instead of filling up
+fn route_volume<T: HeatmapValue>(
+    volume: ArrayView3<T>,
+    algorithms: &[Algorithm],
+    cli: &Cli,
+    starts: &Option<Vec<Pos3D>>,
+    ends: &Option<Vec<Pos3D>>,
+) -> Option<(Vec<Pos3D>, u32)> {
+    compare_algorithms(algorithms, |_, algorithm| {
+        let bar = progress_bar(
+            cli.quiet,
+            None,
+            "{spinner} Searching with {prefix}: {human_pos} nodes expanded{msg} [{elapsed}]",
+        );
+        bar.set_prefix(format!("{:?}", algorithm).to_lowercase());
+        let mut report = |progress: TemporalProgress| {
+            bar.set_position(progress.nodes_expanded as u64);
+            if let Some(cost) = progress.best_cost {
+                bar.set_message(format!(", best cost so far {}", cost));
             }
+            ControlFlow::Continue(())
+        };
+
+        let options = SearchOptionsTemporal {
+            progress: (!cli.quiet).then_some(ProgressReporter {
+                interval: PROGRESS_INTERVAL,
+                callback: &mut report,
+            }),
+            ..Default::default()
+        };
+        let (reach, axis) = (Some(cli.reach.into()), Some(cli.axis));
+        let (starts, ends) = (starts.clone(), ends.clone());
+        let route = match algorithm {
+            Algorithm::Dijkstra => DijkstraTemporal {}
+                .find_route_over_time_with_options(volume, reach, axis, starts, ends, options),
+            Algorithm::Astar => AStarTemporal {}
+                .find_route_over_time_with_options(volume, reach, axis, starts, ends, options),
+            Algorithm::Fringe => FringeTemporal {}
+                .find_route_over_time_with_options(volume, reach, axis, starts, ends, options),
+            Algorithm::All => unreachable!("expanded by Algorithm::runs"),
+        };
+
+        bar.finish_and_clear();
+        route
+    })
+}
+
+/// Parse a route file written by a temporal run: one line per frame, holding the frame number
+/// followed by the x y coordinates of the frame's points. Blank lines and `#` comments are skipped.
+fn read_route_file(path: &Path) -> Result<HashMap<u32, Vec<Pos2D>>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read route file {:?}", path))?;
+
+    let mut points_by_time = HashMap::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let numbers = line
+            .split_whitespace()
+            .map(str::parse::<u32>)
+            .collect::<Result<Vec<u32>, _>>()
+            .with_context(|| format!("Invalid number on line {} of {:?}", index + 1, path))?;
+        let (&frame, coords) = numbers.split_first().expect("the line is not blank");
+        if coords.len() % 2 != 0 {
+            anyhow::bail!(
+                "Line {} of {:?} has an x coordinate without a matching y",
+                index + 1,
+                path
+            );
+        }
+
+        let points: Vec<Pos2D> = coords.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+        points_by_time.insert(frame, points);
+    }
+
+    Ok(points_by_time)
+}
+
+/// Draw the points of each frame onto the matching input image and save it to the output
+/// directory, under the input's file name or, with `--rename-output`, as `frame_NNNN.png` in
+/// route order. Each frame is rendered independently, so the frames are drawn and saved in parallel
+fn render_frames(
+    cli: &Cli,
+    points_by_time: &HashMap<u32, Vec<Pos2D>>,
+    style: PathStyle,
+) -> Result<()> {
+    let bar = progress_bar(
+        cli.quiet,
+        Some(cli.images.len() as u64),
+        "Rendering frames {bar:40} {pos}/{len} [{elapsed}]",
+    );
+    let render_frame = |(t, img_path): (usize, &String)| -> Result<()> {
+        let mut rgb_img = open_image(img_path)
+            .with_context(|| format!("Failed to open image at {}", img_path))?
+            .to_rgb8();
+
+        if let Some(pts) = points_by_time.get(&(t as u32)) {
+            draw_path(&mut rgb_img, pts, style, cli.thickness, Rgb([255, 0, 0]));
         }
+
+        let out_path = if cli.rename_output {
+            cli.output_dir.join(format!("frame_{:04}.png", t))
+        } else {
+            let file_name = std::path::Path::new(img_path)
+                .file_name()
+                .unwrap_or_default();
+            cli.output_dir.join(file_name)
+        };
+        rgb_img
+            .save(&out_path)
+            .with_context(|| format!("Failed to save output image to {:?}", out_path))?;
+        bar.inc(1);
+        Ok(())
+    };
+
+    let mut pool = rayon::ThreadPoolBuilder::new();
+    if let Some(threads) = cli.threads {
+        pool = pool.num_threads(threads);
     }
+    let rendered = pool
+        .build()
+        .context("Failed to create the rendering thread pool")?
+        .install(|| cli.images.par_iter().enumerate().try_for_each(render_frame));
+    bar.finish_and_clear();
+    rendered?;
+    println!("Saved {} frames to {:?}", cli.images.len(), cli.output_dir);
+
+    Ok(())
 }
 
 #[derive(Parser)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    after_help = "Exit codes:\n  0  A path or route was found (or replayed)\n  1  Error, e.g. invalid arguments or unreadable inputs\n  2  No path or route was found"
+)]
 struct Cli {
     /// Input images
-    #[arg(required = true)]
+    #[arg(
+        required_unless_present = "images_from",
+        conflicts_with = "images_from"
+    )]
     images: Vec<String>,
 
+    /// Read the input images from this file instead, one path per line. Blank lines and
+    /// lines starting with `#` are skipped
+    #[arg(long)]
+    images_from: Option<PathBuf>,
+
+    /// Re-render the overlays from this existing route.txt instead of searching for a route.
+    /// The input images are the frames, in the order of the route's frame numbers
+    #[arg(long, conflicts_with_all = ["animate", "json_out", "csv_out", "projection", "summary"])]
+    replay: Option<PathBuf>,
+
     /// Algorithm to use
     #[arg(long, value_enum, default_value_t = Algorithm::Astar)]
     algo: Algorithm,
 
-    /// Start position (X Y). If not provided, uses all positions at axis=0
+    /// Start position (X Y). In temporal mode, the two coordinates other than the routing axis,
+    /// in x, y, t order. If not provided, uses all positions at the start slice
     #[arg(long, num_args = 2, value_names = ["X", "Y"])]
     start: Option<Vec<u32>>,
 
-    /// End position (X Y). If not provided, uses all positions at axis=-1
+    /// End position (X Y). In temporal mode, the two coordinates other than the routing axis,
+    /// in x, y, t order. If not provided, uses all positions at the end slice
     #[arg(long, num_args = 2, value_names = ["X", "Y"])]
     end: Option<Vec<u32>>,
 
+    /// Coordinate along the routing axis the route starts at (temporal only, default: 0)
+    #[arg(long)]
+    start_t: Option<u32>,
+
+    /// Coordinate along the routing axis the route ends at (temporal only, default: the last one)
+    #[arg(long)]
+    end_t: Option<u32>,
+
     /// Reach parameter: number of elements that can be skipped along each non-axis dimension (default: 1)
     #[arg(long, default_value_t = 1)]
     reach: usize,
 
-    /// Axis along which the path must always move forward (0=x, 1=y, 2=t/time, default: 2)
-    #[arg(long, default_value_t = 2)]
+    /// Axis along which the path must always move forward (0=x, 1=y, 2=t/time, default: 2).
+    /// Negative values count from the last axis, so -1 is time
+    #[arg(long, default_value_t = 2, value_parser = parse_axis, allow_negative_numbers = true)]
     axis: usize,
 
     /// Impassable value. If provided, will be used to filter out neighbours that have this value.
@@ -65,60 +491,360 @@ struct Cli {
 
     #[arg(long, default_value = None)]
     filename: Option<String>,
+
+    /// Save the temporal overlays as frame_0000.png, frame_0001.png, ... in route order instead
+    /// of under the input file names, e.g. to assemble them into a video with ffmpeg
+    #[arg(long)]
+    rename_output: bool,
+
+    /// Also write an animated GIF of the search frontier growing, then the final path (2D only)
+    #[arg(long)]
+    animate: bool,
+
+    /// Path of the animated GIF (default: search.gif in the output directory)
+    #[arg(long, requires = "animate")]
+    gif_out: Option<PathBuf>,
+
+    /// Number of expanded cells between two GIF frames
+    #[arg(long, default_value_t = 1000, requires = "animate")]
+    gif_every: usize,
+
+    /// Also write the 2D path and its cost as JSON to this file
+    #[arg(long)]
+    json_out: Option<PathBuf>,
+
+    /// Also write the temporal route as CSV (`frame,x,y`) to this file
+    #[arg(long)]
+    csv_out: Option<PathBuf>,
+
+    /// Also write a summary image of the temporal route to this file: the maximum-intensity
+    /// projection of the volume along the routing axis, with the whole route drawn onto it
+    #[arg(long)]
+    projection: Option<PathBuf>,
+
+    /// Also write a JSON summary of the run to this file: its parameters, the resulting cost
+    /// and path length, and the elapsed time
+    #[arg(long)]
+    summary: Option<PathBuf>,
+
+    /// Scale the cost of each pixel by its alpha channel, read as confidence: the cost becomes
+    /// `value / max(alpha, 1) * 255`, clamped to 255 (2D only)
+    #[arg(long)]
+    use_alpha: bool,
+
+    /// Nearest-neighbour downsample the input images by this factor before searching, for
+    /// faster, coarser routing. The start and end are given at the input resolution, and the
+    /// path is scaled back to it for the outputs. Temporal frames are all kept
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    downsample: u32,
+
+    /// Rescale the reported costs of a downsampled search by the factor, approximating the
+    /// cost at the input resolution instead of the cost in downsampled space
+    #[arg(long)]
+    rescale_cost: bool,
+
+    /// Draw a filled circle at each path point (default)
+    #[arg(long, conflicts_with = "line")]
+    dots: bool,
+
+    /// Draw line segments connecting consecutive path points
+    #[arg(long)]
+    line: bool,
+
+    /// Number of threads used to render temporal frames (default: one per CPU)
+    #[arg(long)]
+    threads: Option<usize>,
+
+    /// Radius of the dots, or half-width of the line segments, in pixels
+    #[arg(long, default_value_t = 3)]
+    thickness: u32,
+
+    /// Run temporal routing even on a single input image, as a one-frame volume, instead of
+    /// 2D pathfinding, so scripts need not special-case runs with one frame
+    #[arg(long)]
+    force_temporal: bool,
+
+    /// Hide the progress bars of the temporal search and frame rendering, e.g. in scripts
+    #[arg(long)]
+    quiet: bool,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum Algorithm {
     Astar,
     Dijkstra,
     Fringe,
+    /// Run every algorithm and compare their costs and running times
+    All,
+}
+
+impl Algorithm {
+    /// The algorithms to run for this choice: all of them for `All`, else just this one.
+    fn runs(self) -> &'static [Algorithm] {
+        match self {
+            Algorithm::Astar => &[Algorithm::Astar],
+            Algorithm::Dijkstra => &[Algorithm::Dijkstra],
+            Algorithm::Fringe => &[Algorithm::Fringe],
+            Algorithm::All => &[Algorithm::Astar, Algorithm::Dijkstra, Algorithm::Fringe],
+        }
+    }
+}
+
+/// Run `search` with each of `algorithms` in order and return the first algorithm's result.
+/// When there is more than one, a table of each one's cost, path length, and running time
+/// is printed.
+fn compare_algorithms<P>(
+    algorithms: &[Algorithm],
+    mut search: impl FnMut(usize, Algorithm) -> Option<(Vec<P>, u32)>,
+) -> Option<(Vec<P>, u32)> {
+    let mut runs = algorithms.iter().enumerate().map(|(i, &algorithm)| {
+        let started = Instant::now();
+        let path = search(i, algorithm);
+        (algorithm, path, started.elapsed())
+    });
+
+    if let [_] = algorithms {
+        return runs.next().and_then(|(_, path, _)| path);
+    }
+
+    println!(
+        "{:<10} {:>10} {:>8} {:>12}",
+        "Algorithm", "Cost", "Length", "Time (ms)"
+    );
+    let mut first = None;
+    for (algorithm, path, elapsed) in runs {
+        let name = format!("{:?}", algorithm).to_lowercase();
+        let millis = elapsed.as_secs_f64() * 1000.0;
+        match &path {
+            Some((points, cost)) => println!(
+                "{:<10} {:>10} {:>8} {:>12.3}",
+                name,
+                cost,
+                points.len(),
+                millis
+            ),
+            None => println!("{:<10} {:>10} {:>8} {:>12.3}", name, "-", "-", millis),
+        }
+        first.get_or_insert(path);
+    }
+
+    println!(
+        "Saving the result of {}",
+        format!("{:?}", algorithms[0]).to_lowercase()
+    );
+    first.flatten()
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+/// Write the `--summary` JSON of a run. Like `--json-out`, it is also written when no path
+/// was found, with a null cost and a path length of 0.
+fn write_summary(
+    cli: &Cli,
+    summary_path: &Path,
+    path: Option<(usize, u32)>,
+    elapsed: Duration,
+) -> Result<()> {
+    let (path_length, cost) = match path {
+        Some((path_length, cost)) => (path_length, Some(cost)),
+        None => (0, None),
+    };
+    let summary = serde_json::json!({
+        "algorithm": format!("{:?}", cli.algo).to_lowercase(),
+        "start": cli.start,
+        "end": cli.end,
+        "start_t": cli.start_t,
+        "end_t": cli.end_t,
+        "reach": cli.reach,
+        "axis": cli.axis,
+        "downsample": cli.downsample,
+        "cost": cost,
+        "path_length": path_length,
+        "frames": cli.images.len(),
+        "elapsed_seconds": elapsed.as_secs_f64(),
+    });
+    let file = fs::File::create(summary_path).context("Failed to create summary file")?;
+    serde_json::to_writer_pretty(file, &summary).context("Failed to write summary file")?;
+    println!("Saved run summary to {:?}", summary_path);
+    Ok(())
+}
+
+fn main() -> Result<ExitCode> {
+    // clap exits with 2 on invalid arguments, which is reserved for a missing path here
+    let mut cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(err) if err.use_stderr() => {
+            err.print()?;
+            return Ok(ExitCode::FAILURE);
+        }
+        Err(err) => err.exit(),
+    };
+    if let Some(list) = &cli.images_from {
+        cli.images = read_image_list(list)?;
+    }
+    if cli.images.is_empty() {
+        anyhow::bail!("No input images were given: pass at least one image or --images-from");
+    }
 
     // Create output directory
     fs::create_dir_all(&cli.output_dir).context("Failed to create output directory")?;
 
-    if cli.images.len() == 1 {
+    let style = if cli.line {
+        PathStyle::Line
+    } else {
+        PathStyle::Dots
+    };
+
+    if let Some(route_path) = &cli.replay {
+        // Replay Case - draw an existing route without searching
+        let points_by_time = read_route_file(route_path)?;
+        if let Some(frame) = points_by_time
+            .keys()
+            .find(|&&frame| frame as usize >= cli.images.len())
+        {
+            anyhow::bail!(
+                "Route file frame {} is out of range for {} input images",
+                frame,
+                cli.images.len()
+            );
+        }
+
+        println!(
+            "Replaying route {:?} on {} frames",
+            route_path,
+            cli.images.len()
+        );
+        render_frames(&cli, &points_by_time, style)?;
+    } else if cli.images.len() == 1 && !cli.force_temporal {
         // 2D Case - use ndarray for pathfinding
         println!("Running 2D pathfinding on {}", cli.images[0]);
         let img_path = &cli.images[0];
-        let array = load_png_to_ndarray(img_path);
+        let array = if cli.use_alpha {
+            load_png_weighted(img_path)
+        } else {
+            load_image_to_ndarray(img_path)
+        };
+        let factor = cli.downsample;
+        let array = downsample_2d(array, factor);
+
+        if cli.csv_out.is_some() {
+            anyhow::bail!("--csv-out is only supported for temporal routing");
+        }
+        if cli.projection.is_some() {
+            anyhow::bail!("--projection is only supported for temporal routing");
+        }
+        if cli.rename_output {
+            anyhow::bail!("--rename-output is only supported for temporal routing");
+        }
+        if cli.start_t.is_some() || cli.end_t.is_some() {
+            anyhow::bail!("--start-t and --end-t are only supported for temporal routing");
+        }
 
         // For 2D, we still need start/end positions
         let start_xy = if let Some(start) = &cli.start {
-            (start[0], start[1])
+            (start[0] / factor, start[1] / factor)
         } else {
             anyhow::bail!("Start position is required for 2D pathfinding");
         };
         let end_xy = if let Some(end) = &cli.end {
-            (end[0], end[1])
+            (end[0] / factor, end[1] / factor)
         } else {
             anyhow::bail!("End position is required for 2D pathfinding");
         };
 
-        let path = match cli.algo {
-            Algorithm::Dijkstra => {
-                Dijkstra2D {}.find_path_in_heatmap(array.view(), start_xy, end_xy, cli.impassable)
-            }
-            Algorithm::Astar => {
-                AStar2D {}.find_path_in_heatmap(array.view(), start_xy, end_xy, cli.impassable)
-            }
-            Algorithm::Fringe => {
-                Fringe2D {}.find_path_in_heatmap(array.view(), start_xy, end_xy, cli.impassable)
-            }
-        };
+        // Expanded cells are only recorded when an animation was requested, and only for the
+        // first run, so the GIF shows a single search
+        let recorder = ExpansionRecorder::default();
+        let started = Instant::now();
+        let path = compare_algorithms(cli.algo.runs(), |i, algorithm| {
+            let observer: &dyn SearchObserver = if cli.animate && i == 0 {
+                &recorder
+            } else {
+                &()
+            };
+            let pathfinder: &dyn ImagePathfinder2D = match algorithm {
+                Algorithm::Dijkstra => &Dijkstra2D {},
+                Algorithm::Astar => &AStar2D::default(),
+                Algorithm::Fringe => &Fringe2D {},
+                Algorithm::All => unreachable!("expanded by Algorithm::runs"),
+            };
+            pathfinder.find_path_in_heatmap_with_observer(
+                array.view(),
+                start_xy,
+                end_xy,
+                cli.impassable,
+                observer,
+            )
+        });
+        let elapsed = started.elapsed();
+
+        // Back to the input resolution, which the outputs are drawn and written at
+        let upscale = |(x, y): Pos2D| (x * factor, y * factor);
+        print_downsample_note(&cli);
+        let path = path.map(|(points, cost)| {
+            let points: Vec<Pos2D> = points.into_iter().map(upscale).collect();
+            (points, report_cost(cost, &cli))
+        });
+
+        if let Some(summary_path) = &cli.summary {
+            let summary = path.as_ref().map(|(points, cost)| (points.len(), *cost));
+            write_summary(&cli, summary_path, summary, elapsed)?;
+        }
+
+        if cli.animate {
+            let gif_path = cli
+                .gif_out
+                .clone()
+                .unwrap_or_else(|| cli.output_dir.join("search.gif"));
+            let base = open_image(img_path)
+                .with_context(|| format!("Failed to load image at {}", img_path))?
+                .to_rgb8();
+            let expanded: Vec<Pos2D> = recorder
+                .0
+                .into_inner()
+                .unwrap()
+                .into_iter()
+                .map(upscale)
+                .collect();
+            let points = path.as_ref().map(|(points, _)| points.as_slice());
+
+            write_search_gif(
+                &base,
+                &expanded,
+                points,
+                cli.gif_every,
+                style,
+                cli.thickness,
+                &gif_path,
+            )?;
+            println!(
+                "Saved search animation ({} cells expanded) to {:?}",
+                expanded.len(),
+                gif_path
+            );
+        }
+
+        if let Some(json_path) = &cli.json_out {
+            // An empty route is still written, so consumers can tell "no path" from a missing file
+            let route = match &path {
+                Some((points, cost)) => serde_json::json!({ "cost": cost, "points": points }),
+                None => serde_json::json!({ "cost": null, "points": [] }),
+            };
+            let file = fs::File::create(json_path).context("Failed to create JSON file")?;
+            serde_json::to_writer(file, &route).context("Failed to write JSON file")?;
+            println!("Saved path JSON to {:?}", json_path);
+        }
 
         if let Some((points, cost)) = path {
             println!("Path found with cost: {}", cost);
-            let mut rgb_img = image::open(img_path)?.to_rgb8();
-            let red = Rgb([255, 0, 0]);
-
-            for (x, y) in points {
-                // Draw a red circle with radius 3 at each path point
-                draw_circle(&mut rgb_img, x, y, 3, red);
-            }
+            let mut rgb_img = open_image(img_path)
+                .with_context(|| format!("Failed to load image at {}", img_path))?
+                .to_rgb8();
+            draw_path(
+                &mut rgb_img,
+                &points,
+                style,
+                cli.thickness,
+                Rgb([255, 0, 0]),
+            );
 
             let file_name = std::path::Path::new(img_path)
                 .file_name()
@@ -136,69 +862,118 @@ fn main() -> Result<()> {
             println!("Saved result to {:?}", out_path);
         } else {
             println!("No path found!");
+            return Ok(ExitCode::from(NO_PATH_EXIT_CODE));
         }
     } else {
+        if cli.animate {
+            anyhow::bail!("--animate is only supported for 2D pathfinding");
+        }
+        if cli.json_out.is_some() {
+            anyhow::bail!("--json-out is only supported for 2D pathfinding");
+        }
+        if cli.use_alpha {
+            anyhow::bail!("--use-alpha is only supported for 2D pathfinding");
+        }
+
         // Temporal Case - use find_route_over_time
         println!("Running temporal routing on {} frames", cli.images.len());
         println!("Reach: {}, Axis: {}", cli.reach, cli.axis);
 
-        let volume = load_images_to_volume(&cli.images);
-        println!("Volume shape: {:?}", volume.shape());
-
-        // Prepare optional start/end positions
-        let starts = cli.start.map(|start| {
-            // For temporal routing, if start is provided, we need to determine the time coordinate
-            // Since routing moves along an axis, if axis=2 (time), we use t=0
-            // For other axes, we'd use the appropriate coordinate
-            let t = if cli.axis == 2 { 0 } else { 0 }; // Default to t=0 for time axis
-            vec![(start[0], start[1], t)]
-        });
-
-        let ends = cli.end.map(|end| {
-            // For temporal routing, if end is provided, we need to determine the time coordinate
-            // Since routing moves along an axis, if axis=2 (time), we use the last frame
-            let (_, _, depth) = volume.dim();
-            let t = if cli.axis == 2 {
-                depth as u32 - 1
-            } else {
-                depth as u32 - 1
-            };
-            vec![(end[0], end[1], t)]
-        });
+        let volume = Volume::load(&cli.images)?;
+        let factor = cli.downsample;
+        // The input volume is kept for the projection, which is drawn at the input resolution
+        let downsampled = (factor > 1).then(|| volume.downsample(factor));
+        let search_volume = downsampled.as_ref().unwrap_or(&volume);
+        let (width, height, depth) = volume.dim();
+        println!(
+            "Volume shape: [{}, {}, {}] ({}-bit)",
+            width,
+            height,
+            depth,
+            volume.bit_depth()
+        );
 
-        if let Some(ref starts) = starts {
-            println!("Start positions: {:?}", starts);
-        } else {
-            println!("Using default start positions (all positions at axis=0)");
+        // The start and end slices along the routing axis, which routes only move forward along
+        let axis_len = [width, height, depth][cli.axis] as u32;
+        let start_t = cli.start_t.unwrap_or(0);
+        let end_t = cli.end_t.unwrap_or(axis_len - 1);
+        for (flag, coord) in [("--start-t", start_t), ("--end-t", end_t)] {
+            if coord >= axis_len {
+                anyhow::bail!(
+                    "{} {} is out of bounds for axis {} of length {}",
+                    flag,
+                    coord,
+                    cli.axis,
+                    axis_len
+                );
+            }
         }
-        if let Some(ref ends) = ends {
-            println!("End positions: {:?}", ends);
-        } else {
-            println!("Using default end positions (all positions at axis=-1)");
+        if start_t > end_t {
+            anyhow::bail!(
+                "--start-t ({}) must not be after --end-t ({}), as routes only move forward along the axis",
+                start_t,
+                end_t
+            );
         }
 
-        // Dispatch algorithm using find_route_over_time
-        let path = match cli.algo {
-            Algorithm::Dijkstra => DijkstraTemporal {}.find_route_over_time(
-                volume.view(),
-                Some(cli.reach),
-                Some(cli.axis),
-                starts,
-                ends,
+        // Without explicit positions or slices, the router defaults to the first and last slices
+        let starts = (cli.start.is_some() || cli.start_t.is_some())
+            .then(|| slice_points(volume.dim(), cli.axis, cli.start.as_deref(), start_t))
+            .transpose()
+            .context("Invalid --start")?;
+        let ends = (cli.end.is_some() || cli.end_t.is_some())
+            .then(|| slice_points(volume.dim(), cli.axis, cli.end.as_deref(), end_t))
+            .transpose()
+            .context("Invalid --end")?;
+
+        match (&cli.start, &starts) {
+            (Some(_), Some(starts)) => println!("Start positions: {:?}", starts),
+            _ => println!(
+                "Using all positions at index {} of axis {} as start positions",
+                start_t, cli.axis
             ),
-            Algorithm::Astar => AStarTemporal {}.find_route_over_time(
-                volume.view(),
-                Some(cli.reach),
-                Some(cli.axis),
-                starts,
-                ends,
+        }
+        match (&cli.end, &ends) {
+            (Some(_), Some(ends)) => println!("End positions: {:?}", ends),
+            _ => println!(
+                "Using all positions at index {} of axis {} as end positions",
+                end_t, cli.axis
             ),
-            Algorithm::Fringe => {
-                anyhow::bail!(
-                    "Fringe algorithm is not supported for temporal routing. Use Dijkstra or Astar instead."
-                );
-            }
+        }
+
+        // Validated and printed at the input resolution, then mapped into the downsampled volume
+        let to_search = |points: Vec<Pos3D>| -> Vec<Pos3D> {
+            points
+                .into_iter()
+                .map(|(x, y, t)| (x / factor, y / factor, t))
+                .collect()
+        };
+        let starts = starts.map(to_search);
+        let ends = ends.map(to_search);
+
+        // Dispatch algorithm using find_route_over_time, at the bit depth of the frames
+        let algorithms = cli.algo.runs();
+        let started = Instant::now();
+        let path = match search_volume {
+            Volume::U8(volume) => route_volume(volume.view(), algorithms, &cli, &starts, &ends),
+            Volume::U16(volume) => route_volume(volume.view(), algorithms, &cli, &starts, &ends),
         };
+        let elapsed = started.elapsed();
+
+        // Back to the input resolution, which the outputs are written at
+        print_downsample_note(&cli);
+        let path = path.map(|(points, cost)| {
+            let points: Vec<Pos3D> = points
+                .into_iter()
+                .map(|(x, y, t)| (x * factor, y * factor, t))
+                .collect();
+            (points, report_cost(cost, &cli))
+        });
+
+        if let Some(summary_path) = &cli.summary {
+            let summary = path.as_ref().map(|(points, cost)| (points.len(), *cost));
+            write_summary(&cli, summary_path, summary, elapsed)?;
+        }
 
         if let Some((points, cost)) = path {
             let path_length = points.len();
@@ -206,8 +981,7 @@ fn main() -> Result<()> {
             println!("Path length: {} points", path_length);
 
             // Group points by time t
-            let mut points_by_time: std::collections::HashMap<u32, Vec<(u32, u32)>> =
-                std::collections::HashMap::new();
+            let mut points_by_time: HashMap<u32, Vec<Pos2D>> = HashMap::new();
             for (x, y, t) in &points {
                 points_by_time.entry(*t).or_default().push((*x, *y));
             }
@@ -244,33 +1018,43 @@ fn main() -> Result<()> {
                 .context("Failed to flush route.txt file")?;
             println!("Saved route to {:?}", route_file_path);
 
-            for (t, img_path) in cli.images.iter().enumerate() {
-                let t_u32 = t as u32;
-                let mut rgb_img = image::open(img_path)
-                    .with_context(|| format!("Failed to open image at {}", img_path))?
-                    .to_rgb8();
+            if let Some(csv_path) = &cli.csv_out {
+                // A stable sort keeps the path order of points sharing the same frame
+                let mut rows = points.clone();
+                rows.sort_by_key(|&(_, _, t)| t);
 
-                if let Some(pts) = points_by_time.get(&t_u32) {
-                    let red = Rgb([255, 0, 0]);
-                    for &(x, y) in pts {
-                        // Draw a red circle with radius 3 at each path point
-                        draw_circle(&mut rgb_img, x, y, 3, red);
-                    }
+                let mut csv_file =
+                    std::fs::File::create(csv_path).context("Failed to create CSV file")?;
+                writeln!(csv_file, "frame,x,y")?;
+                for (x, y, t) in rows {
+                    writeln!(csv_file, "{},{},{}", t, x, y)?;
                 }
+                csv_file.flush().context("Failed to flush CSV file")?;
+                println!("Saved route CSV to {:?}", csv_path);
+            }
 
-                let file_name = std::path::Path::new(img_path)
-                    .file_name()
-                    .unwrap_or_default();
-                let out_path = cli.output_dir.join(file_name);
-                rgb_img
-                    .save(&out_path)
-                    .context("Failed to save output image")?;
+            if let Some(projection_path) = &cli.projection {
+                let mut projection = volume.max_projection(cli.axis);
+                let route = project_route(&points, cli.axis);
+                draw_path(
+                    &mut projection,
+                    &route,
+                    style,
+                    cli.thickness,
+                    Rgb([255, 0, 0]),
+                );
+                projection.save(projection_path).with_context(|| {
+                    format!("Failed to save projection to {:?}", projection_path)
+                })?;
+                println!("Saved route projection to {:?}", projection_path);
             }
-            println!("Saved {} frames to {:?}", cli.images.len(), cli.output_dir);
+
+            render_frames(&cli, &points_by_time, style)?;
         } else {
             println!("No path found!");
+            return Ok(ExitCode::from(NO_PATH_EXIT_CODE));
         }
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
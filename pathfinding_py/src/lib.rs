@@ -1,133 +1,3664 @@
+use image::Rgb;
 use image_pathfinding::{
-    AStar2D, AStarTemporal, Dijkstra2D, DijkstraTemporal, Fringe2D, ImagePathfinder2D,
+    AStar2D, AStarTemporal, AxisStep, Colormap, Connectivity, Dijkstra2D, Dijkstra2DBidirectional,
+    DijkstraTemporal, DirectionWeights, Disjoint, Fringe2D, FringeTemporal, HeatmapValue,
+    Heuristic2D, ImagePathfinder2D, JumpPointSearch2D, NodeLimit, PathStyle, Pos2D, Pos3D, Pos4D,
+    ProgressReporter, Reach, Reach4D, RouteFailure, SearchDeadline, SearchObserver,
+    SearchOptions2D, SearchOptionsTemporal, SearchStats, TemporalProgress, ThetaStar2D, TieBreak,
+    Wrap, drop_collinear, frame_costs, interpolate_route, is_reachable, path_length, save_field_2d,
+    save_overlay_2d, simplify_path, touches_border,
 };
-use numpy::{PyReadonlyArray2, PyReadonlyArray3};
-use pyo3::exceptions::PyValueError;
+use numpy::ndarray::{Array2, ArrayView2, ArrayView3, ArrayView4, Axis};
+use numpy::{
+    PyArray2, PyArrayMethods, PyReadonlyArray2, PyReadonlyArray3, PyReadonlyArray4, PyUntypedArray,
+    PyUntypedArrayMethods,
+};
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::{PyRuntimeError, PyTimeoutError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// A 2D path as returned to Python: either a list of `(x, y)` tuples or an `(N, 2)` array.
+#[derive(IntoPyObject)]
+enum Path2D<'py> {
+    List(Vec<Pos2D>),
+    Array(Bound<'py, PyArray2<u32>>),
+}
+
+impl<'py> Path2D<'py> {
+    /// Convert a path into its Python representation.
+    /// When `as_array` is set, the points are packed into a C-contiguous `(N, 2)` uint32 array.
+    fn new(py: Python<'py>, path: Vec<Pos2D>, as_array: bool) -> Self {
+        if !as_array {
+            return Path2D::List(path);
+        }
+
+        let mut array = Array2::zeros((path.len(), 2));
+        for (i, (x, y)) in path.into_iter().enumerate() {
+            array[[i, 0]] = x;
+            array[[i, 1]] = y;
+        }
+
+        Path2D::Array(PyArray2::from_owned_array(py, array))
+    }
+}
+
+/// One or several 2D points, as accepted from Python: a single `(x, y)` tuple or a list of them.
+#[derive(FromPyObject)]
+enum Points2D {
+    One(Pos2D),
+    Many(Vec<Pos2D>),
+}
+
+impl Points2D {
+    fn as_slice(&self) -> &[Pos2D] {
+        match self {
+            Points2D::One(point) => std::slice::from_ref(point),
+            Points2D::Many(points) => points,
+        }
+    }
+
+    /// The same points given in `order`, converted to (x, y).
+    fn into_xy(self, order: Order2D) -> Self {
+        match self {
+            Points2D::One(point) => Points2D::One(order.swap(point)),
+            Points2D::Many(points) => {
+                Points2D::Many(points.into_iter().map(|point| order.swap(point)).collect())
+            }
+        }
+    }
+}
+
+/// The order of the coordinates of the 2D points exchanged with Python.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Order2D {
+    /// `(x, y)`, indexing the array as `array[x, y]`.
+    XY,
+    /// `(row, col)`, i.e. `(y, x)`, indexing the array as `array[col, row]`.
+    RowCol,
+}
+
+impl Order2D {
+    /// Parse the order of coordinates: "xy" or "rc", ignoring case.
+    fn parse(order: &str) -> PyResult<Self> {
+        match order.to_lowercase().as_str() {
+            "xy" => Ok(Order2D::XY),
+            "rc" => Ok(Order2D::RowCol),
+            _ => Err(PyValueError::new_err(format!(
+                "Unsupported order: {}. Supported: xy, rc",
+                order
+            ))),
+        }
+    }
+
+    /// Convert a point between this order and (x, y). Swapping both coordinates is its own
+    /// inverse, so the same conversion applies in both directions.
+    fn swap(self, (a, b): Pos2D) -> Pos2D {
+        match self {
+            Order2D::XY => (a, b),
+            Order2D::RowCol => (b, a),
+        }
+    }
+}
+
+/// One or several temporal points, as accepted from Python: a single `(x, y, t)` tuple or a list of them.
+#[derive(FromPyObject)]
+enum Points3D {
+    One(Pos3D),
+    Many(Vec<Pos3D>),
+}
+
+impl Points3D {
+    fn as_slice(&self) -> &[Pos3D] {
+        match self {
+            Points3D::One(point) => std::slice::from_ref(point),
+            Points3D::Many(points) => points,
+        }
+    }
+
+    /// The points to route from or to. An empty list means "every position on the axis boundary".
+    fn into_route_points(self) -> Option<Vec<Pos3D>> {
+        match self {
+            Points3D::One(point) => Some(vec![point]),
+            Points3D::Many(points) if points.is_empty() => None,
+            Points3D::Many(points) => Some(points),
+        }
+    }
+}
+
+/// A temporal reach, as accepted from Python: a single int or a `(reach_dim0, reach_dim1)` tuple.
+#[derive(FromPyObject)]
+enum ReachArg {
+    Uniform(usize),
+    PerDimension(usize, usize),
+}
+
+impl From<ReachArg> for Reach {
+    fn from(reach: ReachArg) -> Self {
+        match reach {
+            ReachArg::Uniform(reach) => reach.into(),
+            ReachArg::PerDimension(reach_dim0, reach_dim1) => Reach(reach_dim0, reach_dim1),
+        }
+    }
+}
+
+/// A 4D reach, as accepted from Python: a single int or a tuple with one value per non-axis dimension.
+#[derive(FromPyObject)]
+enum Reach4DArg {
+    Uniform(usize),
+    PerDimension(usize, usize, usize),
+}
+
+impl From<Reach4DArg> for Reach4D {
+    fn from(reach: Reach4DArg) -> Self {
+        match reach {
+            Reach4DArg::Uniform(reach) => reach.into(),
+            Reach4DArg::PerDimension(reach_dim0, reach_dim1, reach_dim2) => {
+                Reach4D(reach_dim0, reach_dim1, reach_dim2)
+            }
+        }
+    }
+}
+
+/// Convert search statistics into a Python dict.
+fn stats_to_dict<'py>(py: Python<'py>, stats: &SearchStats) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("nodes_expanded", stats.nodes_expanded)?;
+    dict.set_item("nodes_generated", stats.nodes_generated)?;
+    dict.set_item("peak_frontier_size", stats.peak_frontier_size)?;
+    Ok(dict)
+}
+
+/// A path through a 2D heatmap and its total cost, as returned to Python.
+type Route2D = Option<(Vec<Pos2D>, u32)>;
+
+/// A temporal route and its total cost, as returned to Python.
+type Route3D = Option<(Vec<Pos3D>, u32)>;
+
+/// A route through a 4D volume and its total cost, as returned to Python.
+type Route4D = Option<(Vec<Pos4D>, u32)>;
+
+/// A 2D heatmap borrowed from NumPy, in one of the supported dtypes.
+enum Heatmap2D<'py> {
+    U8(PyReadonlyArray2<'py, u8>),
+    U16(PyReadonlyArray2<'py, u16>),
+}
+
+impl<'py> Heatmap2D<'py> {
+    /// Borrow a 2D uint8 or uint16 NumPy array, raising a `ValueError` for anything else.
+    /// Arrays of any memory layout are borrowed as they are, as the view follows their strides.
+    fn extract(array: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(array) = array.extract::<PyReadonlyArray2<u8>>() {
+            return Ok(Heatmap2D::U8(array));
+        }
+        if let Ok(array) = array.extract::<PyReadonlyArray2<u16>>() {
+            return Ok(Heatmap2D::U16(array));
+        }
+
+        let found = match array.cast::<PyUntypedArray>() {
+            Ok(array) => format!("a {}D array of dtype {}", array.ndim(), array.dtype()),
+            Err(_) => format!("an object of type {}", array.get_type().name()?),
+        };
+        Err(PyValueError::new_err(format!(
+            "Unsupported heatmap: expected a 2D NumPy array of dtype uint8 or uint16, got {}",
+            found
+        )))
+    }
+
+    fn dim(&self) -> (usize, usize) {
+        match self {
+            Heatmap2D::U8(array) => array.as_array().dim(),
+            Heatmap2D::U16(array) => array.as_array().dim(),
+        }
+    }
+
+    /// Build the cost lookup table for every possible value of this heatmap's dtype, or `None`
+    /// if the pixel values are the costs as they are. Raises a `ValueError` for a `cost_power`
+    /// that is not a finite, positive number, or a free value that does not fit the dtype.
+    ///
+    /// `clamp_cost` optionally caps every cost of the table, once `cost_fn`, `invert`, and
+    /// `cost_power` have been applied. `free` is an optional `(free_value, free_cost)` pair:
+    /// cells holding `free_value` cost `free_cost` as it is, overriding all of them.
+    fn cost_table(
+        &self,
+        cost_fn: Option<&Bound<'_, PyAny>>,
+        invert: bool,
+        cost_power: f64,
+        clamp_cost: Option<u32>,
+        free: Option<(u16, u32)>,
+    ) -> PyResult<Option<Vec<u32>>> {
+        if !cost_power.is_finite() || cost_power <= 0.0 {
+            return Err(PyValueError::new_err(format!(
+                "cost_power must be a finite, positive number, got {}",
+                cost_power
+            )));
+        }
+        if let (Heatmap2D::U8(_), Some((free_value, _))) = (self, free)
+            && u8::try_from(free_value).is_err()
+        {
+            return Err(PyValueError::new_err(format!(
+                "Free value {} does not fit the uint8 heatmap",
+                free_value
+            )));
+        }
+
+        if cost_fn.is_none()
+            && !invert
+            && cost_power == 1.0
+            && clamp_cost.is_none()
+            && free.is_none()
+        {
+            return Ok(None);
+        }
+
+        let mut table = match self {
+            Heatmap2D::U8(_) => build_cost_table::<u8>(cost_fn, invert, cost_power)?,
+            Heatmap2D::U16(_) => build_cost_table::<u16>(cost_fn, invert, cost_power)?,
+        };
+        if let Some(clamp_cost) = clamp_cost {
+            for cost in &mut table {
+                *cost = (*cost).min(clamp_cost);
+            }
+        }
+        if let Some((free_value, free_cost)) = free {
+            table[free_value as usize] = free_cost;
+        }
+
+        Ok(Some(table))
+    }
+
+    /// The pixel value at `(x, y)`, which must be in bounds.
+    fn value(&self, (x, y): Pos2D) -> u16 {
+        let index = [x as usize, y as usize];
+        match self {
+            Heatmap2D::U8(array) => array.as_array()[index] as u16,
+            Heatmap2D::U16(array) => array.as_array()[index],
+        }
+    }
+}
+
+/// Build a cost lookup table by evaluating `cost_fn` once for every possible pixel value.
+/// With `invert`, each value `v` is first replaced by `max - v`, `max` being the largest value
+/// of the dtype. Without `cost_fn`, the (inverted) value is the cost. Each cost `c` is then
+/// mapped to `round(c ** cost_power)`, saturating at `u32::MAX`.
+fn build_cost_table<T: HeatmapValue>(
+    cost_fn: Option<&Bound<'_, PyAny>>,
+    invert: bool,
+    cost_power: f64,
+) -> PyResult<Vec<u32>> {
+    (0..T::VALUE_COUNT)
+        .map(|value| {
+            let value = if invert {
+                T::VALUE_COUNT - 1 - value
+            } else {
+                value
+            };
+            let cost = match cost_fn {
+                Some(cost_fn) => {
+                    let cost = cost_fn.call1((value,))?;
+                    cost.extract::<u32>().map_err(|_| {
+                        PyValueError::new_err(format!(
+                            "cost_fn must return a non-negative integer fitting in uint32, got {} for value {}",
+                            cost, value
+                        ))
+                    })?
+                }
+                None => value as u32,
+            };
+
+            if cost_power == 1.0 {
+                return Ok(cost);
+            }
+            // Float to integer casts saturate, so huge powers clamp to u32::MAX
+            Ok((cost as f64).powf(cost_power).round() as u32)
+        })
+        .collect()
+}
+
+/// How a heatmap is turned into a search graph, shared by every query against it.
+struct Graph2D<'a> {
+    impassable: Option<u16>,
+    wall_value: Option<u16>,
+    block_above: Option<u32>,
+    block_below: Option<u32>,
+    connectivity: Connectivity,
+    neighbours: Option<&'a [(i32, i32)]>,
+    reach: u32,
+    wrap: Wrap,
+    mask: Option<ArrayView2<'a, bool>>,
+    cost_table: Option<&'a [u32]>,
+    speed: Option<ArrayView2<'a, u8>>,
+    turn_penalty: u32,
+    direction_weights: Option<DirectionWeights>,
+    no_corner_cutting: bool,
+    // Whether the graph suits jump point search, once it has been checked
+    jps_support: Option<&'a OnceLock<bool>>,
+}
+
+/// The parts of a 2D query that do not depend on the heatmap dtype.
+struct Query2D<'a> {
+    starts: &'a [Pos2D],
+    ends: &'a [Pos2D],
+    algorithm: Algorithm2D,
+    weight: f64,
+    heuristic: Heuristic2D,
+    tie_break: TieBreak,
+    block_threshold: Option<u32>,
+    timeout_ms: Option<u64>,
+    max_nodes: Option<usize>,
+    max_cost: Option<u32>,
+    drop_collinear: bool,
+    return_segment_costs: bool,
+    return_stats: bool,
+    return_visited: bool,
+    return_length: bool,
+    return_border_flag: bool,
+    normalize_cost: bool,
+    verify_optimal: bool,
+    return_partial: bool,
+    graph: &'a Graph2D<'a>,
+}
+
+/// A path found by a 2D query, with the extras that were requested.
+struct QueryResult2D {
+    path: Vec<Pos2D>,
+    cost: u32,
+    segment_costs: Option<Vec<u32>>,
+    stats: Option<SearchStats>,
+    visited: Option<Array2<u8>>,
+    /// Whether the path stops short of the ends, as none of them could be reached.
+    partial: bool,
+}
+
+/// Check that the `coords` of the point called `name` lie inside an array of the given `shape`,
+/// e.g. `start (10, 0) is out of bounds for array of shape 5x5`.
+fn ensure_in_bounds<C: Copy + Into<i64>>(
+    name: &str,
+    coords: &[C],
+    shape: &[usize],
+) -> PyResult<()> {
+    if coords
+        .iter()
+        .zip(shape)
+        .all(|(&coord, &len)| usize::try_from(coord.into()).is_ok_and(|coord| coord < len))
+    {
+        return Ok(());
+    }
+
+    let coords: Vec<String> = coords
+        .iter()
+        .map(|&coord| coord.into().to_string())
+        .collect();
+    let shape: Vec<String> = shape.iter().map(usize::to_string).collect();
+    Err(PyValueError::new_err(format!(
+        "{} ({}) is out of bounds for array of shape {}",
+        name,
+        coords.join(", "),
+        shape.join("x")
+    )))
+}
+
+/// The error raised when a search exceeds its `timeout_ms` budget.
+fn timeout_error(timeout_ms: u64) -> PyErr {
+    PyTimeoutError::new_err(format!(
+        "Search did not finish within the timeout of {} ms",
+        timeout_ms
+    ))
+}
+
+/// The error raised when a search expands more nodes than its `max_nodes` cap.
+fn node_limit_error(max_nodes: usize) -> PyErr {
+    PyRuntimeError::new_err(format!(
+        "Search did not finish within the limit of {} expanded nodes",
+        max_nodes
+    ))
+}
+
+/// Find the algorithm of `algorithms` whose name matches `name`, ignoring case.
+fn parse_algorithm<A: Copy>(
+    name: &str,
+    algorithms: &[A],
+    algorithm_name: impl Fn(A) -> &'static str,
+) -> PyResult<A> {
+    let lowercase = name.to_lowercase();
+    if let Some(&algorithm) = algorithms.iter().find(|&&a| algorithm_name(a) == lowercase) {
+        return Ok(algorithm);
+    }
+
+    let supported: Vec<&str> = algorithms.iter().map(|&a| algorithm_name(a)).collect();
+    Err(PyValueError::new_err(format!(
+        "Unknown algorithm: {}. Supported algorithms: {}",
+        name,
+        supported.join(", ")
+    )))
+}
+
+/// The 2D algorithms, by the names accepted from Python.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Algorithm2D {
+    AStar,
+    Dijkstra,
+    DijkstraBi,
+    Fringe,
+    Jps,
+    Theta,
+}
+
+impl Algorithm2D {
+    const ALL: [Algorithm2D; 6] = [
+        Algorithm2D::AStar,
+        Algorithm2D::Dijkstra,
+        Algorithm2D::DijkstraBi,
+        Algorithm2D::Fringe,
+        Algorithm2D::Jps,
+        Algorithm2D::Theta,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Algorithm2D::AStar => "astar",
+            Algorithm2D::Dijkstra => "dijkstra",
+            Algorithm2D::DijkstraBi => "dijkstra_bi",
+            Algorithm2D::Fringe => "fringe",
+            Algorithm2D::Jps => "jps",
+            Algorithm2D::Theta => "theta",
+        }
+    }
+
+    /// Parse an algorithm name, ignoring case.
+    fn parse(name: &str) -> PyResult<Self> {
+        parse_algorithm(name, &Self::ALL, Self::name)
+    }
+}
+
+/// The temporal algorithms, by the names accepted from Python.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AlgorithmTemporal {
+    AStar,
+    Dijkstra,
+    Fringe,
+}
+
+impl AlgorithmTemporal {
+    const ALL: [AlgorithmTemporal; 3] = [
+        AlgorithmTemporal::AStar,
+        AlgorithmTemporal::Dijkstra,
+        AlgorithmTemporal::Fringe,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            AlgorithmTemporal::AStar => "astar",
+            AlgorithmTemporal::Dijkstra => "dijkstra",
+            AlgorithmTemporal::Fringe => "fringe",
+        }
+    }
+
+    /// Parse an algorithm name, ignoring case.
+    fn parse(name: &str) -> PyResult<Self> {
+        parse_algorithm(name, &Self::ALL, Self::name)
+    }
+}
+
+/// Create the 2D pathfinder for `algorithm`. `astar` holds the settings used for A*.
+/// `supports_jps` is only called when jump point search is requested.
+fn make_pathfinder_2d<T: HeatmapValue>(
+    algorithm: Algorithm2D,
+    astar: AStar2D,
+    block_threshold: Option<u32>,
+    supports_jps: impl FnOnce() -> bool,
+) -> PyResult<Box<dyn ImagePathfinder2D<T> + Sync>> {
+    let pathfinder: Box<dyn ImagePathfinder2D<T> + Sync> = match algorithm {
+        Algorithm2D::AStar => Box::new(astar),
+        Algorithm2D::Dijkstra => Box::new(Dijkstra2D {}),
+        Algorithm2D::DijkstraBi => Box::new(Dijkstra2DBidirectional {}),
+        Algorithm2D::Fringe => Box::new(Fringe2D {}),
+        Algorithm2D::Theta => Box::new(ThetaStar2D { block_threshold }),
+        Algorithm2D::Jps => {
+            if !supports_jps() {
+                return Err(PyValueError::new_err(
+                    "jps requires connectivity=8, wrap=\"none\", turn_penalty=0, no_corner_cutting=False, neighbors=None, reach=1, speed=None, and the same cost for every free cell; use astar for heatmaps with varying costs",
+                ));
+            }
+            Box::new(JumpPointSearch2D {})
+        }
+    };
+
+    Ok(pathfinder)
+}
+
+/// Convert an impassable value to the uint8 dtype, failing if it does not fit.
+fn impassable_u8(impassable: Option<u16>) -> PyResult<Option<u8>> {
+    impassable
+        .map(|value| {
+            u8::try_from(value).map_err(|_| {
+                PyValueError::new_err(format!(
+                    "Impassable value {} does not fit the uint8 heatmap",
+                    value
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// Parse a `connectivity` of 4 or 8.
+fn parse_connectivity(connectivity: u8) -> PyResult<Connectivity> {
+    match connectivity {
+        4 => Ok(Connectivity::Four),
+        8 => Ok(Connectivity::Eight),
+        _ => Err(PyValueError::new_err(format!(
+            "Unsupported connectivity: {}. Supported values: 4, 8",
+            connectivity
+        ))),
+    }
+}
+
+/// Normalize the routing `axis` of an array with `ndim` dimensions, counting negative axes from
+/// the last one like NumPy, so -1 is the last axis. Raises a `ValueError` for an axis out of range.
+fn normalize_axis(axis: Option<isize>, ndim: usize) -> PyResult<Option<usize>> {
+    let Some(axis) = axis else {
+        return Ok(None);
+    };
+
+    let normalized = if axis < 0 { axis + ndim as isize } else { axis };
+    if let Ok(normalized) = usize::try_from(normalized)
+        && normalized < ndim
+    {
+        return Ok(Some(normalized));
+    }
+
+    let axes: Vec<String> = (0..ndim - 1).map(|axis| axis.to_string()).collect();
+    Err(PyValueError::new_err(format!(
+        "axis must be {} or {} for a {}D array, or -{} to -1 counting from the last axis, got {}",
+        axes.join(", "),
+        ndim - 1,
+        ndim,
+        ndim,
+        axis
+    )))
+}
+
+/// Check an `axis_step_min`/`axis_step_max` pair, which must satisfy `1 <= min <= max`.
+fn check_axis_step(min: u32, max: u32) -> PyResult<AxisStep> {
+    if min == 0 || min > max {
+        return Err(PyValueError::new_err(format!(
+            "axis_step_min ({}) must be at least 1 and at most axis_step_max ({})",
+            min, max
+        )));
+    }
+    Ok(AxisStep { min, max })
+}
+
+/// Parse which borders wrap around: "none", "x", "y", or "xy", ignoring case.
+fn parse_wrap(wrap: &str) -> PyResult<Wrap> {
+    match wrap.to_lowercase().as_str() {
+        "none" => Ok(Wrap::None),
+        "x" => Ok(Wrap::X),
+        "y" => Ok(Wrap::Y),
+        "xy" => Ok(Wrap::XY),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown wrap: {}. Supported values: none, x, y, xy",
+            wrap
+        ))),
+    }
+}
+
+/// Parse the name of a colormap, ignoring case.
+fn parse_colormap(colormap: &str) -> PyResult<Colormap> {
+    match colormap.to_lowercase().as_str() {
+        "viridis" => Ok(Colormap::Viridis),
+        "grayscale" => Ok(Colormap::Grayscale),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown colormap: {}. Supported colormaps: viridis, grayscale",
+            colormap
+        ))),
+    }
+}
+
+/// Parse the name of an A* heuristic, ignoring case.
+fn parse_heuristic(heuristic: &str) -> PyResult<Heuristic2D> {
+    match heuristic.to_lowercase().as_str() {
+        "manhattan" => Ok(Heuristic2D::Manhattan),
+        "euclidean" => Ok(Heuristic2D::Euclidean),
+        "chebyshev" => Ok(Heuristic2D::Chebyshev),
+        "octile" => Ok(Heuristic2D::Octile),
+        "zero" => Ok(Heuristic2D::Zero),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown heuristic: {}. Supported heuristics: manhattan, euclidean, chebyshev, octile, zero",
+            heuristic
+        ))),
+    }
+}
+
+/// Parse which of several equally cheap paths to prefer, ignoring case.
+fn parse_tie_break(prefer: &str) -> PyResult<TieBreak> {
+    match prefer.to_lowercase().as_str() {
+        "none" => Ok(TieBreak::None),
+        "fewest_steps" => Ok(TieBreak::FewestSteps),
+        "shortest_euclidean" => Ok(TieBreak::ShortestEuclidean),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown prefer: {}. Supported values: none, fewest_steps, shortest_euclidean",
+            prefer
+        ))),
+    }
+}
+
+/// Check a custom movement kernel of `(dx, dy)` offsets: it must not be empty, and its
+/// offsets must be nonzero and distinct.
+fn parse_neighbours(neighbours: Option<Vec<(i32, i32)>>) -> PyResult<Option<Vec<(i32, i32)>>> {
+    let Some(neighbours) = neighbours else {
+        return Ok(None);
+    };
+
+    if neighbours.is_empty() {
+        return Err(PyValueError::new_err(
+            "neighbors must hold at least one (dx, dy) offset",
+        ));
+    }
+    let mut seen = HashSet::new();
+    for &offset in &neighbours {
+        if offset == (0, 0) {
+            return Err(PyValueError::new_err(
+                "Neighbor offset (0, 0) is not a move; offsets must be nonzero",
+            ));
+        }
+        if !seen.insert(offset) {
+            return Err(PyValueError::new_err(format!(
+                "Neighbor offset {:?} is given more than once",
+                offset
+            )));
+        }
+    }
+
+    Ok(Some(neighbours))
+}
+
+/// Check the longest cardinal step of a 2D search, which only applies without a movement kernel.
+fn check_reach_2d(reach: u32, neighbours: Option<&[(i32, i32)]>) -> PyResult<u32> {
+    if reach == 0 {
+        return Err(PyValueError::new_err("reach must be at least 1"));
+    }
+    if reach > 1 && neighbours.is_some() {
+        return Err(PyValueError::new_err(
+            "reach cannot be combined with neighbors; add the longer steps to the movement kernel instead",
+        ));
+    }
+    Ok(reach)
+}
+
+/// Parse a dict of per-direction step cost multipliers, e.g. `{"up": 2.0}`. Directions that
+/// are left out weigh 1.
+fn parse_direction_weights(
+    weights: Option<HashMap<String, f64>>,
+) -> PyResult<Option<DirectionWeights>> {
+    let Some(weights) = weights else {
+        return Ok(None);
+    };
+
+    let mut parsed = DirectionWeights::default();
+    for (direction, weight) in weights {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(PyValueError::new_err(format!(
+                "Direction weight for {} must be a finite, non-negative number, got {}",
+                direction, weight
+            )));
+        }
+
+        match direction.to_lowercase().as_str() {
+            "up" => parsed.up = weight,
+            "down" => parsed.down = weight,
+            "left" => parsed.left = weight,
+            "right" => parsed.right = weight,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown direction: {}. Supported directions: up, down, left, right",
+                    direction
+                )));
+            }
+        }
+    }
+
+    Ok(Some(parsed))
+}
+
+/// Merge `impassable` and `wall_value`: a wall is an impassable value that the start and end
+/// may not lie on either.
+fn merge_wall_value(impassable: Option<u16>, wall_value: Option<u16>) -> PyResult<Option<u16>> {
+    match (impassable, wall_value) {
+        (Some(impassable), Some(wall)) if impassable != wall => {
+            Err(PyValueError::new_err(format!(
+                "impassable ({}) and wall_value ({}) must be equal when both are given",
+                impassable, wall
+            )))
+        }
+        (impassable, wall) => Ok(impassable.or(wall)),
+    }
+}
+
+/// Check that `block_above` and `block_below` leave some pixel values passable.
+fn check_block_range(block_above: Option<u32>, block_below: Option<u32>) -> PyResult<()> {
+    if let (Some(above), Some(below)) = (block_above, block_below)
+        && below > above
+    {
+        return Err(PyValueError::new_err(format!(
+            "block_below ({}) must not exceed block_above ({}), or every cell would be blocked",
+            below, above
+        )));
+    }
+    Ok(())
+}
+
+/// Check that `algorithm` can penalize turns if a `turn_penalty` is set. Jump point search is
+/// checked along with its other requirements when the pathfinder is created.
+fn check_turn_penalty(algorithm: Algorithm2D, turn_penalty: u32) -> PyResult<()> {
+    if turn_penalty > 0 && matches!(algorithm, Algorithm2D::DijkstraBi | Algorithm2D::Theta) {
+        return Err(PyValueError::new_err(format!(
+            "turn_penalty is not supported by {}; use astar, dijkstra, or fringe",
+            algorithm.name()
+        )));
+    }
+    Ok(())
+}
+
+/// Check that a mask, if any, has the same shape as the heatmap.
+fn check_mask_shape(mask: Option<ArrayView2<bool>>, heatmap: &Heatmap2D) -> PyResult<()> {
+    if let Some(mask) = mask
+        && mask.dim() != heatmap.dim()
+    {
+        return Err(PyValueError::new_err(format!(
+            "Mask shape {:?} does not match array shape {:?}",
+            mask.dim(),
+            heatmap.dim()
+        )));
+    }
+    Ok(())
+}
+
+/// Check that a speed map, if any, has the same shape as the heatmap.
+fn check_speed_shape(speed: Option<ArrayView2<u8>>, heatmap: &Heatmap2D) -> PyResult<()> {
+    if let Some(speed) = speed
+        && speed.dim() != heatmap.dim()
+    {
+        return Err(PyValueError::new_err(format!(
+            "Speed map shape {:?} does not match array shape {:?}",
+            speed.dim(),
+            heatmap.dim()
+        )));
+    }
+    Ok(())
+}
+
+/// Check the shape of the obstacle mask, and also block the masked cells of a
+/// `numpy.ma.MaskedArray` heatmap. Plain arrays leave the obstacle mask as it is.
+fn merge_heatmap_mask<'py>(
+    array: &Bound<'py, PyAny>,
+    heatmap: &Heatmap2D,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+) -> PyResult<Option<PyReadonlyArray2<'py, bool>>> {
+    check_mask_shape(mask.as_ref().map(|mask| mask.as_array()), heatmap)?;
+
+    let numpy_ma = array.py().import("numpy.ma")?;
+    if !numpy_ma
+        .call_method1("isMaskedArray", (array,))?
+        .is_truthy()?
+    {
+        return Ok(mask);
+    }
+
+    // Unlike `array.mask`, this is a full boolean array even when nothing is masked
+    let own_mask: PyReadonlyArray2<bool> =
+        numpy_ma.call_method1("getmaskarray", (array,))?.extract()?;
+    let Some(mask) = mask else {
+        return Ok(Some(own_mask));
+    };
+
+    let merged = &own_mask.as_array() | &mask.as_array();
+    Ok(Some(
+        PyArray2::from_owned_array(array.py(), merged).readonly(),
+    ))
+}
+
+/// Check the per-query search parameters that do not depend on the heatmap.
+fn check_search_params(weight: f64, simplify: Option<f64>) -> PyResult<()> {
+    if !weight.is_finite() || weight < 0.0 {
+        return Err(PyValueError::new_err(format!(
+            "Weight must be a finite, non-negative number, got {}",
+            weight
+        )));
+    }
+
+    if let Some(tolerance) = simplify
+        && !(tolerance.is_finite() && tolerance >= 0.0)
+    {
+        return Err(PyValueError::new_err(format!(
+            "Simplify tolerance must be a finite, non-negative number, got {}",
+            tolerance
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that every start and end lies in bounds, off walls, and off masked cells.
+fn check_endpoints(
+    heatmap: &Heatmap2D,
+    graph: &Graph2D,
+    starts: &[Pos2D],
+    ends: &[Pos2D],
+) -> PyResult<()> {
+    if starts.is_empty() || ends.is_empty() {
+        return Err(PyValueError::new_err(
+            "At least one start and one end position are required",
+        ));
+    }
+
+    let (width, height) = heatmap.dim();
+    let shape = [width, height];
+    for &(x, y) in starts {
+        ensure_in_bounds("start", &[x, y], &shape)?;
+    }
+    for &(x, y) in ends {
+        ensure_in_bounds("end", &[x, y], &shape)?;
+    }
+
+    if let Some(wall) = graph.wall_value {
+        let on_wall = |&&point: &&Pos2D| heatmap.value(point) == wall;
+        if let Some(start) = starts.iter().find(on_wall) {
+            return Err(PyValueError::new_err(format!(
+                "Start position {:?} lies on a wall (value {})",
+                start, wall
+            )));
+        }
+        if let Some(end) = ends.iter().find(on_wall) {
+            return Err(PyValueError::new_err(format!(
+                "End position {:?} lies on a wall (value {})",
+                end, wall
+            )));
+        }
+    }
+
+    let is_masked = |&&(x, y): &&Pos2D| {
+        graph
+            .mask
+            .is_some_and(|mask| mask[[x as usize, y as usize]])
+    };
+    if let Some(start) = starts.iter().find(is_masked) {
+        return Err(PyValueError::new_err(format!(
+            "Start position {:?} lies on a masked (blocked) cell",
+            start
+        )));
+    }
+    if let Some(end) = ends.iter().find(is_masked) {
+        return Err(PyValueError::new_err(format!(
+            "End position {:?} lies on a masked (blocked) cell",
+            end
+        )));
+    }
+
+    Ok(())
+}
+
+/// Marks each cell a search expands with 1, for `return_visited`.
+struct VisitedCells(Mutex<Array2<u8>>);
+
+impl SearchObserver for VisitedCells {
+    fn on_pop(&self, (x, y): Pos2D) {
+        self.0.lock().unwrap()[[x as usize, y as usize]] = 1;
+    }
+}
+
+/// Run a 2D query over a heatmap of any supported dtype, with the GIL released.
+fn run_query_2d<T: HeatmapValue>(
+    py: Python<'_>,
+    array: ArrayView2<T>,
+    impassable: Option<T>,
+    query: &Query2D,
+) -> PyResult<Option<QueryResult2D>> {
+    let graph = query.graph;
+    check_turn_penalty(query.algorithm, graph.turn_penalty)?;
+    if query.return_partial && graph.turn_penalty > 0 {
+        return Err(PyValueError::new_err(
+            "return_partial is not supported with turn_penalty, as partial paths are found without it",
+        ));
+    }
+    if query.tie_break != TieBreak::None {
+        if !matches!(
+            query.algorithm,
+            Algorithm2D::AStar | Algorithm2D::Dijkstra | Algorithm2D::Fringe
+        ) {
+            return Err(PyValueError::new_err(format!(
+                "prefer is not supported by {}; use astar, dijkstra, or fringe",
+                query.algorithm.name()
+            )));
+        }
+        if graph.turn_penalty > 0 {
+            return Err(PyValueError::new_err(
+                "prefer is not supported with turn_penalty, which already favours some of the equally cheap paths",
+            ));
+        }
+    }
+    if graph.neighbours.is_some() && query.algorithm == Algorithm2D::Theta {
+        return Err(PyValueError::new_err(
+            "neighbors is not supported by theta, whose any-angle steps ignore the movement kernel",
+        ));
+    }
+    if graph.reach > 1 && query.algorithm == Algorithm2D::Theta {
+        return Err(PyValueError::new_err(
+            "reach is not supported by theta, whose any-angle steps ignore the jumps",
+        ));
+    }
+    if query.verify_optimal && query.algorithm == Algorithm2D::Theta {
+        return Err(PyValueError::new_err(
+            "verify_optimal is not supported by theta, whose any-angle paths are not comparable to grid paths",
+        ));
+    }
+    let deadline = query
+        .timeout_ms
+        .map(|timeout| SearchDeadline::after(Duration::from_millis(timeout)));
+    let node_limit = query.max_nodes.map(NodeLimit::new);
+
+    // Expanded cells are only recorded when requested, as the observer locks on every expansion
+    let visited = query
+        .return_visited
+        .then(|| VisitedCells(Mutex::new(Array2::<u8>::zeros(array.dim()))));
+
+    let options = SearchOptions2D {
+        impassable,
+        block_above: graph.block_above,
+        block_below: graph.block_below,
+        connectivity: graph.connectivity,
+        neighbours: graph.neighbours,
+        reach: graph.reach,
+        wrap: graph.wrap,
+        mask: graph.mask,
+        cost_table: graph.cost_table,
+        speed: graph.speed,
+        deadline: deadline.as_ref(),
+        node_limit: node_limit.as_ref(),
+        observer: visited
+            .as_ref()
+            .map(|visited| visited as &dyn SearchObserver),
+        max_cost: query.max_cost,
+        turn_penalty: graph.turn_penalty,
+        direction_weights: graph.direction_weights,
+        blocked_edges: None,
+        no_corner_cutting: graph.no_corner_cutting,
+        tie_break: query.tie_break,
+    };
+
+    let supports_jps = || JumpPointSearch2D::supports(array, &options);
+    let pathfinder = make_pathfinder_2d(
+        query.algorithm,
+        AStar2D {
+            weight: query.weight,
+            heuristic: query.heuristic,
+        },
+        query.block_threshold,
+        || match graph.jps_support {
+            Some(cache) => *cache.get_or_init(supports_jps),
+            None => supports_jps(),
+        },
+    )?;
+
+    // The search only reads the borrowed array views, so the GIL is released while it runs.
+    // NumPy's borrow checking only guards against Rust code: the caller must not mutate
+    // `array` or `mask` from another Python thread until this call returns.
+    // Statistics are only collected when requested, to avoid the bookkeeping overhead.
+    let mut stats = query.return_stats.then(SearchStats::default);
+    let result = py.detach(|| {
+        pathfinder.find_path_between(array, query.starts, query.ends, &options, stats.as_mut())
+    });
+
+    // A failed search falls back to the path towards the closest explored cell, found by a
+    // plain Dijkstra flood, so the observer keeps recording the search that failed
+    let found = result.is_some();
+    let result = match result {
+        None if query.return_partial => {
+            let flood = SearchOptions2D {
+                observer: None,
+                ..options
+            };
+            py.detach(|| {
+                Dijkstra2D {}.find_partial_path(
+                    array,
+                    query.starts,
+                    query.ends,
+                    &flood,
+                    query.heuristic,
+                )
+            })
+        }
+        result => result,
+    };
+
+    if let Some(timeout) = query.timeout_ms
+        && deadline.as_ref().is_some_and(SearchDeadline::has_expired)
+    {
+        return Err(timeout_error(timeout));
+    }
+    if let Some(max_nodes) = query.max_nodes
+        && node_limit.as_ref().is_some_and(NodeLimit::was_exceeded)
+    {
+        return Err(node_limit_error(max_nodes));
+    }
+
+    let Some((path, cost)) = result else {
+        return Ok(None);
+    };
+    if query.verify_optimal && found {
+        verify_optimal_cost(py, array, query, &options, cost)?;
+    }
+    let segment_costs = query.return_segment_costs.then(|| {
+        let costs = if found {
+            pathfinder.segment_costs(array, &path, &options)
+        } else {
+            Dijkstra2D {}.segment_costs(array, &path, &options)
+        };
+        costs.expect("the search only takes edges of the search graph")
+    });
+
+    Ok(Some(QueryResult2D {
+        path,
+        cost,
+        segment_costs,
+        stats,
+        visited: visited.map(|VisitedCells(visited)| visited.into_inner().unwrap()),
+        partial: !found,
+    }))
+}
+
+/// Check that `cost`, the cost of the path found by the query's algorithm, matches the optimal
+/// cost found by Dijkstra with the same options. The check runs without the deadline, node
+/// limit, and observer of the query, as it is only meant for testing.
+fn verify_optimal_cost<T: HeatmapValue>(
+    py: Python<'_>,
+    array: ArrayView2<T>,
+    query: &Query2D,
+    options: &SearchOptions2D<T>,
+    cost: u32,
+) -> PyResult<()> {
+    let options = SearchOptions2D {
+        deadline: None,
+        node_limit: None,
+        observer: None,
+        ..*options
+    };
+    let optimal = py.detach(|| {
+        Dijkstra2D {}.find_path_between(array, query.starts, query.ends, &options, None)
+    });
+
+    match optimal {
+        Some((_, optimal)) if optimal != cost => Err(PyRuntimeError::new_err(format!(
+            "Path found by {} costs {}, but the optimal cost found by dijkstra is {}; check the heuristic and weight",
+            query.algorithm.name(),
+            cost,
+            optimal
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Merge the costs of the edges of `path` into one cost per segment of `simplified`, a
+/// subsequence of `path` with the same first and last points.
+fn merge_segment_costs(path: &[Pos2D], simplified: &[Pos2D], costs: &[u32]) -> Vec<u32> {
+    let mut merged = Vec::with_capacity(simplified.len().saturating_sub(1));
+    let mut corners = simplified.iter().skip(1).peekable();
+    let mut segment_cost = 0;
+
+    for (point, &cost) in path.iter().skip(1).zip(costs) {
+        segment_cost += cost;
+        if corners.next_if_eq(&point).is_some() {
+            merged.push(segment_cost);
+            segment_cost = 0;
+        }
+    }
+
+    merged
+}
+
+/// Run a 2D query and convert its result into the tuple returned by `find_path_2d`.
+fn search_2d<'py>(
+    py: Python<'py>,
+    heatmap: &Heatmap2D,
+    query: &Query2D,
+    (start, end): (&Points2D, &Points2D),
+    simplify: Option<f64>,
+    as_array: bool,
+    order: Order2D,
+) -> PyResult<Option<Bound<'py, PyTuple>>> {
+    let impassable = query.graph.impassable;
+    let result = match heatmap {
+        Heatmap2D::U8(array) => {
+            run_query_2d(py, array.as_array(), impassable_u8(impassable)?, query)?
+        }
+        Heatmap2D::U16(array) => run_query_2d(py, array.as_array(), impassable, query)?,
+    };
+
+    let Some(QueryResult2D {
+        path,
+        cost,
+        mut segment_costs,
+        stats,
+        visited,
+        partial,
+    }) = result
+    else {
+        return Ok(None);
+    };
+
+    // Checked on the full path, as simplification may drop the points lying on the border
+    let on_border = query
+        .return_border_flag
+        .then(|| touches_border(&path, heatmap.dim(), query.graph.wrap));
+
+    // Averaged over the edges of the full path, which the cost is summed over. A single-point
+    // path has no edges and costs nothing, so its mean is 0.
+    let mean_edge_cost = query.normalize_cost.then(|| match path.len() - 1 {
+        0 => 0.0,
+        edges => cost as f64 / edges as f64,
+    });
+
+    // Simplification keeps the first and last points, so the start and end lookups below still work
+    let path = match simplify {
+        Some(tolerance) => {
+            let simplified = simplify_path(&path, tolerance);
+            segment_costs =
+                segment_costs.map(|costs| merge_segment_costs(&path, &simplified, &costs));
+            simplified
+        }
+        None => path,
+    };
+    let path = if query.drop_collinear {
+        let thinned = drop_collinear(&path);
+        segment_costs = segment_costs.map(|costs| merge_segment_costs(&path, &thinned, &costs));
+        thinned
+    } else {
+        path
+    };
+
+    // Measured on the returned points, so a simplified path is measured along its shortcuts
+    let length = query
+        .return_length
+        .then(|| path_length(&path, heatmap.dim(), query.graph.wrap));
+
+    // When several candidates were given, report which start and end were used.
+    // The path starts on one of the candidates, and ends on one unless it is partial: it then
+    // reports the end closest to its last point.
+    let indices = match (start, end) {
+        (Points2D::One(_), Points2D::One(_)) => None,
+        _ => Some((
+            query.starts.iter().position(|p| Some(p) == path.first()),
+            match path.last() {
+                Some(&last) if partial => (0..query.ends.len())
+                    .min_by_key(|&i| query.heuristic.distance(last, query.ends[i])),
+                last => query.ends.iter().position(|p| Some(p) == last),
+            },
+        )),
+    };
+
+    let path = path.into_iter().map(|point| order.swap(point)).collect();
+    let mut items = vec![
+        Path2D::new(py, path, as_array).into_bound_py_any(py)?,
+        cost.into_bound_py_any(py)?,
+    ];
+    if let Some((start_idx, end_idx)) = indices {
+        items.push(start_idx.unwrap_or_default().into_bound_py_any(py)?);
+        items.push(end_idx.unwrap_or_default().into_bound_py_any(py)?);
+    }
+    if let Some(segment_costs) = segment_costs {
+        items.push(segment_costs.into_bound_py_any(py)?);
+    }
+    if let Some(stats) = stats {
+        items.push(stats_to_dict(py, &stats)?.into_any());
+    }
+    if let Some(visited) = visited {
+        items.push(PyArray2::from_owned_array(py, visited).into_any());
+    }
+    if let Some(length) = length {
+        items.push(length.into_bound_py_any(py)?);
+    }
+    if let Some(on_border) = on_border {
+        items.push(on_border.into_bound_py_any(py)?);
+    }
+    if let Some(mean_edge_cost) = mean_edge_cost {
+        items.push(mean_edge_cost.into_bound_py_any(py)?);
+    }
+    if query.return_partial {
+        items.push(partial.into_bound_py_any(py)?);
+    }
+
+    Ok(Some(PyTuple::new(py, items)?))
+}
+
+/// Find a path in a 2D heatmap using the specified algorithm.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `start` - Start position as (x, y) tuple, or a list of candidate start positions
+/// * `end` - End position as (x, y) tuple, or a list of candidate end positions
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", "dijkstra_bi", "fringe", "jps", or "theta"
+/// * `impassable` - Optional: A value that, if provided, will be used to filter out neighbours that have this value.
+/// * `wall_value` - Optional: Like `impassable`, cells with this value are never entered, but a start or end
+///   on such a cell raises `ValueError`. If both are given, they must be equal.
+/// * `free_value` - Optional: Cells with this value cost `free_cost` to enter whatever their intensity, e.g. roads
+///   in a terrain map. `cost_fn`, `invert`, `cost_power`, and `clamp_cost` do not apply to them. Walls take
+///   precedence: a cell matching `impassable`, `wall_value`, `block_above`, or `block_below` stays blocked even if
+///   it holds `free_value`.
+/// * `free_cost` - Optional: The cost of entering a `free_value` cell (default: 0)
+/// * `block_above` - Optional: Cells whose value is strictly above this threshold are never entered, e.g. to
+///   turn a grayscale image into a navigable map. A cell holding exactly `block_above` stays passable.
+/// * `block_below` - Optional: Cells whose value is strictly below this threshold are never entered. A cell
+///   holding exactly `block_below` stays passable. It must not exceed `block_above` if both are given.
+/// * `connectivity` - Optional: 4 for cardinal moves only, or 8 to also allow diagonal moves (default: 8)
+/// * `neighbors` - Optional: A list of `(dx, dy)` offsets defining the movement kernel, e.g. knight moves,
+///   which overrides `connectivity`. Offsets must be nonzero and distinct. A step longer than one cell costs
+///   its Euclidean length times the average cost of its source and destination pixels. Not supported by
+///   "jps" or "theta".
+/// * `reach` - Optional: The longest cardinal step, in cells (default: 1). Above 1, a path may also jump
+///   straight left, right, up, or down by up to `reach` cells in one step, costing the cost of the cell it
+///   lands on times its length, whatever the cells it crosses cost. Jumps can thus skip costly barriers up
+///   to `reach - 1` cells thick, but never cross a blocked cell. Cannot be combined with `neighbors`. Not
+///   supported by "jps" or "theta".
+/// * `wrap` - Optional: Which borders wrap around to the opposite side: "none", "x" (left and right),
+///   "y" (top and bottom), or "xy" (default: "none"). A path crossing a wrapped border steps
+///   directly between the two edge cells, so its coordinates stay inside the array.
+/// * `mask` - Optional: A 2D boolean NumPy array with the same shape as `array`, where `True` marks a blocked cell.
+///   If `array` is a `numpy.ma.MaskedArray`, its masked cells are blocked as well.
+/// * `speed` - Optional: A uint8 speed map with the same shape as `array`, dividing the cost of each cell,
+///   which becomes `cost // speed`, so faster regions such as roads are cheaper. Cells with a speed of 0 are
+///   never entered. "jps" does not support it.
+/// * `weight` - Optional: Heuristic weight for weighted A* (default: 1.0). Values above 1.0 trade optimality
+///   for speed, returning paths at most `weight` times the optimal cost. Only applies to "astar".
+/// * `heuristic` - Optional: The distance estimate used by "astar": "manhattan", "euclidean", "chebyshev",
+///   "octile", or "zero" (default: "manhattan"). "octile" prices straight and diagonal steps at the cheapest
+///   cell cost, so it stays admissible with 8-connectivity. "zero" makes A* behave like Dijkstra.
+/// * `prefer` - Optional: Which of several equally cheap paths to return: "fewest_steps", "shortest_euclidean"
+///   (1 per cardinal step, √2 per diagonal step), or "none" for whichever is found first (default: "none").
+///   The frontier is ranked by cost, then by this secondary criterion, so the cost found does not change.
+///   Only applies to "astar", "dijkstra", and "fringe"; the other algorithms raise `ValueError`. Not supported
+///   with `turn_penalty`.
+/// * `block_threshold` - Optional: Pixel values above this threshold occlude line of sight. Only applies to "theta".
+/// * `cost_fn` - Optional: A callable mapping a pixel value to a non-negative integer step cost. It is
+///   evaluated once per possible pixel value to build a lookup table before the search starts.
+/// * `invert` - Optional: If true, a pixel value `v` costs `255 - v` (`65535 - v` for uint16), so bright
+///   cells become cheap (default: false). `cost_fn`, if any, receives the inverted value. `impassable`,
+///   `wall_value`, `block_above`, `block_below`, and `block_threshold` still compare the original pixel values.
+/// * `turn_penalty` - Optional: An extra cost added to every step that changes the direction of travel,
+///   to discourage zig-zag paths (default: 0). A nonzero penalty makes the search track the direction
+///   each cell was entered from, expanding up to 8 times as many nodes and using as much more memory.
+///   Only applies to "astar", "dijkstra", and "fringe"; the other algorithms raise `ValueError`.
+/// * `dir_weights` - Optional: A dict of multipliers applied to the step costs in each direction, with keys
+///   "up" (towards smaller y), "down", "left", and "right". Missing directions weigh 1.0. A diagonal step
+///   takes the average of its two directions' weights. Each weighted step cost is rounded.
+/// * `cost_power` - Optional: An exponent applied to every step cost, which becomes `round(cost ** cost_power)`,
+///   clamped to uint32 (default: 1.0). Powers above 1 sharpen the differences between costs, powers
+///   below 1 flatten them. It is applied after `invert` and `cost_fn`. Must be positive.
+/// * `clamp_cost` - Optional: A maximum cost of any single cell. Costs above it are capped at it, after
+///   `invert`, `cost_fn`, and `cost_power`, so a few extreme pixels cannot dominate the route. It is applied
+///   to the lookup table, so it adds no cost to the search, and the array itself is left untouched.
+/// * `no_corner_cutting` - Optional: If true, a diagonal step is forbidden when either of the two cells it
+///   passes between is blocked, by value or by `mask`, so paths never squeeze between obstacles touching at a
+///   corner (default: false). "jps" does not support it.
+/// * `simplify` - Optional: A tolerance in pixels. If set, the path is simplified to its corner points with
+///   the Ramer–Douglas–Peucker algorithm. The returned cost is still the cost of the full grid path.
+/// * `drop_collinear` - Optional: If true, drop the points lying on the straight line between their
+///   neighbours, so straight runs collapse into their two ends (default: false). Unlike `simplify`, this is
+///   lossless: drawing straight lines between the remaining points traces exactly the same cells.
+/// * `timeout_ms` - Optional: A time budget in milliseconds. If the search runs longer, it is aborted
+///   and `TimeoutError` is raised. The clock is only checked every few thousand expanded nodes.
+/// * `max_nodes` - Optional: A cap on the number of expanded cells, bounding the memory the search can
+///   take on pathological inputs. If the search needs more, it is aborted and `RuntimeError` is raised.
+/// * `max_cost` - Optional: An inclusive maximum path cost. Cells that can only be reached above it are
+///   not expanded, and None is returned if no path fits, which is cheaper than finding the optimal path.
+/// * `as_array` - Optional: If true, return the path as an `(N, 2)` uint32 NumPy array instead of a list (default: false)
+/// * `return_segment_costs` - Optional: If true, also return the list of the costs of each edge of the path,
+///   summing to the total cost (default: false)
+/// * `return_stats` - Optional: If true, also return a dict of search statistics (default: false)
+/// * `return_visited` - Optional: If true, also return a uint8 array marking the cells the search
+///   expanded, i.e. took from the frontier (default: false)
+/// * `return_length` - Optional: If true, also return the geometric length of the path in pixels, which
+///   unlike the cost ignores pixel values (default: false)
+/// * `return_border_flag` - Optional: If true, also return whether the path touches the border of the
+///   image, which may mean the optimal route was clipped by the image extent (default: false)
+/// * `normalize_cost` - Optional: If true, also return the mean edge cost, the total cost divided by the
+///   number of steps, to compare routes across images of different brightness (default: false)
+/// * `return_partial` - Optional: If true, a search that cannot reach any end returns the cheapest path to
+///   the reachable cell closest to the ends by `heuristic`, instead of None, and a bool telling whether the
+///   path is partial is appended to the result (default: false). Not supported with `turn_penalty`.
+/// * `verify_optimal` - Optional: If true, also run Dijkstra after the search and raise a RuntimeError
+///   with both costs if the path found is not the cheapest one, e.g. because of an inadmissible heuristic
+///   or a `weight` above 1. This doubles the work and is only meant for testing; not supported by theta
+///   (default: false)
+/// * `order` - Optional: The order of the coordinates of `start`, `end`, and the returned path: "xy" for
+///   `(x, y)`, indexing `array[x, y]`, or "rc" for `(row, col)`, i.e. `(y, x)`, indexing `array[col, row]`
+///   (default: "xy"). Error messages still report positions as `(x, y)`.
+///
+/// # Returns
+/// * `Optional[Tuple[List[Tuple[int, int]], int]]` - The path found and total cost, or None if no path was found.
+///   The path is always ordered from the start (index 0) to the end (last index), for every algorithm.
+///   With `as_array=True` the path is a C-contiguous `numpy.ndarray` of shape `(N, 2)` holding `(x, y)` rows.
+///   If `start` or `end` is a list, the cheapest path connecting any start to any end is returned as
+///   `(path, cost, start_idx, end_idx)`, where the indices point at the start and end that were used.
+///   With `return_segment_costs=True` a list with one cost per edge, `len(path) - 1` of them, is
+///   appended to the tuple. If the path was simplified or thinned, each entry covers one remaining segment.
+///   With `return_stats=True` a dict with `nodes_expanded`, `nodes_generated`, and `peak_frontier_size`
+///   is appended to the tuple.
+///   With `return_visited=True` a uint8 array with the same shape as `array`, holding 1 for every cell
+///   that was expanded and 0 elsewhere, is appended next.
+///   With `return_length=True` the sum of the Euclidean distances between consecutive points of the
+///   returned path (1 per cardinal step, √2 per diagonal step) is appended next as a float.
+///   With `return_border_flag=True` a bool is appended next: True if any cell of the path lies on the
+///   first or last row or column, before simplification. Borders joined by `wrap` do not count.
+///   With `normalize_cost=True` the mean edge cost is appended next as a float: the total cost divided
+///   by the number of edges of the path before simplification, or 0.0 for a single-point path.
+///   With `return_partial=True` a bool is appended last: True if no end could be reached and the path
+///   stops at the closest reachable cell instead. `end_idx` then points at the end closest to it.
+///
+/// The GIL is released while searching, so other Python threads keep running. The arrays are
+/// borrowed, not copied: they must not be modified by another thread until the call returns.
+/// Non-contiguous arrays, such as transposed or strided slices, are read through their strides
+/// rather than copied, which gives the same results but may search more slowly.
+#[pyfunction]
+#[pyo3(signature = (array, start, end, algorithm, *, impassable=None, wall_value=None, free_value=None, free_cost=0, block_above=None, block_below=None, connectivity=8, neighbors=None, reach=1, wrap="none", mask=None, speed=None, weight=1.0, heuristic="manhattan", prefer="none", block_threshold=None, cost_fn=None, invert=false, cost_power=1.0, clamp_cost=None, turn_penalty=0, dir_weights=None, no_corner_cutting=false, simplify=None, drop_collinear=false, timeout_ms=None, max_nodes=None, max_cost=None, as_array=false, return_segment_costs=false, return_stats=false, return_visited=false, return_length=false, return_border_flag=false, normalize_cost=false, return_partial=false, verify_optimal=false, order="xy"))]
+#[allow(clippy::too_many_arguments)]
+fn find_path_2d<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    start: Points2D,
+    end: Points2D,
+    algorithm: &str,
+    impassable: Option<u16>,
+    wall_value: Option<u16>,
+    free_value: Option<u16>,
+    free_cost: u32,
+    block_above: Option<u32>,
+    block_below: Option<u32>,
+    connectivity: u8,
+    neighbors: Option<Vec<(i32, i32)>>,
+    reach: u32,
+    wrap: &str,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+    speed: Option<PyReadonlyArray2<'py, u8>>,
+    weight: f64,
+    heuristic: &str,
+    prefer: &str,
+    block_threshold: Option<u32>,
+    cost_fn: Option<Bound<'py, PyAny>>,
+    invert: bool,
+    cost_power: f64,
+    clamp_cost: Option<u32>,
+    turn_penalty: u32,
+    dir_weights: Option<HashMap<String, f64>>,
+    no_corner_cutting: bool,
+    simplify: Option<f64>,
+    drop_collinear: bool,
+    timeout_ms: Option<u64>,
+    max_nodes: Option<usize>,
+    max_cost: Option<u32>,
+    as_array: bool,
+    return_segment_costs: bool,
+    return_stats: bool,
+    return_visited: bool,
+    return_length: bool,
+    return_border_flag: bool,
+    normalize_cost: bool,
+    return_partial: bool,
+    verify_optimal: bool,
+    order: &str,
+) -> PyResult<Option<Bound<'py, PyTuple>>> {
+    // The dtype (uint8 or uint16) is detected at runtime and the search dispatched accordingly.
+    // Arrays must be provided in (x, y) order, i.e. shape (width, height).
+    // Use the array view directly - no transposing.
+    let heatmap = Heatmap2D::extract(array)?;
+
+    let algorithm = Algorithm2D::parse(algorithm)?;
+    check_search_params(weight, simplify)?;
+    let heuristic = parse_heuristic(heuristic)?;
+    let tie_break = parse_tie_break(prefer)?;
+    check_block_range(block_above, block_below)?;
+
+    let mask = merge_heatmap_mask(array, &heatmap, mask)?;
+    let mask = mask.as_ref().map(|mask| mask.as_array());
+    let speed = speed.as_ref().map(|speed| speed.as_array());
+    check_speed_shape(speed, &heatmap)?;
+
+    // The cost function is evaluated up front so the search never calls back into Python.
+    // Inversion goes through the same table, leaving the array itself untouched.
+    let neighbours = parse_neighbours(neighbors)?;
+
+    let free = free_value.map(|free_value| (free_value, free_cost));
+    let cost_table = heatmap.cost_table(cost_fn.as_ref(), invert, cost_power, clamp_cost, free)?;
+
+    let graph = Graph2D {
+        impassable: merge_wall_value(impassable, wall_value)?,
+        wall_value,
+        block_above,
+        block_below,
+        connectivity: parse_connectivity(connectivity)?,
+        neighbours: neighbours.as_deref(),
+        reach: check_reach_2d(reach, neighbours.as_deref())?,
+        wrap: parse_wrap(wrap)?,
+        mask,
+        cost_table: cost_table.as_deref(),
+        speed,
+        turn_penalty,
+        direction_weights: parse_direction_weights(dir_weights)?,
+        no_corner_cutting,
+        jps_support: None,
+    };
+
+    let order = Order2D::parse(order)?;
+    let (start, end) = (start.into_xy(order), end.into_xy(order));
+    let starts = start.as_slice();
+    let ends = end.as_slice();
+    check_endpoints(&heatmap, &graph, starts, ends)?;
+
+    let query = Query2D {
+        starts,
+        ends,
+        algorithm,
+        weight,
+        heuristic,
+        tie_break,
+        block_threshold,
+        timeout_ms,
+        max_nodes,
+        max_cost,
+        drop_collinear,
+        return_segment_costs,
+        return_stats,
+        return_visited,
+        return_length,
+        return_border_flag,
+        normalize_cost,
+        verify_optimal,
+        return_partial,
+        graph: &graph,
+    };
+
+    search_2d(
+        py,
+        &heatmap,
+        &query,
+        (&start, &end),
+        simplify,
+        as_array,
+        order,
+    )
+}
+
+/// An iterator over the points of a path found by `iter_path_2d`, yielding `(x, y)` tuples one
+/// at a time. The path itself stays in Rust, so no Python list of the whole path is built.
+#[pyclass]
+struct PathIterator2D {
+    path: Vec<Pos2D>,
+    next: usize,
+    /// The total cost of the path.
+    #[pyo3(get)]
+    cost: u32,
+}
+
+#[pymethods]
+impl PathIterator2D {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<Pos2D> {
+        let point = self.path.get(self.next).copied()?;
+        self.next += 1;
+        Some(point)
+    }
+
+    /// The number of points not yielded yet, so `list()` can size its result up front.
+    fn __length_hint__(&self) -> usize {
+        self.path.len() - self.next
+    }
+}
+
+/// Find a path like `find_path_2d`, but return it as an iterator over its points, to stream very
+/// long paths without building a list of the whole path at once.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `start` - Start position as (x, y) tuple
+/// * `end` - End position as (x, y) tuple
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", "dijkstra_bi", "fringe", "jps", or "theta"
+/// * `impassable`, `connectivity`, `wrap`, `mask`, `weight`, `heuristic`, `timeout_ms`, `max_cost` -
+///   Optional: As for `find_path_2d`
+///
+/// # Returns
+/// * `Optional[PathIterator2D]` - An iterator yielding the `(x, y)` points of the path from the start to
+///   the end, or None if no path was found. Its `cost` attribute holds the total cost of the path.
+///
+/// The GIL is released while searching. The arrays are borrowed, not copied: they must not be
+/// modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, start, end, algorithm, *, impassable=None, connectivity=8, wrap="none", mask=None, weight=1.0, heuristic="manhattan", timeout_ms=None, max_cost=None))]
+#[allow(clippy::too_many_arguments)]
+fn iter_path_2d<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    start: Pos2D,
+    end: Pos2D,
+    algorithm: &str,
+    impassable: Option<u16>,
+    connectivity: u8,
+    wrap: &str,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+    weight: f64,
+    heuristic: &str,
+    timeout_ms: Option<u64>,
+    max_cost: Option<u32>,
+) -> PyResult<Option<PathIterator2D>> {
+    let heatmap = Heatmap2D::extract(array)?;
+    let algorithm = Algorithm2D::parse(algorithm)?;
+    check_search_params(weight, None)?;
+    let heuristic = parse_heuristic(heuristic)?;
+
+    let mask = merge_heatmap_mask(array, &heatmap, mask)?;
+    let mask = mask.as_ref().map(|mask| mask.as_array());
+
+    let graph = Graph2D {
+        impassable,
+        wall_value: None,
+        block_above: None,
+        block_below: None,
+        connectivity: parse_connectivity(connectivity)?,
+        neighbours: None,
+        reach: 1,
+        wrap: parse_wrap(wrap)?,
+        mask,
+        cost_table: None,
+        speed: None,
+        turn_penalty: 0,
+        direction_weights: None,
+        no_corner_cutting: false,
+        jps_support: None,
+    };
+
+    let (starts, ends) = ([start], [end]);
+    check_endpoints(&heatmap, &graph, &starts, &ends)?;
+
+    let query = Query2D {
+        starts: &starts,
+        ends: &ends,
+        algorithm,
+        weight,
+        heuristic,
+        tie_break: TieBreak::None,
+        block_threshold: None,
+        timeout_ms,
+        max_nodes: None,
+        max_cost,
+        drop_collinear: false,
+        return_segment_costs: false,
+        return_stats: false,
+        return_visited: false,
+        return_length: false,
+        return_border_flag: false,
+        normalize_cost: false,
+        verify_optimal: false,
+        return_partial: false,
+        graph: &graph,
+    };
+
+    let result = match &heatmap {
+        Heatmap2D::U8(array) => {
+            run_query_2d(py, array.as_array(), impassable_u8(impassable)?, &query)?
+        }
+        Heatmap2D::U16(array) => run_query_2d(py, array.as_array(), impassable, &query)?,
+    };
+    Ok(result.map(|result| PathIterator2D {
+        path: result.path,
+        next: 0,
+        cost: result.cost,
+    }))
+}
+
+/// Run a 2D query for the cost of its cheapest path only, with the GIL released.
+fn run_cost_query_2d<T: HeatmapValue>(
+    py: Python<'_>,
+    array: ArrayView2<T>,
+    impassable: Option<T>,
+    query: &Query2D,
+) -> PyResult<Option<u32>> {
+    let graph = query.graph;
+    let deadline = query
+        .timeout_ms
+        .map(|timeout| SearchDeadline::after(Duration::from_millis(timeout)));
+    let node_limit = query.max_nodes.map(NodeLimit::new);
+
+    let options = SearchOptions2D {
+        impassable,
+        block_above: graph.block_above,
+        block_below: graph.block_below,
+        connectivity: graph.connectivity,
+        wrap: graph.wrap,
+        mask: graph.mask,
+        deadline: deadline.as_ref(),
+        node_limit: node_limit.as_ref(),
+        max_cost: query.max_cost,
+        ..Default::default()
+    };
+
+    let pathfinder = make_pathfinder_2d(
+        query.algorithm,
+        AStar2D {
+            weight: query.weight,
+            heuristic: query.heuristic,
+        },
+        None,
+        || JumpPointSearch2D::supports(array, &options),
+    )?;
+    let cost = py.detach(|| pathfinder.path_cost(array, query.starts[0], query.ends[0], &options));
+
+    if let Some(timeout) = query.timeout_ms
+        && deadline.as_ref().is_some_and(SearchDeadline::has_expired)
+    {
+        return Err(timeout_error(timeout));
+    }
+    if let Some(max_nodes) = query.max_nodes
+        && node_limit.as_ref().is_some_and(NodeLimit::was_exceeded)
+    {
+        return Err(node_limit_error(max_nodes));
+    }
+
+    Ok(cost)
+}
+
+/// Find the cost of the cheapest path between two positions in a 2D heatmap, without the path
+/// itself, e.g. to score many candidate start and end pairs.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `start` - Start position as (x, y) tuple
+/// * `end` - End position as (x, y) tuple
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", "dijkstra_bi", "fringe", "jps", or "theta"
+/// * `impassable`, `wall_value`, `block_above`, `block_below`, `connectivity`, `wrap`, `mask`, `weight`,
+///   `heuristic`, `timeout_ms`, `max_nodes`, `max_cost` - Optional: As for `find_path_2d`
+///
+/// # Returns
+/// * `Optional[int]` - The cost `find_path_2d` would return with the same arguments, or None if no
+///   path was found.
+///
+/// "dijkstra", and "astar" when its heuristic cannot overestimate ("zero", or "octile" with a
+/// weight of at most 1), run a search that only keeps the best cost of each cell, without
+/// tracking parents or building the path, which is faster and takes less memory on large maps.
+/// The other algorithms find the path and drop it. The GIL is released while searching. The
+/// arrays are borrowed, not copied: they must not be modified by another thread until the call
+/// returns.
+#[pyfunction]
+#[pyo3(signature = (array, start, end, algorithm, *, impassable=None, wall_value=None, block_above=None, block_below=None, connectivity=8, wrap="none", mask=None, weight=1.0, heuristic="manhattan", timeout_ms=None, max_nodes=None, max_cost=None))]
+#[allow(clippy::too_many_arguments)]
+fn path_cost_2d<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    start: Pos2D,
+    end: Pos2D,
+    algorithm: &str,
+    impassable: Option<u16>,
+    wall_value: Option<u16>,
+    block_above: Option<u32>,
+    block_below: Option<u32>,
+    connectivity: u8,
+    wrap: &str,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+    weight: f64,
+    heuristic: &str,
+    timeout_ms: Option<u64>,
+    max_nodes: Option<usize>,
+    max_cost: Option<u32>,
+) -> PyResult<Option<u32>> {
+    let heatmap = Heatmap2D::extract(array)?;
+    let algorithm = Algorithm2D::parse(algorithm)?;
+    check_search_params(weight, None)?;
+    let heuristic = parse_heuristic(heuristic)?;
+    check_block_range(block_above, block_below)?;
+
+    let mask = merge_heatmap_mask(array, &heatmap, mask)?;
+    let graph = Graph2D {
+        impassable: merge_wall_value(impassable, wall_value)?,
+        wall_value,
+        block_above,
+        block_below,
+        connectivity: parse_connectivity(connectivity)?,
+        neighbours: None,
+        reach: 1,
+        wrap: parse_wrap(wrap)?,
+        mask: mask.as_ref().map(|mask| mask.as_array()),
+        cost_table: None,
+        speed: None,
+        turn_penalty: 0,
+        direction_weights: None,
+        no_corner_cutting: false,
+        jps_support: None,
+    };
+
+    let (starts, ends) = ([start], [end]);
+    check_endpoints(&heatmap, &graph, &starts, &ends)?;
+
+    let query = Query2D {
+        starts: &starts,
+        ends: &ends,
+        algorithm,
+        weight,
+        heuristic,
+        tie_break: TieBreak::None,
+        block_threshold: None,
+        timeout_ms,
+        max_nodes,
+        max_cost,
+        drop_collinear: false,
+        return_segment_costs: false,
+        return_stats: false,
+        return_visited: false,
+        return_length: false,
+        return_border_flag: false,
+        normalize_cost: false,
+        verify_optimal: false,
+        return_partial: false,
+        graph: &graph,
+    };
+
+    match &heatmap {
+        Heatmap2D::U8(array) => run_cost_query_2d(
+            py,
+            array.as_array(),
+            impassable_u8(graph.impassable)?,
+            &query,
+        ),
+        Heatmap2D::U16(array) => run_cost_query_2d(py, array.as_array(), graph.impassable, &query),
+    }
+}
+
+/// Check reachability over a heatmap of any supported dtype, with the GIL released.
+fn reachable_2d<T: HeatmapValue>(
+    py: Python<'_>,
+    array: ArrayView2<T>,
+    (start, end): (Pos2D, Pos2D),
+    impassable: Option<T>,
+    graph: &Graph2D,
+) -> bool {
+    let options = SearchOptions2D {
+        impassable,
+        block_above: graph.block_above,
+        block_below: graph.block_below,
+        connectivity: graph.connectivity,
+        neighbours: graph.neighbours,
+        wrap: graph.wrap,
+        mask: graph.mask,
+        speed: graph.speed,
+        no_corner_cutting: graph.no_corner_cutting,
+        ..Default::default()
+    };
+
+    py.detach(|| is_reachable(array, start, end, &options))
+}
+
+/// Check whether `end` can be reached from `start` in a 2D heatmap, without finding a path.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `start` - Start position as (x, y) tuple
+/// * `end` - End position as (x, y) tuple
+/// * `impassable`, `wall_value`, `block_above`, `block_below`, `connectivity`, `wrap`, `mask`,
+///   `no_corner_cutting` - Optional: As for `find_path_2d`
+///
+/// # Returns
+/// * `bool` - True if some path from `start` to `end` exists, i.e. `find_path_2d` with the same
+///   arguments would not return None.
+///
+/// A breadth-first search ignores the step costs and stops as soon as `end` is touched, so no
+/// path is built. The GIL is released while searching. The arrays are borrowed, not copied: they
+/// must not be modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, start, end, *, impassable=None, wall_value=None, block_above=None, block_below=None, connectivity=8, wrap="none", mask=None, no_corner_cutting=false))]
+#[allow(clippy::too_many_arguments)]
+fn is_reachable_2d<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    start: Pos2D,
+    end: Pos2D,
+    impassable: Option<u16>,
+    wall_value: Option<u16>,
+    block_above: Option<u32>,
+    block_below: Option<u32>,
+    connectivity: u8,
+    wrap: &str,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+    no_corner_cutting: bool,
+) -> PyResult<bool> {
+    let heatmap = Heatmap2D::extract(array)?;
+    check_block_range(block_above, block_below)?;
+
+    let mask = merge_heatmap_mask(array, &heatmap, mask)?;
+    let graph = Graph2D {
+        impassable: merge_wall_value(impassable, wall_value)?,
+        wall_value,
+        block_above,
+        block_below,
+        connectivity: parse_connectivity(connectivity)?,
+        neighbours: None,
+        reach: 1,
+        wrap: parse_wrap(wrap)?,
+        mask: mask.as_ref().map(|mask| mask.as_array()),
+        cost_table: None,
+        speed: None,
+        turn_penalty: 0,
+        direction_weights: None,
+        no_corner_cutting,
+        jps_support: None,
+    };
+    check_endpoints(&heatmap, &graph, &[start], &[end])?;
+
+    Ok(match &heatmap {
+        Heatmap2D::U8(array) => {
+            let impassable = impassable_u8(graph.impassable)?;
+            reachable_2d(py, array.as_array(), (start, end), impassable, &graph)
+        }
+        Heatmap2D::U16(array) => {
+            reachable_2d(py, array.as_array(), (start, end), graph.impassable, &graph)
+        }
+    })
+}
+
+/// A heatmap held by a `PathFinder2D`, in one of the supported dtypes.
+enum HeatmapHandle {
+    U8(Py<PyArray2<u8>>),
+    U16(Py<PyArray2<u16>>),
+}
+
+/// A 2D heatmap prepared once for many path queries.
+///
+/// The constructor takes the graph arguments of `find_path_2d`, validates them, and evaluates
+/// `cost_fn`, `invert`, `cost_power`, and `clamp_cost` into its lookup table, so that each
+/// `find` call only runs the search. Whether the heatmap suits "jps" is checked on the first jps
+/// query and remembered. The search frontier itself depends on the start and end, so every algorithm,
+/// "dijkstra_bi" included, still builds it per query.
+///
+/// The array, mask, and speed map are copied on construction, so the caller may modify or delete
+/// theirs while the `PathFinder2D` is in use: queries always search the heatmap as it was then.
+#[pyclass(frozen)]
+struct PathFinder2D {
+    array: HeatmapHandle,
+    impassable: Option<u16>,
+    wall_value: Option<u16>,
+    block_above: Option<u32>,
+    block_below: Option<u32>,
+    connectivity: Connectivity,
+    neighbours: Option<Vec<(i32, i32)>>,
+    reach: u32,
+    wrap: Wrap,
+    mask: Option<Py<PyArray2<bool>>>,
+    cost_table: Option<Vec<u32>>,
+    speed: Option<Py<PyArray2<u8>>>,
+    turn_penalty: u32,
+    direction_weights: Option<DirectionWeights>,
+    no_corner_cutting: bool,
+    jps_support: OnceLock<bool>,
+}
+
+#[pymethods]
+impl PathFinder2D {
+    /// Prepare `array` for path queries. The arguments are those of `find_path_2d`.
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (array, *, impassable=None, wall_value=None, free_value=None, free_cost=0, block_above=None, block_below=None, connectivity=8, neighbors=None, reach=1, wrap="none", mask=None, speed=None, cost_fn=None, invert=false, cost_power=1.0, clamp_cost=None, turn_penalty=0, dir_weights=None, no_corner_cutting=false))]
+    fn new(
+        array: &Bound<'_, PyAny>,
+        impassable: Option<u16>,
+        wall_value: Option<u16>,
+        free_value: Option<u16>,
+        free_cost: u32,
+        block_above: Option<u32>,
+        block_below: Option<u32>,
+        connectivity: u8,
+        neighbors: Option<Vec<(i32, i32)>>,
+        reach: u32,
+        wrap: &str,
+        mask: Option<PyReadonlyArray2<'_, bool>>,
+        speed: Option<PyReadonlyArray2<'_, u8>>,
+        cost_fn: Option<Bound<'_, PyAny>>,
+        invert: bool,
+        cost_power: f64,
+        clamp_cost: Option<u32>,
+        turn_penalty: u32,
+        dir_weights: Option<HashMap<String, f64>>,
+        no_corner_cutting: bool,
+    ) -> PyResult<Self> {
+        let heatmap = Heatmap2D::extract(array)?;
+        let mask = merge_heatmap_mask(array, &heatmap, mask)?;
+        check_block_range(block_above, block_below)?;
+        check_speed_shape(speed.as_ref().map(|speed| speed.as_array()), &heatmap)?;
+        let neighbours = parse_neighbours(neighbors)?;
+        let reach = check_reach_2d(reach, neighbours.as_deref())?;
+
+        let impassable = merge_wall_value(impassable, wall_value)?;
+        // Copies that only this finder references, so no other code can write to them
+        let py = array.py();
+        let array = match &heatmap {
+            Heatmap2D::U8(array) => {
+                impassable_u8(impassable)?;
+                HeatmapHandle::U8(PyArray2::from_owned_array(py, array.to_owned_array()).unbind())
+            }
+            Heatmap2D::U16(array) => {
+                HeatmapHandle::U16(PyArray2::from_owned_array(py, array.to_owned_array()).unbind())
+            }
+        };
+
+        Ok(PathFinder2D {
+            impassable,
+            wall_value,
+            block_above,
+            block_below,
+            connectivity: parse_connectivity(connectivity)?,
+            neighbours,
+            reach,
+            wrap: parse_wrap(wrap)?,
+            mask: mask.map(|mask| PyArray2::from_owned_array(py, mask.to_owned_array()).unbind()),
+            cost_table: heatmap.cost_table(
+                cost_fn.as_ref(),
+                invert,
+                cost_power,
+                clamp_cost,
+                free_value.map(|free_value| (free_value, free_cost)),
+            )?,
+            speed: speed
+                .map(|speed| PyArray2::from_owned_array(py, speed.to_owned_array()).unbind()),
+            turn_penalty,
+            direction_weights: parse_direction_weights(dir_weights)?,
+            no_corner_cutting,
+            jps_support: OnceLock::new(),
+            array,
+        })
+    }
+
+    /// Find a path from `start` to `end` using the specified algorithm.
+    ///
+    /// The arguments and the returned tuple are those of `find_path_2d`.
+    #[pyo3(signature = (start, end, algorithm, *, weight=1.0, heuristic="manhattan", prefer="none", block_threshold=None, simplify=None, drop_collinear=false, timeout_ms=None, max_nodes=None, max_cost=None, as_array=false, return_segment_costs=false, return_stats=false, return_visited=false, return_length=false, return_border_flag=false, normalize_cost=false, return_partial=false, verify_optimal=false, order="xy"))]
+    #[allow(clippy::too_many_arguments)]
+    fn find<'py>(
+        &self,
+        py: Python<'py>,
+        start: Points2D,
+        end: Points2D,
+        algorithm: &str,
+        weight: f64,
+        heuristic: &str,
+        prefer: &str,
+        block_threshold: Option<u32>,
+        simplify: Option<f64>,
+        drop_collinear: bool,
+        timeout_ms: Option<u64>,
+        max_nodes: Option<usize>,
+        max_cost: Option<u32>,
+        as_array: bool,
+        return_segment_costs: bool,
+        return_stats: bool,
+        return_visited: bool,
+        return_length: bool,
+        return_border_flag: bool,
+        normalize_cost: bool,
+        return_partial: bool,
+        verify_optimal: bool,
+        order: &str,
+    ) -> PyResult<Option<Bound<'py, PyTuple>>> {
+        let algorithm = Algorithm2D::parse(algorithm)?;
+        check_search_params(weight, simplify)?;
+        let heuristic = parse_heuristic(heuristic)?;
+        let tie_break = parse_tie_break(prefer)?;
+
+        let heatmap = match &self.array {
+            HeatmapHandle::U8(array) => Heatmap2D::U8(array.bind(py).try_readonly()?),
+            HeatmapHandle::U16(array) => Heatmap2D::U16(array.bind(py).try_readonly()?),
+        };
+        let mask = self
+            .mask
+            .as_ref()
+            .map(|mask| mask.bind(py).try_readonly())
+            .transpose()?;
+        let speed = self
+            .speed
+            .as_ref()
+            .map(|speed| speed.bind(py).try_readonly())
+            .transpose()?;
+
+        let graph = Graph2D {
+            impassable: self.impassable,
+            wall_value: self.wall_value,
+            block_above: self.block_above,
+            block_below: self.block_below,
+            connectivity: self.connectivity,
+            neighbours: self.neighbours.as_deref(),
+            reach: self.reach,
+            wrap: self.wrap,
+            mask: mask.as_ref().map(|mask| mask.as_array()),
+            cost_table: self.cost_table.as_deref(),
+            speed: speed.as_ref().map(|speed| speed.as_array()),
+            turn_penalty: self.turn_penalty,
+            direction_weights: self.direction_weights,
+            no_corner_cutting: self.no_corner_cutting,
+            jps_support: Some(&self.jps_support),
+        };
+
+        let order = Order2D::parse(order)?;
+        let (start, end) = (start.into_xy(order), end.into_xy(order));
+        let starts = start.as_slice();
+        let ends = end.as_slice();
+        check_endpoints(&heatmap, &graph, starts, ends)?;
+
+        let query = Query2D {
+            starts,
+            ends,
+            algorithm,
+            weight,
+            heuristic,
+            tie_break,
+            block_threshold,
+            timeout_ms,
+            max_nodes,
+            max_cost,
+            drop_collinear,
+            return_segment_costs,
+            return_stats,
+            return_visited,
+            return_length,
+            return_border_flag,
+            normalize_cost,
+            verify_optimal,
+            return_partial,
+            graph: &graph,
+        };
+
+        search_2d(
+            py,
+            &heatmap,
+            &query,
+            (&start, &end),
+            simplify,
+            as_array,
+            order,
+        )
+    }
+}
+
+/// The D* Lite state of a `DStarLite2D`, in the dtype of its heatmap.
+enum DStarLiteState {
+    U8(image_pathfinding::DStarLite2D<u8>),
+    U16(image_pathfinding::DStarLite2D<u16>),
+}
+
+/// An incremental pathfinder for heatmaps that change between queries, using D* Lite.
+///
+/// `initialize` copies the heatmap and sets the start and end. `update_cell` changes single
+/// cells of that copy, and `replan` returns the cheapest path for the heatmap as it is now,
+/// repairing only the part of the previous search that the changes invalidated. The start may
+/// move between calls to `replan`, e.g. as an agent walks along the path.
+///
+/// Every `replan` returns an optimal path for the current heatmap: its cost is the one
+/// `find_path_2d` with "dijkstra" would return for the same heatmap.
+#[pyclass]
+struct DStarLite2D {
+    impassable: Option<u16>,
+    connectivity: Connectivity,
+    wrap: Wrap,
+    state: Option<DStarLiteState>,
+}
+
+impl DStarLite2D {
+    fn state(&mut self) -> PyResult<&mut DStarLiteState> {
+        self.state
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("initialize() must be called first"))
+    }
+}
+
+#[pymethods]
+impl DStarLite2D {
+    /// Create a replanner. `impassable`, `connectivity`, and `wrap` are those of `find_path_2d`.
+    #[new]
+    #[pyo3(signature = (*, impassable=None, connectivity=8, wrap="none"))]
+    fn new(impassable: Option<u16>, connectivity: u8, wrap: &str) -> PyResult<Self> {
+        Ok(DStarLite2D {
+            impassable,
+            connectivity: parse_connectivity(connectivity)?,
+            wrap: parse_wrap(wrap)?,
+            state: None,
+        })
+    }
+
+    /// Copy `array` and plan from `start` to `end`, discarding any previous state.
+    /// No search runs until `replan` is called.
+    fn initialize(&mut self, array: &Bound<'_, PyAny>, start: Pos2D, end: Pos2D) -> PyResult<()> {
+        let heatmap = Heatmap2D::extract(array)?;
+        let (width, height) = heatmap.dim();
+        ensure_in_bounds("start", &[start.0, start.1], &[width, height])?;
+        ensure_in_bounds("end", &[end.0, end.1], &[width, height])?;
+
+        let (connectivity, wrap) = (self.connectivity, self.wrap);
+        self.state = Some(match heatmap {
+            Heatmap2D::U8(array) => DStarLiteState::U8(image_pathfinding::DStarLite2D::new(
+                array.as_array().to_owned(),
+                start,
+                end,
+                impassable_u8(self.impassable)?,
+                connectivity,
+                wrap,
+            )),
+            Heatmap2D::U16(array) => DStarLiteState::U16(image_pathfinding::DStarLite2D::new(
+                array.as_array().to_owned(),
+                start,
+                end,
+                self.impassable,
+                connectivity,
+                wrap,
+            )),
+        });
+        Ok(())
+    }
+
+    /// Set the value of the cell at `(x, y)`. The path is repaired on the next `replan`.
+    fn update_cell(&mut self, x: u32, y: u32, new_value: u16) -> PyResult<()> {
+        match self.state()? {
+            DStarLiteState::U8(planner) => {
+                let (width, height) = planner.array().dim();
+                ensure_in_bounds("cell", &[x, y], &[width, height])?;
+                let value = u8::try_from(new_value).map_err(|_| {
+                    PyValueError::new_err(format!(
+                        "Value {} does not fit the uint8 heatmap",
+                        new_value
+                    ))
+                })?;
+                planner.update_cell((x, y), value);
+            }
+            DStarLiteState::U16(planner) => {
+                let (width, height) = planner.array().dim();
+                ensure_in_bounds("cell", &[x, y], &[width, height])?;
+                planner.update_cell((x, y), new_value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the cheapest path from the start, or from `start` if given, to the end.
+    ///
+    /// Returns `(path, cost)` like `find_path_2d`, or `None` if the end cannot be reached.
+    /// The GIL is released while searching.
+    #[pyo3(signature = (start=None, *, as_array=false))]
+    fn replan<'py>(
+        &mut self,
+        py: Python<'py>,
+        start: Option<Pos2D>,
+        as_array: bool,
+    ) -> PyResult<Option<(Path2D<'py>, u32)>> {
+        let result = match self.state()? {
+            DStarLiteState::U8(planner) => replan_d_star_lite(py, planner, start)?,
+            DStarLiteState::U16(planner) => replan_d_star_lite(py, planner, start)?,
+        };
+        Ok(result.map(|(path, cost)| (Path2D::new(py, path, as_array), cost)))
+    }
+
+    /// The number of cells the last `replan` expanded, which shows how much of the previous
+    /// search it could reuse.
+    #[getter]
+    fn nodes_expanded(&self) -> usize {
+        match &self.state {
+            Some(DStarLiteState::U8(planner)) => planner.nodes_expanded(),
+            Some(DStarLiteState::U16(planner)) => planner.nodes_expanded(),
+            None => 0,
+        }
+    }
+}
+
+/// Move the start of a D* Lite planner if `start` is given, then replan with the GIL released.
+fn replan_d_star_lite<T: HeatmapValue>(
+    py: Python<'_>,
+    planner: &mut image_pathfinding::DStarLite2D<T>,
+    start: Option<Pos2D>,
+) -> PyResult<Option<(Vec<Pos2D>, u32)>> {
+    if let Some(start) = start {
+        let (width, height) = planner.array().dim();
+        ensure_in_bounds("start", &[start.0, start.1], &[width, height])?;
+        planner.set_start(start);
+    }
+    Ok(py.detach(|| planner.replan()))
+}
+
+/// Find up to `k` cheap, distinct paths between two points, in increasing cost order,
+/// using a variant of Yen's algorithm over the chosen pathfinder.
+fn k_paths_2d<T: HeatmapValue>(
+    py: Python<'_>,
+    array: ArrayView2<T>,
+    (start, end): (Pos2D, Pos2D),
+    k: usize,
+    algorithm: Algorithm2D,
+    impassable: Option<T>,
+    max_shared_fraction: f64,
+) -> PyResult<Vec<(Vec<Pos2D>, u32)>> {
+    let options = SearchOptions2D {
+        impassable,
+        ..Default::default()
+    };
+
+    // Any-angle paths are waypoints rather than adjacent cells, which the spur searches rely on
+    if algorithm == Algorithm2D::Theta {
+        return Err(PyValueError::new_err(
+            "find_k_paths_2d does not support theta, as its paths skip over cells",
+        ));
+    }
+
+    let pathfinder = make_pathfinder_2d(algorithm, AStar2D::default(), None, || {
+        JumpPointSearch2D::supports(array, &options)
+    })?;
+
+    Ok(py.detach(|| pathfinder.find_k_paths(array, start, end, k, max_shared_fraction, &options)))
+}
+
+/// Find up to `k` alternative paths between two points in a 2D heatmap.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `start` - Start position as (x, y) tuple
+/// * `end` - End position as (x, y) tuple
+/// * `k` - The maximum number of paths to return
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", "dijkstra_bi", "fringe", or "jps"
+/// * `impassable` - Optional: A value that, if provided, will be used to filter out neighbours that have this value.
+/// * `max_shared_fraction` - Optional: The largest fraction of a path's edges it may share with any
+///   cheaper returned path (default: 0.5)
+///
+/// # Returns
+/// * `List[Tuple[List[Tuple[int, int]], int]]` - Up to `k` `(path, cost)` pairs in increasing cost order.
+///   Fewer are returned if not enough sufficiently distinct paths exist.
+///
+/// The GIL is released while searching. The array is borrowed, not copied: it must not be
+/// modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, start, end, k, algorithm, *, impassable=None, max_shared_fraction=0.5))]
+#[allow(clippy::too_many_arguments)]
+fn find_k_paths_2d(
+    py: Python<'_>,
+    array: &Bound<'_, PyAny>,
+    start: Pos2D,
+    end: Pos2D,
+    k: usize,
+    algorithm: &str,
+    impassable: Option<u16>,
+    max_shared_fraction: f64,
+) -> PyResult<Vec<(Vec<Pos2D>, u32)>> {
+    let heatmap = Heatmap2D::extract(array)?;
+    let algorithm = Algorithm2D::parse(algorithm)?;
+
+    let (width, height) = heatmap.dim();
+    ensure_in_bounds("start", &[start.0, start.1], &[width, height])?;
+    ensure_in_bounds("end", &[end.0, end.1], &[width, height])?;
+
+    if !(0.0..=1.0).contains(&max_shared_fraction) {
+        return Err(PyValueError::new_err(format!(
+            "max_shared_fraction must be between 0 and 1, got {}",
+            max_shared_fraction
+        )));
+    }
+
+    let points = (start, end);
+    match &heatmap {
+        Heatmap2D::U8(array) => k_paths_2d(
+            py,
+            array.as_array(),
+            points,
+            k,
+            algorithm,
+            impassable_u8(impassable)?,
+            max_shared_fraction,
+        ),
+        Heatmap2D::U16(array) => k_paths_2d(
+            py,
+            array.as_array(),
+            points,
+            k,
+            algorithm,
+            impassable,
+            max_shared_fraction,
+        ),
+    }
+}
+
+/// Parse which parts of the avoided path a disjoint path must not share: "nodes" or "edges", ignoring case.
+fn parse_disjoint(disjoint: &str) -> PyResult<Disjoint> {
+    match disjoint.to_lowercase().as_str() {
+        "nodes" => Ok(Disjoint::Nodes),
+        "edges" => Ok(Disjoint::Edges),
+        _ => Err(PyValueError::new_err(format!(
+            "Unsupported disjoint: {}. Supported: nodes, edges",
+            disjoint
+        ))),
+    }
+}
+
+/// Find a path avoiding an earlier one over a heatmap of any supported dtype.
+fn disjoint_path_2d<T: HeatmapValue>(
+    py: Python<'_>,
+    array: ArrayView2<T>,
+    (start, end): (Pos2D, Pos2D),
+    avoid_path: &[Pos2D],
+    algorithm: Algorithm2D,
+    disjoint: Disjoint,
+    options: SearchOptions2D<T>,
+) -> PyResult<Option<(Vec<Pos2D>, u32)>> {
+    // Jump point search moves past cells without taking single steps, so it cannot skip edges
+    if algorithm == Algorithm2D::Jps && disjoint == Disjoint::Edges {
+        return Err(PyValueError::new_err(
+            "find_disjoint_path_2d does not support jps with disjoint=\"edges\", use astar instead",
+        ));
+    }
+
+    let pathfinder = make_pathfinder_2d(algorithm, AStar2D::default(), None, || {
+        JumpPointSearch2D::supports(array, &options)
+    })?;
+
+    Ok(py.detach(|| {
+        pathfinder.find_disjoint_path(array, start, end, avoid_path, disjoint, &options)
+    }))
+}
+
+/// Find the cheapest path between two points that shares no cell, or no step, with an earlier path,
+/// e.g. a backup route.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `start` - Start position as (x, y) tuple
+/// * `end` - End position as (x, y) tuple
+/// * `avoid_path` - The path to avoid, as a list of adjacent (x, y) cells, e.g. as returned by `find_path_2d`
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", "dijkstra_bi", "fringe", "jps", or "theta"
+/// * `disjoint` - Optional: "nodes" to block every cell of `avoid_path` except `start` and `end`, or
+///   "edges" to only block its steps, in both directions, so cells may be crossed again (default: "nodes").
+///   Node-disjoint paths are also edge-disjoint. "jps" only supports "nodes", and searches with
+///   "astar" and the octile heuristic instead, as its jumps cannot avoid single steps.
+/// * `impassable` - Optional: A value that, if provided, will be used to filter out neighbours that have this value.
+/// * `connectivity` - Optional: 4 for cardinal moves only, or 8 to also allow diagonal moves (default: 8)
+/// * `mask` - Optional: A 2D boolean NumPy array with the same shape as `array`, where `True` marks a blocked cell
+///
+/// # Returns
+/// * `Optional[Tuple[List[Tuple[int, int]], int]]` - The path found and total cost, or None if no
+///   disjoint path exists.
+///
+/// The GIL is released while searching. The arrays are borrowed, not copied: they must not be
+/// modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, start, end, avoid_path, algorithm, *, disjoint="nodes", impassable=None, connectivity=8, mask=None))]
+#[allow(clippy::too_many_arguments)]
+fn find_disjoint_path_2d<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    start: Pos2D,
+    end: Pos2D,
+    avoid_path: Vec<Pos2D>,
+    algorithm: &str,
+    disjoint: &str,
+    impassable: Option<u16>,
+    connectivity: u8,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+) -> PyResult<Option<(Vec<Pos2D>, u32)>> {
+    let heatmap = Heatmap2D::extract(array)?;
+    let algorithm = Algorithm2D::parse(algorithm)?;
+    let disjoint = parse_disjoint(disjoint)?;
+    let connectivity = parse_connectivity(connectivity)?;
+
+    let (width, height) = heatmap.dim();
+    ensure_in_bounds("start", &[start.0, start.1], &[width, height])?;
+    ensure_in_bounds("end", &[end.0, end.1], &[width, height])?;
+    for &(x, y) in &avoid_path {
+        ensure_in_bounds("avoid_path point", &[x, y], &[width, height])?;
+    }
+    // Steps are only blocked between adjacent cells, so any-angle waypoints would block nothing
+    let is_adjacent = |(x1, y1): Pos2D, (x2, y2): Pos2D| x1.abs_diff(x2).max(y1.abs_diff(y2)) == 1;
+    if let Some(step) = avoid_path
+        .windows(2)
+        .find(|step| !is_adjacent(step[0], step[1]))
+    {
+        return Err(PyValueError::new_err(format!(
+            "avoid_path must be made of adjacent cells, but {:?} and {:?} are not",
+            step[0], step[1]
+        )));
+    }
+
+    let mask = merge_heatmap_mask(array, &heatmap, mask)?;
+    let mask = mask.as_ref().map(|mask| mask.as_array());
+
+    let points = (start, end);
+    match &heatmap {
+        Heatmap2D::U8(array) => {
+            let options = SearchOptions2D {
+                impassable: impassable_u8(impassable)?,
+                connectivity,
+                mask,
+                ..Default::default()
+            };
+            disjoint_path_2d(
+                py,
+                array.as_array(),
+                points,
+                &avoid_path,
+                algorithm,
+                disjoint,
+                options,
+            )
+        }
+        Heatmap2D::U16(array) => {
+            let options = SearchOptions2D {
+                impassable,
+                connectivity,
+                mask,
+                ..Default::default()
+            };
+            disjoint_path_2d(
+                py,
+                array.as_array(),
+                points,
+                &avoid_path,
+                algorithm,
+                disjoint,
+                options,
+            )
+        }
+    }
+}
+
+/// Chain searches through the waypoints over a heatmap of any supported dtype.
+fn waypoints_path_2d<T: HeatmapValue>(
+    py: Python<'_>,
+    array: ArrayView2<T>,
+    points: &[Pos2D],
+    algorithm: Algorithm2D,
+    impassable: Option<T>,
+) -> PyResult<(Vec<Pos2D>, u32)> {
+    let options = SearchOptions2D {
+        impassable,
+        ..Default::default()
+    };
+
+    let pathfinder = make_pathfinder_2d(algorithm, AStar2D::default(), None, || {
+        JumpPointSearch2D::supports(array, &options)
+    })?;
+
+    py.detach(|| pathfinder.find_path_through_waypoints(array, points, &options))
+        .map_err(|leg| {
+            PyValueError::new_err(format!(
+                "Leg {} from {:?} to {:?} is unreachable",
+                leg,
+                points[leg],
+                points[leg + 1]
+            ))
+        })
+}
+
+/// Find a path through a sequence of waypoints in a 2D heatmap, visiting them in order.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `points` - The positions to visit as a list of (x, y) tuples, from the start to the end
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", "dijkstra_bi", "fringe", "jps", or "theta"
+/// * `impassable` - Optional: A value that, if provided, will be used to filter out neighbours that have this value.
+///
+/// # Returns
+/// * `Tuple[List[Tuple[int, int]], int]` - The combined path and the summed cost of every leg.
+///   Points joining two legs appear once.
+///
+/// Raises `ValueError` naming the leg if one of them has no path, leg `i` going from
+/// `points[i]` to `points[i + 1]`.
+///
+/// The GIL is released while searching. The array is borrowed, not copied: it must not be
+/// modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, points, algorithm, *, impassable=None))]
+fn find_path_through_waypoints_2d(
+    py: Python<'_>,
+    array: &Bound<'_, PyAny>,
+    points: Vec<Pos2D>,
+    algorithm: &str,
+    impassable: Option<u16>,
+) -> PyResult<(Vec<Pos2D>, u32)> {
+    let heatmap = Heatmap2D::extract(array)?;
+    let algorithm = Algorithm2D::parse(algorithm)?;
+
+    if points.len() < 2 {
+        return Err(PyValueError::new_err(format!(
+            "At least two points are required, got {}",
+            points.len()
+        )));
+    }
+
+    let (width, height) = heatmap.dim();
+    for &(x, y) in &points {
+        ensure_in_bounds("waypoint", &[x, y], &[width, height])?;
+    }
+
+    match &heatmap {
+        Heatmap2D::U8(array) => waypoints_path_2d(
+            py,
+            array.as_array(),
+            &points,
+            algorithm,
+            impassable_u8(impassable)?,
+        ),
+        Heatmap2D::U16(array) => {
+            waypoints_path_2d(py, array.as_array(), &points, algorithm, impassable)
+        }
+    }
+}
+
+/// Compute the cost-to-go field from a source over a heatmap of any supported dtype.
+fn distance_field_2d<'py, T: HeatmapValue>(
+    py: Python<'py>,
+    array: ArrayView2<T>,
+    source: Pos2D,
+    impassable: Option<T>,
+) -> Bound<'py, PyArray2<u32>> {
+    let options = SearchOptions2D {
+        impassable,
+        ..Default::default()
+    };
+
+    let field = py.detach(|| Dijkstra2D {}.distance_field(array, source, &options));
+    PyArray2::from_owned_array(py, field)
+}
+
+/// Compute the minimum accumulated cost from a source to every cell of a 2D heatmap.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `source` - Source position as (x, y) tuple
+/// * `impassable` - Optional: A value that, if provided, will be used to filter out neighbours that have this value.
+///
+/// # Returns
+/// * `numpy.ndarray` - A uint32 array with the same shape as `array`, holding the cost of the cheapest
+///   path from `source` to each cell. The source holds 0 and unreachable cells hold `2**32 - 1`.
+///
+/// The GIL is released while searching. The array is borrowed, not copied: it must not be
+/// modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, source, *, impassable=None))]
+fn compute_distance_field_2d<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    source: Pos2D,
+    impassable: Option<u16>,
+) -> PyResult<Bound<'py, PyArray2<u32>>> {
+    let heatmap = Heatmap2D::extract(array)?;
 
-/// Find a path in a 2D heatmap using the specified algorithm.
+    let (width, height) = heatmap.dim();
+    ensure_in_bounds("source", &[source.0, source.1], &[width, height])?;
+
+    Ok(match &heatmap {
+        Heatmap2D::U8(array) => {
+            distance_field_2d(py, array.as_array(), source, impassable_u8(impassable)?)
+        }
+        Heatmap2D::U16(array) => distance_field_2d(py, array.as_array(), source, impassable),
+    })
+}
+
+/// Compute the flow field towards a goal over a heatmap of any supported dtype.
+fn flow_field_2d<'py, T: HeatmapValue>(
+    py: Python<'py>,
+    array: ArrayView2<T>,
+    goal: Pos2D,
+    impassable: Option<T>,
+) -> Bound<'py, PyArray2<u8>> {
+    let options = SearchOptions2D {
+        impassable,
+        connectivity: Connectivity::Four,
+        ..Default::default()
+    };
+
+    let field = py.detach(|| Dijkstra2D {}.flow_field(array, goal, &options));
+    PyArray2::from_owned_array(py, field)
+}
+
+/// Compute the direction of the cheapest step towards a goal for every cell of a 2D heatmap,
+/// so many agents heading to the same goal can share one precomputed field.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `goal` - Goal position as (x, y) tuple
+/// * `impassable` - Optional: A value that, if provided, will be used to filter out neighbours that have this value.
+///
+/// # Returns
+/// * `numpy.ndarray` - A uint8 array with the same shape as `array`, holding the direction of the
+///   first step of each cell's cheapest 4-connected path to `goal`: 0 is left (x - 1), 1 is right
+///   (x + 1), 2 is up (y - 1) and 3 is down (y + 1). The goal and cells that cannot reach it hold 255.
+///
+/// The GIL is released while searching. The array is borrowed, not copied: it must not be
+/// modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, goal, *, impassable=None))]
+fn compute_flow_field_2d<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    goal: Pos2D,
+    impassable: Option<u16>,
+) -> PyResult<Bound<'py, PyArray2<u8>>> {
+    let heatmap = Heatmap2D::extract(array)?;
+
+    let (width, height) = heatmap.dim();
+    ensure_in_bounds("goal", &[goal.0, goal.1], &[width, height])?;
+
+    Ok(match &heatmap {
+        Heatmap2D::U8(array) => {
+            flow_field_2d(py, array.as_array(), goal, impassable_u8(impassable)?)
+        }
+        Heatmap2D::U16(array) => flow_field_2d(py, array.as_array(), goal, impassable),
+    })
+}
+
+/// Search for the cheapest path to a goal region over a heatmap of any supported dtype,
+/// raising `TimeoutError` if the search runs out of time.
+fn region_path_2d<T: HeatmapValue>(
+    py: Python<'_>,
+    array: ArrayView2<T>,
+    start: Pos2D,
+    goal: ArrayView2<bool>,
+    options: SearchOptions2D<T>,
+    timeout_ms: Option<u64>,
+) -> PyResult<Option<(Vec<Pos2D>, u32)>> {
+    let deadline = timeout_ms.map(|timeout| SearchDeadline::after(Duration::from_millis(timeout)));
+    let options = SearchOptions2D {
+        deadline: deadline.as_ref(),
+        ..options
+    };
+
+    let result =
+        py.detach(|| Dijkstra2D {}.find_path_to_region(array, start, goal, &options, None));
+
+    if let Some(timeout) = timeout_ms
+        && deadline.as_ref().is_some_and(SearchDeadline::has_expired)
+    {
+        return Err(timeout_error(timeout));
+    }
+
+    Ok(result)
+}
+
+/// Find the cheapest path from a start position to any cell of a goal region, using Dijkstra's algorithm.
 ///
 /// # Arguments
-/// * `array` - A 2D NumPy array with dtype uint8 (shape: x, y) i.e. (width, height)
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
 /// * `start` - Start position as (x, y) tuple
-/// * `end` - End position as (x, y) tuple
-/// * `algorithm` - Algorithm to use: "astar", "dijkstra", or "fringe"
+/// * `goal_mask` - A 2D boolean NumPy array with the same shape as `array`, where `True` marks a goal cell
 /// * `impassable` - Optional: A value that, if provided, will be used to filter out neighbours that have this value.
+/// * `connectivity` - Optional: 4 for cardinal moves only, or 8 to also allow diagonal moves (default: 8)
+/// * `wrap` - Optional: Which borders wrap around to the opposite side: "none", "x", "y", or "xy" (default: "none")
+/// * `mask` - Optional: A 2D boolean NumPy array with the same shape as `array`, where `True` marks a blocked cell
+/// * `timeout_ms` - Optional: A time budget in milliseconds. If the search runs longer, it is aborted
+///   and `TimeoutError` is raised.
+/// * `max_cost` - Optional: An inclusive maximum path cost. None is returned if no goal can be reached within it.
+/// * `as_array` - Optional: If true, return the path as an `(N, 2)` uint32 NumPy array instead of a list (default: false)
 ///
 /// # Returns
-/// * `Optional[Tuple[List[Tuple[int, int]], int]]` - The path found and total cost, or None if no path was found
+/// * `Optional[Tuple[List[Tuple[int, int]], int, Tuple[int, int]]]` - The path to the nearest goal cell,
+///   its cost, and the goal cell that was reached, or None if no goal cell can be reached.
+///
+/// The search stops as soon as the cheapest goal cell is reached, which is cheaper than calling
+/// `find_path_2d` with every goal cell as a candidate end.
+///
+/// The GIL is released while searching. The arrays are borrowed, not copied: they must not be
+/// modified by another thread until the call returns.
 #[pyfunction]
-#[pyo3(signature = (array, start, end, algorithm, *, impassable=None))]
-fn find_path_2d(
-    array: PyReadonlyArray2<u8>,
-    start: (u32, u32),
-    end: (u32, u32),
-    algorithm: &str,
-    impassable: Option<u8>,
-) -> PyResult<Option<(Vec<(u32, u32)>, u32)>> {
-    // PyReadonlyArray2<u8> enforces 2D array with u8 dtype at the Python binding level.
-    // Arrays must be provided in (x, y) order, i.e. shape (width, height).
-    // Use the array view directly - no transposing.
-    let array_2d = array.as_array();
+#[pyo3(signature = (array, start, goal_mask, *, impassable=None, connectivity=8, wrap="none", mask=None, timeout_ms=None, max_cost=None, as_array=false))]
+#[allow(clippy::too_many_arguments)]
+fn find_path_to_region_2d<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    start: Pos2D,
+    goal_mask: PyReadonlyArray2<'py, bool>,
+    impassable: Option<u16>,
+    connectivity: u8,
+    wrap: &str,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+    timeout_ms: Option<u64>,
+    max_cost: Option<u32>,
+    as_array: bool,
+) -> PyResult<Option<(Path2D<'py>, u32, Pos2D)>> {
+    let heatmap = Heatmap2D::extract(array)?;
 
-    let (width, height) = array_2d.dim();
+    let (width, height) = heatmap.dim();
+    ensure_in_bounds("start", &[start.0, start.1], &[width, height])?;
 
-    let width = width as u32;
-    let height = height as u32;
+    let goal = goal_mask.as_array();
+    if goal.dim() != heatmap.dim() {
+        return Err(PyValueError::new_err(format!(
+            "Goal mask shape {:?} does not match array shape {:?}",
+            goal.dim(),
+            heatmap.dim()
+        )));
+    }
 
-    if start.0 >= width || start.1 >= height || end.0 >= width || end.1 >= height {
+    let mask = merge_heatmap_mask(array, &heatmap, mask)?;
+    let mask = mask.as_ref().map(|mask| mask.as_array());
+    if mask.is_some_and(|mask| mask[[start.0 as usize, start.1 as usize]]) {
         return Err(PyValueError::new_err(format!(
-            "Start or end position is out of bounds: start={:?}, end={:?}, shape={:?}",
-            start,
-            end,
-            (width, height)
+            "Start position {:?} lies on a masked (blocked) cell",
+            start
         )));
     }
 
-    // Dispatch to appropriate algorithm
-    let result = match algorithm.to_lowercase().as_str() {
-        "astar" => AStar2D {}.find_path_in_heatmap(array_2d.view(), start, end, impassable),
-        "dijkstra" => Dijkstra2D {}.find_path_in_heatmap(array_2d.view(), start, end, impassable),
-        "fringe" => Fringe2D {}.find_path_in_heatmap(array_2d.view(), start, end, impassable),
-        _ => {
-            return Err(PyValueError::new_err(format!(
-                "Unknown algorithm: {}. Supported algorithms: astar, dijkstra, fringe",
-                algorithm
-            )));
+    let connectivity = parse_connectivity(connectivity)?;
+    let wrap = parse_wrap(wrap)?;
+    let result = match &heatmap {
+        Heatmap2D::U8(array) => {
+            let options = SearchOptions2D {
+                impassable: impassable_u8(impassable)?,
+                connectivity,
+                wrap,
+                mask,
+                max_cost,
+                ..Default::default()
+            };
+            region_path_2d(py, array.as_array(), start, goal, options, timeout_ms)?
         }
+        Heatmap2D::U16(array) => {
+            let options = SearchOptions2D {
+                impassable,
+                connectivity,
+                wrap,
+                mask,
+                max_cost,
+                ..Default::default()
+            };
+            region_path_2d(py, array.as_array(), start, goal, options, timeout_ms)?
+        }
+    };
+
+    Ok(result.map(|(path, cost)| {
+        // The search stops on a goal cell, so the path always ends on one
+        let reached = *path.last().expect("a path holds at least the start");
+        (Path2D::new(py, path, as_array), cost, reached)
+    }))
+}
+
+/// Find the cheapest path over a continuous cost surface, using Dijkstra's algorithm with float costs.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype float32 (shape: x, y) i.e. (width, height), holding the cost of
+///   entering each cell. Costs must not be negative or NaN. Cells holding `inf` can never be entered.
+/// * `start` - Start position as (x, y) tuple
+/// * `end` - End position as (x, y) tuple
+/// * `connectivity` - Optional: 4 for cardinal moves only, or 8 to also allow diagonal moves (default: 8)
+/// * `wrap` - Optional: Which borders wrap around to the opposite side: "none", "x", "y", or "xy" (default: "none")
+/// * `mask` - Optional: A 2D boolean NumPy array with the same shape as `array`, where `True` marks a blocked cell
+/// * `timeout_ms` - Optional: A time budget in milliseconds. If the search runs longer, it is aborted
+///   and `TimeoutError` is raised.
+/// * `as_array` - Optional: If true, return the path as an `(N, 2)` uint32 NumPy array instead of a list (default: false)
+///
+/// # Returns
+/// * `Optional[Tuple[List[Tuple[int, int]], float]]` - The path found and its total cost, accumulated
+///   as a 64-bit float, or None if no path was found.
+///
+/// Steps cost as in `find_path_2d`, without rounding: a cardinal step costs the value of the destination
+/// cell, and a diagonal step √2 times the mean of the source and destination values.
+///
+/// The GIL is released while searching. The arrays are borrowed, not copied: they must not be
+/// modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, start, end, *, connectivity=8, wrap="none", mask=None, timeout_ms=None, as_array=false))]
+#[allow(clippy::too_many_arguments)]
+fn find_path_float_2d<'py>(
+    py: Python<'py>,
+    array: PyReadonlyArray2<'py, f32>,
+    start: Pos2D,
+    end: Pos2D,
+    connectivity: u8,
+    wrap: &str,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+    timeout_ms: Option<u64>,
+    as_array: bool,
+) -> PyResult<Option<(Path2D<'py>, f64)>> {
+    let array = array.as_array();
+    let (width, height) = array.dim();
+    ensure_in_bounds("start", &[start.0, start.1], &[width, height])?;
+    ensure_in_bounds("end", &[end.0, end.1], &[width, height])?;
+
+    // Negative costs would let Dijkstra's algorithm settle cells too early, and NaN has no order
+    if let Some(((x, y), value)) = array
+        .indexed_iter()
+        .find(|(_, value)| value.is_nan() || **value < 0.0)
+    {
+        return Err(PyValueError::new_err(format!(
+            "Costs must be non-negative and not NaN, but the cost at ({}, {}) is {}",
+            x, y, value
+        )));
+    }
+
+    let mask = mask.as_ref().map(|mask| mask.as_array());
+    if let Some(mask) = mask
+        && mask.dim() != array.dim()
+    {
+        return Err(PyValueError::new_err(format!(
+            "Mask shape {:?} does not match array shape {:?}",
+            mask.dim(),
+            array.dim()
+        )));
+    }
+
+    let deadline = timeout_ms.map(|timeout| SearchDeadline::after(Duration::from_millis(timeout)));
+    let options = SearchOptions2D {
+        connectivity: parse_connectivity(connectivity)?,
+        wrap: parse_wrap(wrap)?,
+        mask,
+        deadline: deadline.as_ref(),
+        ..Default::default()
+    };
+
+    let result = py.detach(|| Dijkstra2D {}.find_path_float(array, start, end, &options));
+
+    if let Some(timeout) = timeout_ms
+        && deadline.as_ref().is_some_and(SearchDeadline::has_expired)
+    {
+        return Err(timeout_error(timeout));
+    }
+
+    Ok(result.map(|(path, cost)| (Path2D::new(py, path, as_array), cost)))
+}
+
+/// Search an approximate path in two resolutions over a heatmap of any supported dtype,
+/// raising `TimeoutError` if the search runs out of time.
+#[allow(clippy::too_many_arguments)]
+fn hierarchical_path_2d<T: HeatmapValue>(
+    py: Python<'_>,
+    array: ArrayView2<T>,
+    start: Pos2D,
+    end: Pos2D,
+    algorithm: Algorithm2D,
+    factor: usize,
+    corridor: usize,
+    options: SearchOptions2D<T>,
+    timeout_ms: Option<u64>,
+) -> PyResult<Option<(Vec<Pos2D>, u32)>> {
+    let deadline = timeout_ms.map(|timeout| SearchDeadline::after(Duration::from_millis(timeout)));
+    let options = SearchOptions2D {
+        deadline: deadline.as_ref(),
+        ..options
     };
 
+    let result = py.detach(|| match algorithm {
+        Algorithm2D::AStar => {
+            AStar2D::default().find_path_hierarchical(array, start, end, factor, corridor, &options)
+        }
+        Algorithm2D::Dijkstra => {
+            Dijkstra2D {}.find_path_hierarchical(array, start, end, factor, corridor, &options)
+        }
+        Algorithm2D::DijkstraBi => Dijkstra2DBidirectional {}
+            .find_path_hierarchical(array, start, end, factor, corridor, &options),
+        Algorithm2D::Fringe => {
+            Fringe2D {}.find_path_hierarchical(array, start, end, factor, corridor, &options)
+        }
+        Algorithm2D::Jps | Algorithm2D::Theta => unreachable!("rejected before searching"),
+    });
+
+    if let Some(timeout) = timeout_ms
+        && deadline.as_ref().is_some_and(SearchDeadline::has_expired)
+    {
+        return Err(timeout_error(timeout));
+    }
+
     Ok(result)
 }
 
+/// Find an approximate path on a very large 2D heatmap, by searching a coarse path on a
+/// downsampled copy and refining it at full resolution within a corridor around it.
+///
+/// # Arguments
+/// * `array` - A 2D NumPy array with dtype uint8 or uint16 (shape: x, y) i.e. (width, height)
+/// * `start` - Start position as (x, y) tuple
+/// * `end` - End position as (x, y) tuple
+/// * `algorithm` - Algorithm to use for both passes: "astar", "dijkstra", "dijkstra_bi", or "fringe"
+/// * `factor` - Optional: The side length in pixels of the square block each coarse cell covers (default: 8)
+/// * `corridor` - Optional: The number of coarse cells on each side of the coarse path that the refined
+///   path may use (default: 1)
+/// * `impassable` - Optional: A value that, if provided, will be used to filter out neighbours that have this value.
+/// * `connectivity` - Optional: 4 for cardinal moves only, or 8 to also allow diagonal moves (default: 8)
+/// * `wrap` - Optional: Which borders wrap around to the opposite side: "none", "x", "y", or "xy" (default: "none")
+/// * `mask` - Optional: A 2D boolean NumPy array with the same shape as `array`, where `True` marks a blocked cell
+/// * `timeout_ms` - Optional: A time budget in milliseconds. If the search runs longer, it is aborted
+///   and `TimeoutError` is raised.
+/// * `as_array` - Optional: If true, return the path as an `(N, 2)` uint32 NumPy array instead of a list (default: false)
+///
+/// # Returns
+/// * `Optional[Tuple[List[Tuple[int, int]], int]]` - The path found and its cost at full resolution, or None
+///   if no path exists.
+///
+/// The path is approximate: each coarse cell costs the average of the cells it covers, so the
+/// coarse path may lead through a region that is cheap on average but not along the best route,
+/// and a cheaper route outside the corridor is never considered. If the corridor holds no path,
+/// the whole heatmap is searched instead.
+///
+/// The GIL is released while searching. The arrays are borrowed, not copied: they must not be
+/// modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, start, end, algorithm, *, factor=8, corridor=1, impassable=None, connectivity=8, wrap="none", mask=None, timeout_ms=None, as_array=false))]
+#[allow(clippy::too_many_arguments)]
+fn find_path_hierarchical_2d<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    start: Pos2D,
+    end: Pos2D,
+    algorithm: &str,
+    factor: usize,
+    corridor: usize,
+    impassable: Option<u16>,
+    connectivity: u8,
+    wrap: &str,
+    mask: Option<PyReadonlyArray2<'py, bool>>,
+    timeout_ms: Option<u64>,
+    as_array: bool,
+) -> PyResult<Option<(Path2D<'py>, u32)>> {
+    let heatmap = Heatmap2D::extract(array)?;
+
+    let algorithm = Algorithm2D::parse(algorithm)?;
+    if matches!(algorithm, Algorithm2D::Jps | Algorithm2D::Theta) {
+        return Err(PyValueError::new_err(format!(
+            "find_path_hierarchical_2d does not support {}; use astar, dijkstra, dijkstra_bi, or fringe",
+            algorithm.name()
+        )));
+    }
+    if factor == 0 {
+        return Err(PyValueError::new_err("factor must be at least 1"));
+    }
+
+    let mask = merge_heatmap_mask(array, &heatmap, mask)?;
+    let graph = Graph2D {
+        impassable,
+        wall_value: None,
+        block_above: None,
+        block_below: None,
+        connectivity: parse_connectivity(connectivity)?,
+        neighbours: None,
+        reach: 1,
+        wrap: parse_wrap(wrap)?,
+        mask: mask.as_ref().map(|mask| mask.as_array()),
+        cost_table: None,
+        speed: None,
+        turn_penalty: 0,
+        direction_weights: None,
+        no_corner_cutting: false,
+        jps_support: None,
+    };
+    check_endpoints(&heatmap, &graph, &[start], &[end])?;
+
+    let result = match &heatmap {
+        Heatmap2D::U8(array) => {
+            let options = SearchOptions2D {
+                impassable: impassable_u8(impassable)?,
+                connectivity: graph.connectivity,
+                wrap: graph.wrap,
+                mask: graph.mask,
+                ..Default::default()
+            };
+            hierarchical_path_2d(
+                py,
+                array.as_array(),
+                start,
+                end,
+                algorithm,
+                factor,
+                corridor,
+                options,
+                timeout_ms,
+            )?
+        }
+        Heatmap2D::U16(array) => {
+            let options = SearchOptions2D {
+                impassable,
+                connectivity: graph.connectivity,
+                wrap: graph.wrap,
+                mask: graph.mask,
+                ..Default::default()
+            };
+            hierarchical_path_2d(
+                py,
+                array.as_array(),
+                start,
+                end,
+                algorithm,
+                factor,
+                corridor,
+                options,
+                timeout_ms,
+            )?
+        }
+    };
+
+    Ok(result.map(|(path, cost)| (Path2D::new(py, path, as_array), cost)))
+}
+
+/// Route through a temporal volume with the temporal pathfinder for `algorithm`.
+fn route_temporal(
+    algorithm: AlgorithmTemporal,
+    volume: ArrayView3<u8>,
+    reach: Option<Reach>,
+    axis: Option<usize>,
+    (starts, ends): (Option<Vec<Pos3D>>, Option<Vec<Pos3D>>),
+    options: SearchOptionsTemporal,
+) -> Result<(Vec<Pos3D>, u32), RouteFailure> {
+    match algorithm {
+        AlgorithmTemporal::AStar => {
+            AStarTemporal {}.try_find_route_over_time(volume, reach, axis, starts, ends, options)
+        }
+        AlgorithmTemporal::Dijkstra => {
+            DijkstraTemporal {}.try_find_route_over_time(volume, reach, axis, starts, ends, options)
+        }
+        AlgorithmTemporal::Fringe => {
+            FringeTemporal {}.try_find_route_over_time(volume, reach, axis, starts, ends, options)
+        }
+    }
+}
+
 /// Find a route through a temporal volume using the specified algorithm.
 ///
 /// # Arguments
 /// * `array` - A 3D NumPy array with dtype uint8 (shape: x, y, t) i.e. (width, height, time)
-/// * `algorithm` - Algorithm to use: "astar" or "dijkstra"
-/// * `start` - Start position as (x, y, t) tuple
-/// * `end` - End position as (x, y, t) tuple
-/// * `reach` - Optional: Number of elements that can be skipped along each non-axis dimension (default: 1)
-/// * `axis` - Optional: The axis along which the path must always move forward (default: 2 for time)
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", or "fringe"
+/// * `start` - Optional: Start position as (x, y, t) tuple, or a list of candidate start positions.
+///   None or an empty list starts from every position on the first slice along `axis`, so the
+///   route begins wherever the cheapest route does (default: None)
+/// * `end` - Optional: End position as (x, y, t) tuple, or a list of candidate end positions.
+///   None or an empty list ends at any position on the last slice along `axis` (default: None)
+/// * `reach` - Optional: Number of elements that can be skipped along each non-axis dimension (default: 1),
+///   or a `(reach_dim0, reach_dim1)` tuple with one value per non-axis dimension, in axis order.
+///   When routing along x or y, time always advances by one frame, so only `reach_dim0` applies.
+/// * `axis` - Optional: The axis along which the path must always move forward (default: 2 for time).
+///   Negative values count from the last axis, so -1 is time. Axes out of range raise `ValueError`.
+/// * `progress_fn` - Optional: Called as `progress_fn(best_cost, nodes_expanded)` every `progress_interval`
+///   expanded nodes. `best_cost` is the cost of the cheapest complete route found so far, or None.
+///   If it raises, the search is aborted and the exception propagated.
+/// * `progress_interval` - Optional: Number of expanded nodes between two calls to `progress_fn` (default: 10000)
+/// * `timeout_ms` - Optional: A time budget in milliseconds. If the search runs longer, it is aborted
+///   and `TimeoutError` is raised. The clock is only checked every few thousand expanded nodes.
+/// * `max_nodes` - Optional: A cap on the number of expanded nodes, across all start positions, bounding
+///   the memory the search can take. If the search needs more, it is aborted and `RuntimeError` is raised.
+/// * `max_cost` - Optional: An inclusive maximum route cost. Nodes that can only be reached above it are
+///   not expanded, and None is returned if no route fits.
+/// * `strict` - Optional: If true, raise `ValueError` explaining why no route was found instead of
+///   returning None: every end lies before every start along `axis`, every end is further away
+///   than `reach` allows, or no route fits `max_cost` (default: false)
+/// * `scale_diagonal` - Optional: If true, steps moving along both non-axis dimensions at once cost √2 times
+///   the value they enter, like diagonal steps in 2D. Otherwise every step costs the value it enters (default: false)
+/// * `axis_step_min`, `axis_step_max` - Optional: The range of indices each step advances along `axis`
+///   (default: 1 and 1). A larger maximum lets a route skip indices, e.g. frames, which are neither
+///   visited nor paid for, unless `interpolate` fills them in; `reach` still bounds the other coordinates per step. `axis_step_min` must
+///   be at least 1 and at most `axis_step_max`.
+/// * `return_frame_costs` - Optional: If true, also return the cost incurred at each index along `axis`,
+///   e.g. to find the hardest frames (default: false)
+/// * `interpolate` - Optional: If true, fill every index along `axis` the route skips with a point whose
+///   other two coordinates are linearly interpolated between its neighbours and rounded, so the route
+///   holds one point per index. The filled points are not searched: they add nothing to the cost or to
+///   `return_frame_costs` (default: false)
 ///
 /// # Returns
-/// * `Optional[Tuple[List[Tuple[int, int, int]], int]]` - The route found and total cost, or None if no route was found
+/// * `Optional[Tuple[List[Tuple[int, int, int]], int]]` - The route found and total cost, or None if no route was found.
+///   With several starts or ends, the cheapest route connecting any start to any end is returned.
+///   The first and last points of the route are the start and end that were used.
+///   With `return_frame_costs=True` a list with one cost per index along `axis` is appended to the
+///   tuple. Each step's cost is counted at the slice it enters, so the costs sum to the total cost,
+///   and the start slice, skipped slices, and slices outside the route hold 0.
+///   With the default axis step range, each step advances by exactly one index along `axis`, so the
+///   route holds one point per index between its start and end; `reach` only bounds the jumps in the
+///   other coordinates.
+///
+/// The GIL is released while searching, so other Python threads keep running. It is only
+/// reacquired to call `progress_fn`. The array is borrowed, not copied: it must not be
+/// modified by another thread until the call returns. Non-contiguous arrays, such as transposed
+/// or strided slices, are read through their strides rather than copied, which gives the same
+/// results but may search more slowly.
 #[pyfunction]
-#[pyo3(signature = (array, algorithm, start, end, *, reach=None, axis=None))]
-fn find_route_temporal(
+#[pyo3(signature = (array, algorithm, start=None, end=None, *, reach=None, axis=None, progress_fn=None, progress_interval=10000, timeout_ms=None, max_nodes=None, max_cost=None, strict=false, scale_diagonal=false, axis_step_min=1, axis_step_max=1, return_frame_costs=false, interpolate=false))]
+#[allow(clippy::too_many_arguments)]
+fn find_route_temporal<'py>(
+    py: Python<'py>,
     array: PyReadonlyArray3<u8>,
     algorithm: &str,
-    start: (u32, u32, u32),
-    end: (u32, u32, u32),
-    reach: Option<usize>,
-    axis: Option<usize>,
-) -> PyResult<Option<(Vec<(u32, u32, u32)>, u32)>> {
+    start: Option<Points3D>,
+    end: Option<Points3D>,
+    reach: Option<ReachArg>,
+    axis: Option<isize>,
+    progress_fn: Option<Py<PyAny>>,
+    progress_interval: usize,
+    timeout_ms: Option<u64>,
+    max_nodes: Option<usize>,
+    max_cost: Option<u32>,
+    strict: bool,
+    scale_diagonal: bool,
+    axis_step_min: u32,
+    axis_step_max: u32,
+    return_frame_costs: bool,
+    interpolate: bool,
+) -> PyResult<Option<Bound<'py, PyTuple>>> {
     // PyReadonlyArray3<u8> enforces 3D array with u8 dtype at the Python binding level.
     // This provides runtime validation from Python's perspective.
-    // Use the array view directly to avoid copying
+    // Use the array view directly to avoid copying. It follows the strides of the array, so
+    // transposed, Fortran-ordered, and sliced arrays are indexed like their contiguous copies
     let array_3d = array.as_array();
 
-    let (width, height, time) = array_3d.dim();
-    let width = width as u32;
-    let height = height as u32;
-    let time = time as u32;
+    let shape = array_3d.shape();
+    for &(x, y, t) in start.iter().flat_map(Points3D::as_slice) {
+        ensure_in_bounds("start", &[x, y, t], shape)?;
+    }
+    for &(x, y, t) in end.iter().flat_map(Points3D::as_slice) {
+        ensure_in_bounds("end", &[x, y, t], shape)?;
+    }
 
-    if start.0 >= width
-        || start.1 >= height
-        || start.2 >= time
-        || end.0 >= width
-        || end.1 >= height
-        || end.2 >= time
-    {
-        return Err(PyValueError::new_err(format!(
-            "Start or end position is out of bounds: start={:?}, end={:?}, shape={:?}",
-            start,
-            end,
-            (width, height, time)
-        )));
+    if progress_interval == 0 {
+        return Err(PyValueError::new_err(
+            "progress_interval must be at least 1",
+        ));
     }
 
+    let algorithm = AlgorithmTemporal::parse(algorithm)?;
+    let axis = normalize_axis(axis, 3)?;
+    let axis_step = check_axis_step(axis_step_min, axis_step_max)?;
+
+    let reach = reach.map(Reach::from);
+
     // Convert single points to vectors for the underlying function
-    let starts = Some(vec![start]);
-    let ends = Some(vec![end]);
+    let starts = start.and_then(Points3D::into_route_points);
+    let ends = end.and_then(Points3D::into_route_points);
 
-    // Dispatch to appropriate algorithm
-    let result = match algorithm.to_lowercase().as_str() {
-        "astar" => {
-            AStarTemporal {}.find_route_over_time(array_3d.view(), reach, axis, starts, ends)
-        }
-        "dijkstra" => {
-            DijkstraTemporal {}.find_route_over_time(array_3d.view(), reach, axis, starts, ends)
-        }
-        _ => {
+    // Dispatch to appropriate algorithm. The GIL is released during the search, so the
+    // caller must not mutate `array` from another Python thread until this call returns.
+    // It is only reacquired every `progress_interval` nodes to report progress.
+    let deadline = timeout_ms.map(|timeout| SearchDeadline::after(Duration::from_millis(timeout)));
+    let node_limit = max_nodes.map(NodeLimit::new);
+    let mut error = None;
+    let result = py.detach(|| {
+        let mut report = |progress: TemporalProgress| {
+            let Some(progress_fn) = &progress_fn else {
+                return ControlFlow::Continue(());
+            };
+
+            Python::attach(|py| {
+                match progress_fn.call1(py, (progress.best_cost, progress.nodes_expanded)) {
+                    Ok(_) => ControlFlow::Continue(()),
+                    Err(err) => {
+                        error = Some(err);
+                        ControlFlow::Break(())
+                    }
+                }
+            })
+        };
+
+        let progress = progress_fn.is_some().then_some(ProgressReporter {
+            interval: progress_interval,
+            callback: &mut report,
+        });
+        let options = SearchOptionsTemporal {
+            progress,
+            deadline: deadline.as_ref(),
+            node_limit: node_limit.as_ref(),
+            max_cost,
+            scale_diagonal,
+            axis_step,
+        };
+
+        route_temporal(algorithm, array_3d, reach, axis, (starts, ends), options)
+    });
+
+    // A raising progress_fn aborts the search, and its exception takes precedence
+    if let Some(err) = error {
+        return Err(err);
+    }
+
+    if let Some(timeout) = timeout_ms
+        && deadline.as_ref().is_some_and(SearchDeadline::has_expired)
+    {
+        return Err(timeout_error(timeout));
+    }
+    if let Some(max_nodes) = max_nodes
+        && node_limit.as_ref().is_some_and(NodeLimit::was_exceeded)
+    {
+        return Err(node_limit_error(max_nodes));
+    }
+
+    let (route, cost) = match result {
+        Ok(route) => route,
+        Err(reason) if strict && reason != RouteFailure::Aborted => {
             return Err(PyValueError::new_err(format!(
-                "Unknown algorithm: {}. Supported algorithms: astar, dijkstra",
-                algorithm
+                "No route found along axis {}: {}",
+                axis.unwrap_or(2),
+                reason
             )));
         }
+        Err(_) => return Ok(None),
     };
 
-    Ok(result)
+    // Frame costs are split over the searched steps, before any point is filled in
+    let frame_costs =
+        return_frame_costs.then(|| frame_costs(array_3d, &route, axis, scale_diagonal));
+    let route = if interpolate {
+        interpolate_route(&route, axis)
+    } else {
+        route
+    };
+
+    let mut items = vec![route.into_bound_py_any(py)?, cost.into_bound_py_any(py)?];
+    if let Some(frame_costs) = frame_costs {
+        items.push(frame_costs.into_bound_py_any(py)?);
+    }
+
+    Ok(Some(PyTuple::new(py, items)?))
+}
+
+/// Find a path in each slice of a stack of independent 2D heatmaps.
+///
+/// # Arguments
+/// * `array` - A 3D NumPy array with dtype uint8 (shape: x, y, n), holding `n` heatmaps of shape (width, height)
+/// * `start` - Start position as (x, y) tuple, shared by every slice
+/// * `end` - End position as (x, y) tuple, shared by every slice
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", "dijkstra_bi", "fringe", "jps", or "theta"
+/// * `impassable` - Optional: A value that, if provided, will be used to filter out neighbours that have this value.
+/// * `connectivity` - Optional: 4 for cardinal moves only, or 8 to also allow diagonal moves (default: 8)
+/// * `timeout_ms` - Optional: A time budget in milliseconds for the whole batch. If it runs out,
+///   the remaining searches are aborted and `TimeoutError` is raised.
+///
+/// # Returns
+/// * `List[Optional[Tuple[List[Tuple[int, int]], int]]]` - One result per slice, in slice order: the path
+///   and its cost as returned by `find_path_2d`, or None if the slice has no path.
+///
+/// Unlike `find_route_temporal`, the slices are not connected: each one is searched on its own, as
+/// `find_path_2d(array[:, :, i], start, end, algorithm)` would. The slices are searched in parallel on
+/// a thread pool with the GIL released; the array is borrowed, not copied, and must not be modified
+/// by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, start, end, algorithm, *, impassable=None, connectivity=8, timeout_ms=None))]
+#[allow(clippy::too_many_arguments)]
+fn find_paths_batch_2d(
+    py: Python<'_>,
+    array: PyReadonlyArray3<u8>,
+    start: Pos2D,
+    end: Pos2D,
+    algorithm: &str,
+    impassable: Option<u16>,
+    connectivity: u8,
+    timeout_ms: Option<u64>,
+) -> PyResult<Vec<Route2D>> {
+    let stack = array.as_array();
+    let algorithm = Algorithm2D::parse(algorithm)?;
+    let impassable = impassable_u8(impassable)?;
+    let connectivity = parse_connectivity(connectivity)?;
+
+    let (width, height, _) = stack.dim();
+    ensure_in_bounds("start", &[start.0, start.1], &[width, height])?;
+    ensure_in_bounds("end", &[end.0, end.1], &[width, height])?;
+
+    let deadline = timeout_ms.map(|timeout| SearchDeadline::after(Duration::from_millis(timeout)));
+    let options = SearchOptions2D {
+        impassable,
+        connectivity,
+        deadline: deadline.as_ref(),
+        ..Default::default()
+    };
+
+    // A single pathfinder serves every slice, so jps must suit all of them
+    let pathfinder = make_pathfinder_2d(algorithm, AStar2D::default(), None, || {
+        stack
+            .axis_iter(Axis(2))
+            .all(|slice| JumpPointSearch2D::supports(slice, &options))
+    })?;
+
+    let paths = py.detach(|| {
+        (0..stack.len_of(Axis(2)))
+            .into_par_iter()
+            .map(|i| {
+                let slice = stack.index_axis(Axis(2), i);
+                pathfinder.find_path_with_options(slice, start, end, &options)
+            })
+            .collect()
+    });
+
+    if let Some(timeout) = timeout_ms
+        && deadline.as_ref().is_some_and(SearchDeadline::has_expired)
+    {
+        return Err(timeout_error(timeout));
+    }
+
+    Ok(paths)
+}
+
+/// Find routes for many independent `(start, end)` pairs through the same temporal volume.
+///
+/// # Arguments
+/// * `array` - A 3D NumPy array with dtype uint8 (shape: x, y, t) i.e. (width, height, time)
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", or "fringe"
+/// * `queries` - A list of `((x, y, t), (x, y, t))` start and end pairs
+/// * `reach`, `axis`, `max_cost`, `scale_diagonal`, `axis_step_min`, `axis_step_max` - Optional: As for
+///   `find_route_temporal`, applied to every query
+/// * `timeout_ms` - Optional: A time budget in milliseconds for the whole batch. If it runs out,
+///   the remaining searches are aborted and `TimeoutError` is raised.
+///
+/// # Returns
+/// * `List[Optional[Tuple[List[Tuple[int, int, int]], int]]]` - One result per query, in the order
+///   of `queries`: the route and its cost as returned by `find_route_temporal`, or None if the
+///   query has no route.
+///
+/// The array and every query are validated once, before any search starts. The queries then
+/// run in parallel on a thread pool with the GIL released; the array is borrowed, not copied,
+/// and must not be modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, algorithm, queries, *, reach=None, axis=None, timeout_ms=None, max_cost=None, scale_diagonal=false, axis_step_min=1, axis_step_max=1))]
+#[allow(clippy::too_many_arguments)]
+fn find_routes_temporal(
+    py: Python<'_>,
+    array: PyReadonlyArray3<u8>,
+    algorithm: &str,
+    queries: Vec<(Pos3D, Pos3D)>,
+    reach: Option<ReachArg>,
+    axis: Option<isize>,
+    timeout_ms: Option<u64>,
+    max_cost: Option<u32>,
+    scale_diagonal: bool,
+    axis_step_min: u32,
+    axis_step_max: u32,
+) -> PyResult<Vec<Route3D>> {
+    let array_3d = array.as_array();
+    let algorithm = AlgorithmTemporal::parse(algorithm)?;
+    let axis = normalize_axis(axis, 3)?;
+    let axis_step = check_axis_step(axis_step_min, axis_step_max)?;
+
+    let shape = array_3d.shape();
+    for (i, &((sx, sy, st), (ex, ey, et))) in queries.iter().enumerate() {
+        ensure_in_bounds(&format!("start of query {}", i), &[sx, sy, st], shape)?;
+        ensure_in_bounds(&format!("end of query {}", i), &[ex, ey, et], shape)?;
+    }
+
+    let reach = reach.map(Reach::from);
+    let deadline = timeout_ms.map(|timeout| SearchDeadline::after(Duration::from_millis(timeout)));
+    let routes: Vec<Route3D> = py.detach(|| {
+        queries
+            .par_iter()
+            .map(|&(start, end)| {
+                let options = SearchOptionsTemporal {
+                    deadline: deadline.as_ref(),
+                    max_cost,
+                    scale_diagonal,
+                    axis_step,
+                    ..Default::default()
+                };
+                let endpoints = (Some(vec![start]), Some(vec![end]));
+                route_temporal(algorithm, array_3d, reach, axis, endpoints, options).ok()
+            })
+            .collect()
+    });
+
+    if let Some(timeout) = timeout_ms
+        && deadline.as_ref().is_some_and(SearchDeadline::has_expired)
+    {
+        return Err(timeout_error(timeout));
+    }
+
+    Ok(routes)
+}
+
+/// Route through a 4D volume with the temporal pathfinder for `algorithm`.
+fn route_4d(
+    algorithm: AlgorithmTemporal,
+    volume: ArrayView4<u8>,
+    reach: Option<Reach4D>,
+    axis: Option<usize>,
+    (starts, ends): (Option<Vec<Pos4D>>, Option<Vec<Pos4D>>),
+    options: SearchOptionsTemporal,
+) -> Result<(Vec<Pos4D>, u32), RouteFailure> {
+    match algorithm {
+        AlgorithmTemporal::AStar => {
+            AStarTemporal {}.try_find_route_4d(volume, reach, axis, starts, ends, options)
+        }
+        AlgorithmTemporal::Dijkstra => {
+            DijkstraTemporal {}.try_find_route_4d(volume, reach, axis, starts, ends, options)
+        }
+        AlgorithmTemporal::Fringe => {
+            FringeTemporal {}.try_find_route_4d(volume, reach, axis, starts, ends, options)
+        }
+    }
+}
+
+/// Find a route through a 4D volume, advancing along one axis while moving freely along the others.
+///
+/// # Arguments
+/// * `array` - A 4D NumPy array with dtype uint8, e.g. (t, z, y, x) or (x, y, z, t)
+/// * `algorithm` - Algorithm to use: "astar", "dijkstra", or "fringe"
+/// * `start` - Optional: Start position as a tuple of 4 indices, in the order of the array's axes.
+///   None starts from every position on the first slice along `axis` (default: None)
+/// * `end` - Optional: End position as a tuple of 4 indices. None ends at any position on the last
+///   slice along `axis` (default: None)
+/// * `reach` - Optional: Number of elements that can be skipped along each non-axis dimension (default: 1),
+///   or a `(reach_dim0, reach_dim1, reach_dim2)` tuple with one value per non-axis dimension, in axis order.
+/// * `axis` - Optional: The axis along which the path must always move forward (default: 3, the last axis).
+///   Negative values count from the last axis, as for `find_route_temporal`.
+/// * `timeout_ms`, `max_cost`, `strict`, `axis_step_min`, `axis_step_max` - Optional: As for `find_route_temporal`
+/// * `scale_diagonal` - Optional: If true, steps moving along n >= 2 non-axis dimensions at once cost √n
+///   times the value they enter (default: false)
+///
+/// # Returns
+/// * `Optional[Tuple[List[Tuple[int, int, int, int]], int]]` - The route found and total cost, or None if
+///   no route was found. Unlike `find_route_temporal`, no axis advances implicitly: each step advances
+///   along `axis` only, and moves within `reach` along the three others.
+///
+/// The search graph is much larger than in 3D: each node has up to `(2 * reach + 1) ** 3` neighbours,
+/// and the search keeps a cost for every node it reaches, which can approach the size of the volume.
+/// Without `start`, a search runs from every position on the first slice, so pass `start` on large
+/// volumes. The GIL is released while searching, and the array is borrowed, not copied: it must not
+/// be modified by another thread until the call returns.
+#[pyfunction]
+#[pyo3(signature = (array, algorithm, start=None, end=None, *, reach=None, axis=None, timeout_ms=None, max_cost=None, strict=false, scale_diagonal=false, axis_step_min=1, axis_step_max=1))]
+#[allow(clippy::too_many_arguments)]
+fn find_route_4d(
+    py: Python<'_>,
+    array: PyReadonlyArray4<u8>,
+    algorithm: &str,
+    start: Option<Pos4D>,
+    end: Option<Pos4D>,
+    reach: Option<Reach4DArg>,
+    axis: Option<isize>,
+    timeout_ms: Option<u64>,
+    max_cost: Option<u32>,
+    strict: bool,
+    scale_diagonal: bool,
+    axis_step_min: u32,
+    axis_step_max: u32,
+) -> PyResult<Route4D> {
+    let array_4d = array.as_array();
+    let algorithm = AlgorithmTemporal::parse(algorithm)?;
+    let axis_step = check_axis_step(axis_step_min, axis_step_max)?;
+
+    let axis = normalize_axis(axis, 4)?;
+
+    let shape = array_4d.shape();
+    if let Some((a, b, c, d)) = start {
+        ensure_in_bounds("start", &[a, b, c, d], shape)?;
+    }
+    if let Some((a, b, c, d)) = end {
+        ensure_in_bounds("end", &[a, b, c, d], shape)?;
+    }
+
+    let reach = reach.map(Reach4D::from);
+    let endpoints = (start.map(|start| vec![start]), end.map(|end| vec![end]));
+    let deadline = timeout_ms.map(|timeout| SearchDeadline::after(Duration::from_millis(timeout)));
+    let result = py.detach(|| {
+        let options = SearchOptionsTemporal {
+            deadline: deadline.as_ref(),
+            max_cost,
+            scale_diagonal,
+            axis_step,
+            ..Default::default()
+        };
+        route_4d(algorithm, array_4d, reach, axis, endpoints, options)
+    });
+
+    if let Some(timeout) = timeout_ms
+        && deadline.as_ref().is_some_and(SearchDeadline::has_expired)
+    {
+        return Err(timeout_error(timeout));
+    }
+
+    match result {
+        Ok(route) => Ok(Some(route)),
+        Err(reason) if strict && reason != RouteFailure::Aborted => {
+            Err(PyValueError::new_err(format!(
+                "No route found along axis {}: {}",
+                axis.unwrap_or(3),
+                reason
+            )))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Draw a path over an image and save the result, the same way the CLI renders its outputs.
+///
+/// # Arguments
+/// * `image_path` - Path to the image to draw on, in any format the CLI accepts.
+/// * `path` - The path as a list of (x, y) tuples, e.g. as returned by `find_path_2d`.
+/// * `out_path` - Where to save the overlay. The format follows the file extension.
+/// * `color` - Optional: The (r, g, b) color of the path. Defaults to red.
+/// * `radius` - Optional: The radius of the dots, or the half-width of the line, in pixels. Defaults to 3.
+/// * `line` - Optional: Connect consecutive points with line segments instead of drawing a dot at each.
+///
+/// Points outside the image are clipped. Raises `ValueError` if the image cannot be read or
+/// the overlay cannot be written.
+#[pyfunction]
+#[pyo3(signature = (image_path, path, out_path, *, color=(255, 0, 0), radius=3, line=false))]
+fn draw_path_overlay(
+    py: Python<'_>,
+    image_path: &str,
+    path: Vec<Pos2D>,
+    out_path: &str,
+    color: (u8, u8, u8),
+    radius: u32,
+    line: bool,
+) -> PyResult<()> {
+    let style = if line {
+        PathStyle::Line
+    } else {
+        PathStyle::Dots
+    };
+    let color = Rgb([color.0, color.1, color.2]);
+
+    py.detach(|| save_overlay_2d(image_path, &path, out_path, style, radius, color))
+        .map_err(|e| {
+            PyValueError::new_err(format!(
+                "failed to draw overlay from {image_path} to {out_path}: {e}"
+            ))
+        })
+}
+
+/// Render a cost or distance field, e.g. from `compute_distance_field_2d`, to a colormapped image.
+///
+/// # Arguments
+/// * `field` - A 2D NumPy array with dtype uint32 (shape: x, y) i.e. (width, height)
+/// * `out_path` - Where to save the image. The format follows the file extension and should support
+///   transparency, such as PNG.
+/// * `colormap` - Optional: The color scale, "viridis" or "grayscale" (default: "viridis").
+/// * `unreachable_color` - Optional: The (r, g, b) color of cells holding `2**32 - 1`. Defaults to
+///   transparent.
+///
+/// The reachable costs are normalized from their minimum to their maximum, so the cheapest cell
+/// gets the first color of the scale and the costliest the last. Raises `ValueError` if the
+/// colormap is unknown or the image cannot be written.
+#[pyfunction]
+#[pyo3(signature = (field, out_path, *, colormap="viridis", unreachable_color=None))]
+fn save_distance_field(
+    py: Python<'_>,
+    field: PyReadonlyArray2<u32>,
+    out_path: &str,
+    colormap: &str,
+    unreachable_color: Option<(u8, u8, u8)>,
+) -> PyResult<()> {
+    let colormap = parse_colormap(colormap)?;
+    let unreachable = unreachable_color.map(|(r, g, b)| Rgb([r, g, b]));
+    let field = field.as_array();
+
+    py.detach(|| save_field_2d(field, out_path, colormap, unreachable))
+        .map_err(|e| {
+            PyValueError::new_err(format!("failed to save distance field to {out_path}: {e}"))
+        })
+}
+
+/// Check that a 2D point lies inside an array of the given shape, without running a search, e.g.
+/// to pre-screen user input before a batch of queries.
+///
+/// # Arguments
+/// * `shape` - The shape of the array, e.g. `array.shape`, as (width, height)
+/// * `point` - The position as (x, y) tuple. Negative coordinates are reported as out of bounds.
+/// * `name` - Optional: The name of the point in the error message (default: "point")
+///
+/// # Returns
+/// * `bool` - True if the point lies inside the array. Otherwise `ValueError` is raised with the
+///   message `find_path_2d` reports, e.g. `point (10, 0) is out of bounds for array of shape 5x5`.
+#[pyfunction]
+#[pyo3(signature = (shape, point, *, name="point"))]
+fn validate_point_2d(shape: (usize, usize), point: (i64, i64), name: &str) -> PyResult<bool> {
+    ensure_in_bounds(name, &[point.0, point.1], &[shape.0, shape.1])?;
+    Ok(true)
+}
+
+/// Check that a 3D point lies inside a volume of the given shape, without running a search.
+///
+/// # Arguments
+/// * `shape` - The shape of the volume, e.g. `array.shape`, as (width, height, depth)
+/// * `point` - The position as (x, y, t) tuple. Negative coordinates are reported as out of bounds.
+/// * `name` - Optional: The name of the point in the error message (default: "point")
+///
+/// # Returns
+/// * `bool` - True if the point lies inside the volume. Otherwise `ValueError` is raised with the
+///   message `find_route_temporal` reports, e.g. `point (0, 0, 9) is out of bounds for array of
+///   shape 5x5x3`.
+#[pyfunction]
+#[pyo3(signature = (shape, point, *, name="point"))]
+fn validate_point_3d(
+    shape: (usize, usize, usize),
+    point: (i64, i64, i64),
+    name: &str,
+) -> PyResult<bool> {
+    ensure_in_bounds(
+        name,
+        &[point.0, point.1, point.2],
+        &[shape.0, shape.1, shape.2],
+    )?;
+    Ok(true)
+}
+
+/// List the algorithm names accepted by the 2D functions, e.g. `find_path_2d`.
+///
+/// # Returns
+/// * `List[str]` - The names, in lowercase. Names are matched ignoring case.
+#[pyfunction]
+fn supported_algorithms_2d() -> Vec<&'static str> {
+    Algorithm2D::ALL.map(Algorithm2D::name).to_vec()
+}
+
+/// List the algorithm names accepted by `find_route_temporal`.
+///
+/// # Returns
+/// * `List[str]` - The names, in lowercase. Names are matched ignoring case.
+#[pyfunction]
+fn supported_algorithms_temporal() -> Vec<&'static str> {
+    AlgorithmTemporal::ALL.map(AlgorithmTemporal::name).to_vec()
+}
+
+/// Report the versions of this build, e.g. for bug reports.
+///
+/// # Returns
+/// * `Dict[str, str]` - The version of `pathfinding_py` under the `"pathfinding_py"` key, and the
+///   version of the `image_pathfinding` crate it was compiled against under `"image_pathfinding"`.
+#[pyfunction]
+fn version() -> HashMap<&'static str, &'static str> {
+    HashMap::from([
+        ("pathfinding_py", env!("CARGO_PKG_VERSION")),
+        ("image_pathfinding", image_pathfinding::VERSION),
+    ])
 }
 
 /// A Python module implemented in Rust. The name of this function must match
@@ -136,6 +3667,31 @@ fn find_route_temporal(
 #[pymodule]
 fn pathfinding_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(find_path_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_path_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(path_cost_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(find_k_paths_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(find_disjoint_path_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(find_path_through_waypoints_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_distance_field_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_flow_field_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(find_path_to_region_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(find_path_float_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(find_paths_batch_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(is_reachable_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(find_path_hierarchical_2d, m)?)?;
     m.add_function(wrap_pyfunction!(find_route_temporal, m)?)?;
+    m.add_function(wrap_pyfunction!(find_routes_temporal, m)?)?;
+    m.add_function(wrap_pyfunction!(find_route_4d, m)?)?;
+    m.add_function(wrap_pyfunction!(draw_path_overlay, m)?)?;
+    m.add_function(wrap_pyfunction!(save_distance_field, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_point_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_point_3d, m)?)?;
+    m.add_function(wrap_pyfunction!(supported_algorithms_2d, m)?)?;
+    m.add_function(wrap_pyfunction!(supported_algorithms_temporal, m)?)?;
+    m.add_function(wrap_pyfunction!(version, m)?)?;
+    m.add_class::<PathFinder2D>()?;
+    m.add_class::<PathIterator2D>()?;
+    m.add_class::<DStarLite2D>()?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
     Ok(())
 }
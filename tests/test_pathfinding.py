@@ -1,6 +1,11 @@
 """Integration tests for pathfinding_py module."""
 
+import gc
+import math
 import pathlib
+import subprocess
+import sys
+from concurrent.futures import ThreadPoolExecutor
 
 import numpy as np
 import pytest
@@ -78,6 +83,2134 @@ def test_find_path_2d_fringe():
     assert path[-1] == end, "Path should end at the end position"
 
 
+def test_find_path_2d_as_array():
+    """Test that as_array=True returns the path as an (N, 2) uint32 array."""
+    array = np.ones((10, 10), dtype=np.uint8) * 200
+
+    for i in range(10):
+        array[i, i] = 10
+
+    start = (0, 0)
+    end = (9, 9)
+
+    path_list, cost_list = pathfinding_py.find_path_2d(array, start, end, "astar")
+    path_array, cost_array = pathfinding_py.find_path_2d(
+        array, start, end, "astar", as_array=True
+    )
+
+    assert isinstance(path_array, np.ndarray), "Path should be a NumPy array"
+    assert path_array.dtype == np.uint32, "Path array should be uint32"
+    assert path_array.shape == (len(path_list), 2), "Path array should have shape (N, 2)"
+    assert path_array.flags["C_CONTIGUOUS"], "Path array should be C-contiguous"
+    assert [tuple(p) for p in path_array.tolist()] == path_list
+    assert cost_array == cost_list
+
+
+def test_find_path_2d_as_array_no_path():
+    """Test that as_array=True still returns None when no path exists."""
+    array = np.ones((5, 5), dtype=np.uint8) * 50
+    array[2, :] = 0  # Impassable wall across the whole map
+
+    result = pathfinding_py.find_path_2d(
+        array, (0, 0), (4, 4), "astar", impassable=0, as_array=True
+    )
+
+    assert result is None, "No path should be found through an impassable wall"
+
+
+def test_find_path_2d_connectivity_4_moves_cardinally():
+    """Test that connectivity=4 only produces cardinal steps."""
+    array = np.ones((6, 6), dtype=np.uint8) * 10
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (5, 5), "dijkstra", connectivity=4)
+
+    assert result is not None, "Path should be found"
+    path, cost = result
+    for (x1, y1), (x2, y2) in zip(path, path[1:]):
+        assert abs(x1 - x2) + abs(y1 - y2) == 1, "Each step should be cardinal"
+    assert cost == 10 * 10, "Cost should be 10 cardinal steps of cost 10"
+
+
+def test_find_path_2d_connectivity_8_scales_diagonals():
+    """Test that connectivity=8 takes diagonals costed at sqrt(2) times the averaged pixel cost."""
+    array = np.ones((6, 6), dtype=np.uint8) * 10
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (5, 5), "dijkstra", connectivity=8)
+
+    assert result is not None, "Path should be found"
+    path, cost = result
+    assert len(path) == 6, "Path should walk the diagonal"
+    assert cost == 5 * round(10 * 2**0.5), "Each diagonal step should cost round(sqrt(2) * 10)"
+
+
+def test_find_path_2d_invalid_connectivity():
+    """Test that an unsupported connectivity raises an error."""
+    array = np.ones((5, 5), dtype=np.uint8) * 50
+
+    with pytest.raises(ValueError):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", connectivity=6)
+
+
+def test_find_path_2d_mask_blocks_cells():
+    """Test that masked cells are never part of the path."""
+    array = np.ones((7, 7), dtype=np.uint8) * 10
+    mask = np.zeros((7, 7), dtype=bool)
+    mask[3, 0:6] = True  # Wall with a gap at y=6
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (6, 0), "astar", mask=mask)
+
+    assert result is not None, "Path should be found through the gap"
+    path, _ = result
+    for x, y in path:
+        assert not mask[x, y], f"Path should not cross masked cell {(x, y)}"
+
+
+def test_find_path_2d_mask_fully_blocking():
+    """Test that a fully blocking mask yields no path."""
+    array = np.ones((5, 5), dtype=np.uint8) * 10
+    mask = np.zeros((5, 5), dtype=bool)
+    mask[2, :] = True
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "dijkstra", mask=mask)
+
+    assert result is None, "No path should be found through a masked wall"
+
+
+def test_find_path_2d_mask_on_start_or_end():
+    """Test that a start or end on a masked cell raises an error."""
+    array = np.ones((5, 5), dtype=np.uint8) * 10
+    mask = np.zeros((5, 5), dtype=bool)
+    mask[0, 0] = True
+    mask[4, 4] = True
+
+    with pytest.raises(ValueError, match="Start"):
+        pathfinding_py.find_path_2d(array, (0, 0), (2, 2), "astar", mask=mask)
+
+    with pytest.raises(ValueError, match="End"):
+        pathfinding_py.find_path_2d(array, (2, 2), (4, 4), "astar", mask=mask)
+
+
+def test_find_path_2d_mask_shape_mismatch():
+    """Test that a mask with the wrong shape raises an error."""
+    array = np.ones((5, 5), dtype=np.uint8) * 10
+    mask = np.zeros((4, 5), dtype=bool)
+
+    with pytest.raises(ValueError):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", mask=mask)
+
+
+def test_find_path_2d_masked_array_blocks_masked_cells():
+    """Test that the cells masked in a numpy.ma.MaskedArray heatmap are never entered."""
+    data = np.ones((7, 7), dtype=np.uint8) * 10
+    array = np.ma.masked_array(data, mask=np.zeros((7, 7), dtype=bool))
+    array[3, 0:6] = np.ma.masked  # Wall with a gap at y=6
+
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (6, 0), "dijkstra")
+
+    for x, y in path:
+        assert not array.mask[x, y], f"Path should not cross masked cell {(x, y)}"
+    assert cost > pathfinding_py.find_path_2d(data, (0, 0), (6, 0), "dijkstra")[1], "The detour should cost more"
+
+    finder = pathfinding_py.PathFinder2D(array)
+    assert finder.find((0, 0), (6, 0), "dijkstra") == (path, cost), "PathFinder2D should honor the mask too"
+
+
+def test_find_path_2d_masked_array_combines_with_mask():
+    """Test that the array's own mask and the mask argument both block cells."""
+    array = np.ma.masked_equal(np.ones((5, 5), dtype=np.uint8) * 10, 0)  # Nothing masked
+    array[2, 0:3] = np.ma.masked
+    mask = np.zeros((5, 5), dtype=bool)
+    mask[2, 3:] = True
+
+    assert pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar") is not None, "The array mask leaves a gap"
+    assert pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", mask=mask) is None, (
+        "Both masks together should wall off the end"
+    )
+
+    with pytest.raises(ValueError, match="masked"):
+        pathfinding_py.find_path_2d(array, (2, 0), (4, 4), "astar")
+
+
+def test_find_path_2d_block_above_and_below():
+    """Test that cells outside the thresholds are never entered, with inclusive boundaries."""
+    array = np.ones((7, 7), dtype=np.uint8) * 100
+    array[3, 0:6] = 200  # Bright wall with a gap at y=6
+    array[0:3, 3] = 20  # Dark wall on the left half
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 0), (6, 0), "dijkstra", block_above=150)
+    assert all(array[p] <= 150 for p in path), "Path should avoid cells above block_above"
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 0), (6, 0), "dijkstra", block_above=200)
+    assert any(array[p] == 200 for p in path), "A cell equal to block_above should stay passable"
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (0, 6), "dijkstra", block_above=150, block_below=50)
+    assert result is None, "Both thresholds together should wall off the end"
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 0), (0, 6), "dijkstra", block_below=20)
+    assert path == pathfinding_py.find_path_2d(array, (0, 0), (0, 6), "dijkstra")[0], (
+        "A cell equal to block_below should stay passable"
+    )
+
+
+def test_find_path_2d_block_range_invalid():
+    """Test that a block_below above block_above raises ValueError."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="block_below"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", block_above=10, block_below=20)
+
+
+def test_find_path_2d_weight_one_matches_default():
+    """Test that weight=1.0 reproduces the default A* result."""
+    rng = np.random.default_rng(0)
+    array = rng.integers(1, 255, size=(30, 30), dtype=np.uint8)
+
+    default = pathfinding_py.find_path_2d(array, (0, 0), (29, 29), "astar")
+    weighted = pathfinding_py.find_path_2d(array, (0, 0), (29, 29), "astar", weight=1.0)
+
+    assert default == weighted, "weight=1.0 should reproduce the default behavior"
+
+
+def test_find_path_2d_weight_inflated():
+    """Test that an inflated weight still finds a valid path."""
+    rng = np.random.default_rng(1)
+    array = rng.integers(1, 255, size=(30, 30), dtype=np.uint8)
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (29, 29), "astar", weight=5.0)
+
+    assert result is not None, "Path should be found"
+    path, _ = result
+    assert path[0] == (0, 0), "Path should start at the start position"
+    assert path[-1] == (29, 29), "Path should end at the end position"
+
+
+def test_find_path_2d_invalid_weight():
+    """Test that a negative weight raises an error."""
+    array = np.ones((5, 5), dtype=np.uint8) * 50
+
+    with pytest.raises(ValueError):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", weight=-1.0)
+
+
+def test_find_path_2d_multiple_starts_and_ends():
+    """Test that lists of starts and ends return the cheapest connecting path and its indices."""
+    array = np.ones((10, 10), dtype=np.uint8) * 100
+    array[:, 5] = 1  # Cheap column at y=5
+
+    starts = [(0, 0), (0, 5)]
+    ends = [(9, 9), (9, 5)]
+
+    result = pathfinding_py.find_path_2d(array, starts, ends, "dijkstra")
+
+    assert result is not None, "Path should be found"
+    path, cost, start_idx, end_idx = result
+    assert start_idx == 1, "The start on the cheap column should be used"
+    assert end_idx == 1, "The end on the cheap column should be used"
+    assert path[0] == starts[start_idx], "Path should start at the chosen start"
+    assert path[-1] == ends[end_idx], "Path should end at the chosen end"
+    assert cost == 9, "Path should only cross cheap cells"
+
+
+def test_find_path_2d_multiple_ends_matches_single_best():
+    """Test that a multi-end search costs no more than any single-end search."""
+    rng = np.random.default_rng(2)
+    array = rng.integers(1, 255, size=(20, 20), dtype=np.uint8)
+    ends = [(19, 0), (19, 19), (0, 19)]
+
+    _, cost, _, end_idx = pathfinding_py.find_path_2d(array, (0, 0), ends, "astar")
+    single_costs = [pathfinding_py.find_path_2d(array, (0, 0), e, "dijkstra")[1] for e in ends]
+
+    assert cost == min(single_costs), "Multi-end cost should match the best single-end cost"
+    assert single_costs[end_idx] == cost, "The returned index should point at the best end"
+
+
+def test_find_path_2d_empty_starts():
+    """Test that an empty list of starts raises an error."""
+    array = np.ones((5, 5), dtype=np.uint8) * 50
+
+    with pytest.raises(ValueError):
+        pathfinding_py.find_path_2d(array, [], (4, 4), "astar")
+
+
+def test_find_path_2d_return_stats():
+    """Test that return_stats=True appends a dict of search statistics."""
+    array = np.ones((15, 15), dtype=np.uint8) * 10
+
+    for algorithm in ["astar", "dijkstra", "fringe"]:
+        result = pathfinding_py.find_path_2d(
+            array, (0, 0), (14, 14), algorithm, return_stats=True
+        )
+
+        assert result is not None, f"Path should be found with {algorithm}"
+        path, cost, stats = result
+        assert path[-1] == (14, 14), "Path should end at the end position"
+        assert set(stats) == {"nodes_expanded", "nodes_generated", "peak_frontier_size"}
+        assert stats["nodes_expanded"] > 0, "At least one node should be expanded"
+        assert stats["nodes_generated"] >= stats["nodes_expanded"], (
+            "Every expansion on an open grid generates at least one neighbour"
+        )
+        assert stats["peak_frontier_size"] > 0, "The frontier should hold nodes at some point"
+
+
+def test_find_path_2d_return_stats_astar_expands_fewer_nodes():
+    """Test that A* expands no more nodes than Dijkstra on a uniform map."""
+    array = np.ones((40, 40), dtype=np.uint8) * 10
+
+    *_, astar_stats = pathfinding_py.find_path_2d(array, (0, 0), (39, 20), "astar", return_stats=True)
+    *_, dijkstra_stats = pathfinding_py.find_path_2d(
+        array, (0, 0), (39, 20), "dijkstra", return_stats=True
+    )
+
+    assert astar_stats["nodes_expanded"] <= dijkstra_stats["nodes_expanded"]
+
+
+def test_find_path_2d_return_visited():
+    """Test that return_visited=True appends a uint8 map of the expanded cells."""
+    array = np.ones((30, 30), dtype=np.uint8) * 10
+
+    path, _, stats, visited = pathfinding_py.find_path_2d(
+        array, (0, 0), (29, 10), "dijkstra", return_stats=True, return_visited=True
+    )
+
+    assert visited.shape == array.shape, "Visited map should have the same shape as the heatmap"
+    assert visited.dtype == np.uint8, "Visited map should be uint8"
+    assert set(np.unique(visited)) <= {0, 1}, "Visited map should only hold 0 and 1"
+    assert visited[0, 0] == 1, "The start should be expanded"
+    assert visited.sum() == stats["nodes_expanded"], "Each expanded cell should be marked once"
+
+
+def test_find_path_2d_return_visited_astar_is_focused():
+    """Test that A* leaves a smaller footprint than Dijkstra on a uniform map."""
+    array = np.ones((40, 40), dtype=np.uint8) * 10
+
+    *_, astar_visited = pathfinding_py.find_path_2d(array, (0, 0), (39, 20), "astar", return_visited=True)
+    *_, dijkstra_visited = pathfinding_py.find_path_2d(array, (0, 0), (39, 20), "dijkstra", return_visited=True)
+
+    assert astar_visited.sum() <= dijkstra_visited.sum(), "A* should expand no more cells than Dijkstra"
+
+
+def test_find_path_2d_return_visited_every_algorithm():
+    """Test that every algorithm reports the cells it expands, including the start."""
+    array = np.ones((20, 20), dtype=np.uint8) * 10
+
+    for algorithm in pathfinding_py.supported_algorithms_2d():
+        *_, visited = pathfinding_py.find_path_2d(array, (0, 0), (19, 7), algorithm, return_visited=True)
+        assert visited[0, 0] == 1, f"{algorithm} should report expanding the start"
+        assert visited.sum() > 1, f"{algorithm} should report every cell it expands"
+
+
+def test_find_path_2d_return_length():
+    """Test that return_length=True appends the Euclidean length of the path, independent of its cost."""
+    array = np.ones((10, 10), dtype=np.uint8) * 50
+
+    path, cost, length = pathfinding_py.find_path_2d(array, (0, 0), (3, 5), "dijkstra", return_length=True)
+    assert cost > length, "The length should ignore the pixel values"
+    assert length == pytest.approx(3 * math.sqrt(2) + 2), "3 diagonal and 2 cardinal steps are expected"
+
+    _, _, length = pathfinding_py.find_path_2d(
+        array, (0, 0), (9, 0), "dijkstra", connectivity=4, return_length=True
+    )
+    assert length == pytest.approx(9.0), "Cardinal steps should have length 1"
+
+    _, _, length = pathfinding_py.find_path_2d(array, (0, 0), (9, 0), "dijkstra", wrap="x", return_length=True)
+    assert length == pytest.approx(1.0), "A step across a wrapped border should have length 1"
+
+
+def test_find_path_2d_return_length_single_point_and_extras():
+    """Test that a single-point path has length 0 and that the length comes after the other extras."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    assert pathfinding_py.find_path_2d(array, (2, 2), (2, 2), "astar", return_length=True) == ([(2, 2)], 0, 0.0)
+
+    result = pathfinding_py.find_path_2d(
+        array, (0, 0), (4, 4), "astar", return_stats=True, return_visited=True, return_length=True
+    )
+    assert isinstance(result[-1], float), "The length should be appended last"
+    assert result[-1] == pytest.approx(4 * math.sqrt(2)), "The diagonal path should have length 4√2"
+
+def test_find_path_2d_normalize_cost():
+    """Test that normalize_cost=True appends the mean edge cost, comparable across brightness levels."""
+    dark = np.full((10, 10), 20, dtype=np.uint8)
+    bright = np.full((20, 20), 20, dtype=np.uint8)
+
+    path, cost, mean = pathfinding_py.find_path_2d(dark, (0, 0), (0, 9), "dijkstra", normalize_cost=True)
+    assert mean == pytest.approx(cost / (len(path) - 1)), "The mean should be the cost per step"
+    assert mean == pytest.approx(20.0), "Every step enters a cell of value 20"
+
+    *_, long_mean = pathfinding_py.find_path_2d(bright, (0, 0), (0, 19), "dijkstra", normalize_cost=True)
+    assert long_mean == pytest.approx(mean), "A longer route over the same values should have the same mean"
+
+    _, cost, mean = pathfinding_py.find_path_2d(dark, (0, 0), (0, 9), "dijkstra", simplify=1.0, normalize_cost=True)
+    assert mean == pytest.approx(cost / 9), "Steps should be counted before simplification"
+
+
+def test_find_path_2d_normalize_cost_single_point():
+    """Test that a single-point path has a mean edge cost of 0 and that the mean is appended last."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    assert pathfinding_py.find_path_2d(array, (2, 2), (2, 2), "astar", normalize_cost=True) == ([(2, 2)], 0, 0.0)
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (4, 0), "astar", return_border_flag=True, normalize_cost=True)
+    assert result[-2] is True and result[-1] == pytest.approx(1.0), "The mean should come after the border flag"
+
+
+def test_find_path_2d_order_rc():
+    """Test that order="rc" takes and returns (row, col) points, i.e. the (x, y) points swapped."""
+    array = np.random.default_rng(0).integers(1, 50, size=(12, 8), dtype=np.uint8)
+
+    path, cost = pathfinding_py.find_path_2d(array, (1, 2), (10, 6), "dijkstra")
+    rc_path, rc_cost = pathfinding_py.find_path_2d(array, (2, 1), (6, 10), "dijkstra", order="rc")
+    assert rc_cost == cost, "Both orders should find the same route"
+    assert rc_path == [(y, x) for x, y in path], "The path should be returned as (row, col)"
+
+    rc_array, _ = pathfinding_py.find_path_2d(array, (2, 1), (6, 10), "dijkstra", order="RC", as_array=True)
+    assert rc_array.tolist() == [list(point) for point in rc_path], "as_array should keep the (row, col) order"
+
+
+def test_find_path_2d_order_invalid():
+    """Test that an unknown order raises a ValueError."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="Unsupported order"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", order="yx")
+
+
+def test_find_path_2d_return_border_flag():
+    """Test that return_border_flag=True tells whether the path touches the image border, for every algorithm."""
+    array = np.ones((10, 10), dtype=np.uint8)
+
+    for algorithm in pathfinding_py.supported_algorithms_2d():
+        *_, inside = pathfinding_py.find_path_2d(array, (2, 2), (7, 7), algorithm, return_border_flag=True)
+        assert inside is False, f"{algorithm}: a path away from the edges should not touch the border"
+
+        *_, touching = pathfinding_py.find_path_2d(array, (2, 2), (9, 5), algorithm, return_border_flag=True)
+        assert touching is True, f"{algorithm}: a path ending on the last column should touch the border"
+
+
+def test_find_path_2d_return_border_flag_checks_full_path():
+    """Test that the border flag sees cells dropped by simplify and ignores wrapped borders."""
+    array = np.full((10, 10), 50, dtype=np.uint8)
+    array[:, 0] = 1  # A cheap corridor along the top row
+
+    path, _, touching = pathfinding_py.find_path_2d(
+        array, (1, 3), (8, 3), "dijkstra", simplify=20.0, return_border_flag=True
+    )
+    assert all(y != 0 for _, y in path), "The simplified path should not keep the border cells"
+    assert touching is True, "The flag should be computed on the full path"
+
+    *_, touching = pathfinding_py.find_path_2d(
+        array, (1, 3), (8, 3), "dijkstra", wrap="y", return_border_flag=True
+    )
+    assert touching is False, "A border joined by wrap should not count"
+
+def test_find_path_2d_uint16():
+    """Test 2D pathfinding on a uint16 heatmap."""
+    array = np.full((10, 10), 40000, dtype=np.uint16)
+    array[:, 0] = 1000  # Cheap row at y=0
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (9, 0), "dijkstra", connectivity=4)
+
+    assert result is not None, "Path should be found"
+    path, cost = result
+    assert path == [(x, 0) for x in range(10)], "Path should follow the cheap row"
+    assert cost == 9 * 1000, "Cost should accumulate the uint16 values without truncation"
+
+
+def test_find_path_2d_uint16_matches_uint8():
+    """Test that a uint16 copy of a uint8 heatmap gives the same result."""
+    rng = np.random.default_rng(4)
+    array = rng.integers(1, 255, size=(20, 20), dtype=np.uint8)
+
+    for algorithm in ["astar", "dijkstra", "fringe"]:
+        result_u8 = pathfinding_py.find_path_2d(array, (0, 0), (19, 19), algorithm)
+        result_u16 = pathfinding_py.find_path_2d(
+            array.astype(np.uint16), (0, 0), (19, 19), algorithm
+        )
+        assert result_u8 == result_u16, f"uint8 and uint16 results should match for {algorithm}"
+
+
+def test_find_path_2d_uint16_impassable():
+    """Test that impassable values above 255 work on uint16 heatmaps."""
+    array = np.full((5, 5), 10, dtype=np.uint16)
+    array[2, :] = 1000
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", impassable=1000)
+
+    assert result is None, "No path should be found through an impassable wall"
+
+
+def test_find_path_2d_unsupported_dtype():
+    """Test that unsupported dtypes raise a ValueError."""
+    array = np.ones((5, 5), dtype=np.float64)
+
+    with pytest.raises(ValueError, match="uint8 or uint16"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar")
+
+
+def test_find_path_2d_cost_fn_inverts_costs():
+    """Test that cost_fn remaps pixel values before the search."""
+    array = np.zeros((10, 10), dtype=np.uint8)
+    array[:, 5] = 250  # Bright row is expensive as-is, cheap once inverted
+
+    result = pathfinding_py.find_path_2d(
+        array, (0, 5), (9, 5), "dijkstra", connectivity=4, cost_fn=lambda v: 255 - v
+    )
+
+    assert result is not None, "Path should be found"
+    path, cost = result
+    assert path == [(x, 5) for x in range(10)], "Path should follow the bright row"
+    assert cost == 9 * 5, "Cost should use the remapped values"
+
+
+def test_find_path_2d_cost_fn_called_once_per_value():
+    """Test that cost_fn is evaluated once per possible value, not per expansion."""
+    calls = []
+
+    def cost_fn(value):
+        calls.append(value)
+        return value**2
+
+    array = np.ones((30, 30), dtype=np.uint8) * 3
+    pathfinding_py.find_path_2d(array, (0, 0), (29, 29), "astar", cost_fn=cost_fn)
+
+    assert sorted(calls) == list(range(256)), "cost_fn should be called once for each uint8 value"
+
+
+def test_find_path_2d_cost_fn_invalid_return():
+    """Test that cost_fn returning a negative or non-integer value raises a ValueError."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    with pytest.raises(ValueError):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", cost_fn=lambda v: -1)
+
+    with pytest.raises(ValueError):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", cost_fn=lambda v: v * 0.5)
+
+
+def test_find_path_2d_cost_fn_exception_propagates():
+    """Test that an exception raised by cost_fn propagates to the caller."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    def cost_fn(value):
+        raise KeyError("boom")
+
+    with pytest.raises(KeyError):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", cost_fn=cost_fn)
+
+
+def test_find_path_2d_invert_makes_bright_cells_cheap():
+    """Test that invert costs each cell 255 - value, matching the equivalent cost_fn."""
+    array = np.zeros((10, 10), dtype=np.uint8)
+    array[:, 5] = 250
+
+    result = pathfinding_py.find_path_2d(array, (0, 5), (9, 5), "dijkstra", connectivity=4, invert=True)
+    expected = pathfinding_py.find_path_2d(
+        array, (0, 5), (9, 5), "dijkstra", connectivity=4, cost_fn=lambda v: 255 - v
+    )
+
+    assert result == expected, "invert should match cost_fn=lambda v: 255 - v"
+    assert result[1] == 9 * 5, "Cost should use the inverted values"
+    assert array[0, 5] == 250, "The input array should not be modified"
+
+
+def test_find_path_2d_invert_uint16():
+    """Test that invert uses the uint16 range for uint16 heatmaps."""
+    array = np.full((5, 5), 65535, dtype=np.uint16)
+    array[2, 2] = 65534
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (4, 0), "dijkstra", connectivity=4, invert=True)
+
+    assert result is not None, "Path should be found"
+    assert result[1] == 0, "Cells at the uint16 maximum should cost 0 once inverted"
+
+
+def test_find_path_2d_invert_with_cost_fn_and_wall_value():
+    """Test that cost_fn receives inverted values while wall_value matches the original ones."""
+    array = np.zeros((5, 5), dtype=np.uint8)
+    array[2, :4] = 255  # Free once inverted, but still a wall
+
+    result = pathfinding_py.find_path_2d(
+        array,
+        (0, 0),
+        (4, 0),
+        "dijkstra",
+        connectivity=4,
+        wall_value=255,
+        invert=True,
+        cost_fn=lambda v: v + 1,
+    )
+
+    assert result is not None, "Path should be found"
+    path, cost = result
+    assert all(x != 2 or y == 4 for x, y in path), "Path should go around the wall"
+    assert cost == 256 * (len(path) - 1), "cost_fn should receive 255 - value"
+
+
+def test_find_path_2d_cost_power():
+    """Test that cost_power raises each step cost to the given power."""
+    array = np.full((5, 5), 3, dtype=np.uint8)
+
+    _, cost = pathfinding_py.find_path_2d(array, (0, 0), (4, 0), "dijkstra", connectivity=4, cost_power=2.0)
+    assert cost == 4 * 9, "Each step should cost 3 ** 2"
+
+    _, cost = pathfinding_py.find_path_2d(array, (0, 0), (4, 0), "dijkstra", connectivity=4, cost_power=0.5)
+    assert cost == 4 * round(3**0.5), "Each step should cost round(3 ** 0.5)"
+
+    default = pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar")
+    assert pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", cost_power=1.0) == default
+
+
+def test_find_path_2d_cost_power_sharpens_bright_regions():
+    """Test that a high cost_power makes the path detour around a moderately bright region."""
+    array = np.ones((9, 9), dtype=np.uint8)
+    array[4, 1:] = 3  # Cheaper to cross than to detour around, until costs are sharpened
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 8), (8, 8), "dijkstra", connectivity=4)
+    assert (4, 8) in path, "Without cost_power the path should cross the bright row"
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 8), (8, 8), "dijkstra", connectivity=4, cost_power=4.0)
+    assert (4, 0) in path, "With cost_power the path should go around the bright row"
+
+
+def test_find_path_2d_invalid_cost_power():
+    """Test that a zero, negative, or non-finite cost_power raises a ValueError."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    for cost_power in (0.0, -1.0, float("nan"), float("inf")):
+        with pytest.raises(ValueError, match="cost_power"):
+            pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", cost_power=cost_power)
+
+
+def _count_turns(path):
+    steps = [(b[0] - a[0], b[1] - a[1]) for a, b in zip(path, path[1:])]
+    return sum(1 for a, b in zip(steps, steps[1:]) if a != b)
+
+
+def test_find_path_2d_turn_penalty_straightens_path():
+    """Test that turn_penalty removes zig-zags and is included in the cost."""
+    array = np.ones((10, 10), dtype=np.uint8)
+
+    for algorithm in ("astar", "dijkstra", "fringe"):
+        path, cost, segment_costs = pathfinding_py.find_path_2d(
+            array, (0, 0), (6, 4), algorithm, connectivity=4, turn_penalty=5, return_segment_costs=True
+        )
+
+        assert _count_turns(path) == 1, f"{algorithm}: path should turn once"
+        assert cost == 10 + 5, f"{algorithm}: cost should include one turn penalty"
+        assert sum(segment_costs) == cost, f"{algorithm}: edge costs should include the penalty"
+
+
+def test_find_path_2d_turn_penalty_zero_matches_default():
+    """Test that turn_penalty=0 is the default behavior."""
+    rng = np.random.default_rng(3)
+    array = rng.integers(1, 256, size=(20, 20), dtype=np.uint8)
+
+    default = pathfinding_py.find_path_2d(array, (0, 0), (19, 19), "dijkstra")
+    assert pathfinding_py.find_path_2d(array, (0, 0), (19, 19), "dijkstra", turn_penalty=0) == default
+
+
+def test_find_path_2d_turn_penalty_unsupported_algorithms():
+    """Test that algorithms that cannot penalize turns raise a ValueError."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    for algorithm in ("dijkstra_bi", "theta", "jps"):
+        with pytest.raises(ValueError, match="turn_penalty"):
+            pathfinding_py.find_path_2d(array, (0, 0), (4, 4), algorithm, turn_penalty=1)
+
+
+def test_find_path_2d_dir_weights():
+    """Test that dir_weights scales the cost of steps in each direction."""
+    array = np.full((5, 5), 10, dtype=np.uint8)
+    weights = {"up": 2.0, "down": 1.0, "left": 1.0, "right": 0.5}
+
+    _, up_cost = pathfinding_py.find_path_2d(array, (2, 4), (2, 0), "dijkstra", connectivity=4, dir_weights=weights)
+    _, down_cost = pathfinding_py.find_path_2d(array, (2, 0), (2, 4), "dijkstra", connectivity=4, dir_weights=weights)
+    _, right_cost = pathfinding_py.find_path_2d(array, (0, 2), (4, 2), "dijkstra", connectivity=4, dir_weights=weights)
+
+    assert up_cost == 4 * 20, "Moving up should cost twice as much"
+    assert down_cost == 4 * 10, "Moving down should keep the pixel cost"
+    assert right_cost == 4 * 5, "Moving right should cost half as much"
+
+
+def test_find_path_2d_free_value():
+    """Test that free_value cells cost free_cost whatever their intensity, e.g. a road through terrain."""
+    array = np.full((10, 3), 50, dtype=np.uint8)
+    array[:, 2] = 200  # A bright road, expensive as plain intensity
+
+    path, cost = pathfinding_py.find_path_2d(array, (0, 2), (9, 2), "dijkstra", connectivity=4, free_value=200)
+    assert path == [(x, 2) for x in range(10)] and cost == 0, "The road should be free to follow"
+
+    _, cost = pathfinding_py.find_path_2d(
+        array, (0, 2), (9, 2), "dijkstra", connectivity=4, free_value=200, free_cost=3, invert=True
+    )
+    assert cost == 27, "free_cost should apply as it is, without invert"
+
+
+def test_find_path_2d_free_value_walls_take_precedence():
+    """Test that a cell matching both free_value and wall_value stays a wall, and that free_value must fit uint8."""
+    array = np.full((5, 5), 9, dtype=np.uint8)
+    array[2, :] = 0
+
+    assert pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", wall_value=0, free_value=0) is None
+
+    with pytest.raises(ValueError, match="Free value 300"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", free_value=300)
+
+
+def test_find_path_2d_no_corner_cutting():
+    """Test that no_corner_cutting=True stops diagonal steps between two walls touching at a corner."""
+    array = np.ones((3, 3), dtype=np.uint8)
+    array[1, 0] = 0
+    array[0, 1] = 0
+
+    for algorithm in pathfinding_py.supported_algorithms_2d():
+        if algorithm == "jps":
+            continue
+        cutting = pathfinding_py.find_path_2d(array, (0, 0), (2, 2), algorithm, impassable=0)
+        assert cutting is not None, f"{algorithm}: by default the path should cut the corner"
+
+        blocked = pathfinding_py.find_path_2d(array, (0, 0), (2, 2), algorithm, impassable=0, no_corner_cutting=True)
+        assert blocked is None, f"{algorithm}: the only way out squeezes between the walls"
+
+    assert not pathfinding_py.is_reachable_2d(array, (0, 0), (2, 2), impassable=0, no_corner_cutting=True)
+
+
+def test_find_path_2d_no_corner_cutting_single_wall_and_jps():
+    """Test that one blocked corner is enough to forbid a diagonal step, and that jps rejects the option."""
+    array = np.ones((3, 3), dtype=np.uint8)
+    mask = np.zeros((3, 3), dtype=bool)
+    mask[1, 0] = True
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 0), (1, 1), "dijkstra", mask=mask, no_corner_cutting=True)
+    assert path == [(0, 0), (0, 1), (1, 1)], "The path should go around the masked corner"
+
+    with pytest.raises(ValueError, match="no_corner_cutting"):
+        pathfinding_py.find_path_2d(array, (0, 0), (2, 2), "jps", no_corner_cutting=True)
+
+
+def test_find_path_2d_speed_map():
+    """Test that a speed map divides cell costs, so the search prefers a fast road over a shorter detour."""
+    array = np.full((5, 5), 40, dtype=np.uint8)
+    speed = np.ones((5, 5), dtype=np.uint8)
+    speed[:, 0] = 4
+
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (4, 0), "dijkstra", connectivity=4, speed=speed)
+    assert path == [(x, 0) for x in range(5)], "The path should follow the fast row"
+    assert cost == 4 * (40 // 4), "Each step should cost the cell cost divided by its speed"
+
+    speed[2, 0] = 0
+    path, _ = pathfinding_py.find_path_2d(array, (0, 0), (4, 0), "dijkstra", connectivity=4, speed=speed)
+    assert (2, 0) not in path, "A cell with zero speed should be impassable"
+
+
+def test_find_path_2d_speed_map_validation():
+    """Test that a speed map of another shape is rejected, as is jps."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="Speed map shape"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", speed=np.ones((5, 4), dtype=np.uint8))
+    with pytest.raises(ValueError, match="speed=None"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "jps", speed=np.ones((5, 5), dtype=np.uint8))
+
+
+def test_find_path_2d_neighbors_knight_moves():
+    """Test that a knight-move kernel only takes knight moves, each costing its Euclidean length."""
+    array = np.full((8, 8), 10, dtype=np.uint8)
+    knight = [(1, 2), (2, 1), (-1, 2), (-2, 1), (1, -2), (2, -1), (-1, -2), (-2, -1)]
+
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (2, 1), "dijkstra", neighbors=knight)
+    assert path == [(0, 0), (2, 1)], "A single knight move should reach the end"
+    assert cost == round(10 * 5**0.5), "A knight move should cost √5 times the pixel cost"
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 0), (7, 7), "astar", heuristic="euclidean", neighbors=knight)
+    for (x0, y0), (x1, y1) in zip(path, path[1:]):
+        assert sorted((abs(x1 - x0), abs(y1 - y0))) == [1, 2], "Every step should be a knight move"
+
+    four = pathfinding_py.find_path_2d(array, (0, 0), (3, 3), "dijkstra", connectivity=4)
+    custom = pathfinding_py.find_path_2d(array, (0, 0), (3, 3), "dijkstra", neighbors=[(-1, 0), (1, 0), (0, -1), (0, 1)])
+    assert custom == four, "A kernel of the cardinal moves should match 4-connectivity"
+
+
+def test_find_path_2d_neighbors_validation():
+    """Test that zero and duplicate offsets are rejected, as are jps and theta."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="nonzero"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "dijkstra", neighbors=[(1, 0), (0, 0)])
+    with pytest.raises(ValueError, match="more than once"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "dijkstra", neighbors=[(1, 0), (1, 0)])
+    with pytest.raises(ValueError, match="neighbors"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "jps", neighbors=[(1, 0), (0, 1)])
+    with pytest.raises(ValueError, match="theta"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "theta", neighbors=[(1, 0), (0, 1)])
+
+
+def test_find_path_2d_reach_jumps_costly_barriers():
+    """Test that reach lets paths jump a thin costly barrier for the landing cell's cost per cell."""
+    array = np.full((10, 5), 10, dtype=np.uint8)
+    array[5, :] = 250  # A costly ridge one cell thick
+
+    _, walk = pathfinding_py.find_path_2d(array, (0, 2), (9, 2), "dijkstra")
+    assert walk > 250, "Walking should pay for the ridge"
+
+    path, jump = pathfinding_py.find_path_2d(array, (0, 2), (9, 2), "dijkstra", reach=2)
+    assert jump == 90, "A jump of two cells should cost twice the cell it lands on"
+    assert jump < walk, "Jumping the ridge should be cheaper than walking through it"
+    assert any(x0 < 5 < x1 for (x0, _), (x1, _) in zip(path, path[1:])), "One step should jump the ridge"
+
+    uniform = np.full((15, 12), 10, dtype=np.uint8)
+    _, walk = pathfinding_py.find_path_2d(uniform, (0, 0), (14, 11), "dijkstra")
+    _, jump = pathfinding_py.find_path_2d(uniform, (0, 0), (14, 11), "dijkstra", reach=4)
+    assert jump == walk, "On a uniform map, jumping should cost the same as walking"
+
+
+def test_find_path_2d_reach_never_crosses_blocked_cells():
+    """Test that jumps stop at impassable, masked, and out-of-range cells like single steps."""
+    array = np.full((10, 5), 10, dtype=np.uint8)
+    wall = array.copy()
+    wall[5, :] = 0
+    mask = np.zeros((10, 5), dtype=bool)
+    mask[5, :] = True
+    ridge = array.copy()
+    ridge[5, :] = 250
+
+    assert pathfinding_py.find_path_2d(wall, (0, 2), (9, 2), "dijkstra", impassable=0, reach=3) is None
+    assert pathfinding_py.find_path_2d(array, (0, 2), (9, 2), "dijkstra", mask=mask, reach=3) is None
+    assert pathfinding_py.find_path_2d(ridge, (0, 2), (9, 2), "astar", block_above=200, reach=3) is None
+
+
+def test_find_path_2d_reach_validation():
+    """Test that reach=0 is rejected, as are neighbors, jps, and theta with a reach above 1."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    assert pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "jps", reach=1) is not None
+    with pytest.raises(ValueError, match="at least 1"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "dijkstra", reach=0)
+    with pytest.raises(ValueError, match="neighbors"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "dijkstra", reach=2, neighbors=[(1, 0), (0, 1)])
+    with pytest.raises(ValueError, match="reach=1"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "jps", reach=2)
+    with pytest.raises(ValueError, match="theta"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "theta", reach=2)
+
+
+def test_find_path_2d_dir_weights_default_matches_symmetric():
+    """Test that weights of 1.0 everywhere match the unweighted search."""
+    rng = np.random.default_rng(5)
+    array = rng.integers(1, 256, size=(20, 20), dtype=np.uint8)
+    weights = {"up": 1.0, "down": 1.0, "left": 1.0, "right": 1.0}
+
+    expected = pathfinding_py.find_path_2d(array, (0, 0), (19, 19), "astar")
+    assert pathfinding_py.find_path_2d(array, (0, 0), (19, 19), "astar", dir_weights=weights) == expected
+
+
+def test_find_path_2d_dir_weights_invalid():
+    """Test that unknown directions and negative weights raise a ValueError."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="Unknown direction"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", dir_weights={"north": 2.0})
+
+    with pytest.raises(ValueError, match="non-negative"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", dir_weights={"up": -1.0})
+
+
+def test_find_path_2d_dijkstra_bi_matches_dijkstra_on_random_maps():
+    """Test that bidirectional Dijkstra finds the same optimal cost as Dijkstra."""
+    rng = np.random.default_rng(42)
+
+    for _ in range(20):
+        width, height = rng.integers(5, 40, size=2)
+        array = rng.integers(0, 256, size=(width, height), dtype=np.uint8)
+        start = (int(rng.integers(width)), int(rng.integers(height)))
+        end = (int(rng.integers(width)), int(rng.integers(height)))
+
+        for connectivity in (4, 8):
+            for impassable in (None, 0):
+                array[start] = array[end] = 1
+                kwargs = {"connectivity": connectivity, "impassable": impassable}
+
+                expected = pathfinding_py.find_path_2d(array, start, end, "dijkstra", **kwargs)
+                result = pathfinding_py.find_path_2d(array, start, end, "dijkstra_bi", **kwargs)
+
+                if expected is None:
+                    assert result is None, "Bidirectional search should agree when no path exists"
+                    continue
+
+                path, cost = result
+                assert cost == expected[1], "Bidirectional search should find the optimal cost"
+                assert path[0] == start, "Path should start at the start position"
+                assert path[-1] == end, "Path should end at the end position"
+                for (x1, y1), (x2, y2) in zip(path, path[1:]):
+                    assert max(abs(x1 - x2), abs(y1 - y2)) == 1, "Path steps should be adjacent"
+
+
+def test_find_path_2d_ordered_start_to_end():
+    """Test that every algorithm returns its path ordered from start to end, in every output form."""
+    rng = np.random.default_rng(7)
+    array = (rng.random((30, 30)) > 0.25).astype(np.uint8)  # 1 is free, 0 is blocked
+
+    for _ in range(10):
+        start = (int(rng.integers(30)), int(rng.integers(30)))
+        end = (int(rng.integers(30)), int(rng.integers(30)))
+        array[start] = array[end] = 1
+
+        for algorithm in pathfinding_py.supported_algorithms_2d():
+            result = pathfinding_py.find_path_2d(array, start, end, algorithm, impassable=0)
+            if result is None:
+                continue
+
+            path, _ = result
+            assert path[0] == start, f"{algorithm}: path[0] should be the start"
+            assert path[-1] == end, f"{algorithm}: path[-1] should be the end"
+
+            points, _ = pathfinding_py.find_path_2d(array, start, end, algorithm, impassable=0, as_array=True)
+            assert tuple(points[0]) == start and tuple(points[-1]) == end, f"{algorithm}: array rows should be ordered"
+
+            simplified, _ = pathfinding_py.find_path_2d(array, end, start, algorithm, impassable=0, simplify=2.0)
+            assert simplified[0] == end and simplified[-1] == start, f"{algorithm}: swapped endpoints should swap the order"
+
+
+def test_find_path_2d_dijkstra_bi_multiple_ends():
+    """Test that bidirectional Dijkstra picks the cheapest of several end positions."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+
+    path, cost, start_idx, end_idx = pathfinding_py.find_path_2d(
+        array, (0, 0), [(9, 9), (2, 0)], "dijkstra_bi"
+    )
+
+    assert end_idx == 1, "The nearer end should be chosen"
+    assert path[-1] == (2, 0), "Path should end at the nearer end"
+    assert cost == 20, "Two cardinal steps over cost-10 cells should cost 20"
+
+
+def test_find_path_2d_jps_matches_dijkstra_on_binary_maps():
+    """Test that Jump Point Search finds optimal paths on uniform-cost grids."""
+    rng = np.random.default_rng(7)
+
+    for _ in range(20):
+        array = np.where(rng.random((30, 30)) < 0.3, 0, 10).astype(np.uint8)
+        array[0, 0] = array[29, 29] = 10
+
+        expected = pathfinding_py.find_path_2d(array, (0, 0), (29, 29), "dijkstra", impassable=0)
+        result = pathfinding_py.find_path_2d(array, (0, 0), (29, 29), "jps", impassable=0)
+
+        if expected is None:
+            assert result is None, "JPS should agree when no path exists"
+            continue
+
+        path, cost = result
+        assert cost == expected[1], "JPS should find the optimal cost"
+        assert path[0] == (0, 0) and path[-1] == (29, 29), "Path should connect start and end"
+        for (x1, y1), (x2, y2) in zip(path, path[1:]):
+            assert max(abs(x1 - x2), abs(y1 - y2)) == 1, "Path should list every cell"
+            assert array[x2, y2] != 0, "Path should avoid impassable cells"
+
+
+def test_find_path_2d_jps_with_mask():
+    """Test that Jump Point Search treats masked cells as blocked."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+    mask = np.zeros((10, 10), dtype=bool)
+    mask[5, :9] = True
+
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (9, 0), "jps", mask=mask)
+    expected = pathfinding_py.find_path_2d(array, (0, 0), (9, 0), "dijkstra", mask=mask)
+
+    assert cost == expected[1], "JPS should find the optimal cost around the wall"
+    assert all(not mask[p] for p in path), "Path should avoid masked cells"
+
+
+def test_find_path_2d_jps_rejects_non_uniform_costs():
+    """Test that Jump Point Search raises ValueError on heatmaps with varying costs."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+    array[3, 3] = 20
+
+    with pytest.raises(ValueError, match="astar"):
+        pathfinding_py.find_path_2d(array, (0, 0), (9, 9), "jps")
+
+
+def test_find_path_2d_jps_uniform_dir_weights_match_dijkstra():
+    """Test that Jump Point Search scales its costs by a uniform direction weight other than 1."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+
+    for weight in [2.0, 0.5, 1.3]:
+        weights = {"up": weight, "down": weight, "left": weight, "right": weight}
+        _, jps_cost = pathfinding_py.find_path_2d(array, (0, 0), (9, 4), "jps", dir_weights=weights)
+        _, dijkstra_cost = pathfinding_py.find_path_2d(array, (0, 0), (9, 4), "dijkstra", dir_weights=weights)
+        assert jps_cost == dijkstra_cost, f"JPS should match Dijkstra with every direction weighing {weight}"
+
+
+def test_find_path_2d_jps_rejects_four_connectivity():
+    """Test that Jump Point Search raises ValueError when connectivity is 4."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+
+    with pytest.raises(ValueError, match="connectivity=8"):
+        pathfinding_py.find_path_2d(array, (0, 0), (9, 9), "jps", connectivity=4)
+
+
+def test_find_path_2d_theta_straight_line_of_sight():
+    """Test that Theta* returns a single straight segment across an open map."""
+    array = np.ones((30, 30), dtype=np.uint8) * 10
+
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (20, 7), "theta")
+    _, astar_cost = pathfinding_py.find_path_2d(array, (0, 0), (20, 7), "astar")
+
+    assert path == [(0, 0), (20, 7)], "An unobstructed path should be a single segment"
+    assert cost <= astar_cost, "Theta* should not cost more than the grid path on an open map"
+
+
+def test_find_path_2d_theta_fewer_waypoints_than_astar():
+    """Test that Theta* returns far fewer waypoints than the grid-constrained A* path."""
+    array = np.ones((40, 40), dtype=np.uint8) * 10
+    array[20, :30] = 0
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 0), (39, 0), "theta", impassable=0)
+    grid_path, _ = pathfinding_py.find_path_2d(array, (0, 0), (39, 0), "astar", impassable=0)
+
+    assert path[0] == (0, 0) and path[-1] == (39, 0), "Path should connect start and end"
+    assert len(path) < len(grid_path) // 4, "Theta* should need far fewer waypoints"
+
+
+def test_find_path_2d_theta_block_threshold():
+    """Test that cells above block_threshold occlude line of sight."""
+    array = np.ones((30, 30), dtype=np.uint8) * 10
+    array[10, 5] = 11
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 5), (20, 5), "theta")
+    assert path == [(0, 5), (20, 5)], "Without a threshold the brighter cell does not occlude"
+
+    path, _ = pathfinding_py.find_path_2d(array, (0, 5), (20, 5), "theta", block_threshold=10)
+    assert len(path) > 2, "The brighter cell should occlude the straight segment"
+
+
+def _three_tube_map():
+    """A map of three horizontal tubes joined at both ends, everything else impassable."""
+    array = np.zeros((21, 21), dtype=np.uint8)
+    array[0, 2:19] = 10
+    array[20, 2:19] = 10
+    array[:, [2, 10, 18]] = 10
+    return array
+
+
+def test_iter_path_2d_matches_find_path_2d():
+    """Test that iter_path_2d yields the points of the find_path_2d path lazily and exposes its cost."""
+    array = np.random.default_rng(1).integers(1, 50, size=(15, 10), dtype=np.uint8)
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (14, 9), "astar")
+
+    points = pathfinding_py.iter_path_2d(array, (0, 0), (14, 9), "astar")
+    assert points.cost == cost, "The iterator should carry the cost of the path"
+    assert next(points) == (0, 0), "The first point should be the start"
+    assert [(0, 0)] + list(points) == path, "The remaining points should follow the path"
+    assert list(points) == [], "An exhausted iterator should yield nothing more"
+
+
+def test_iter_path_2d_no_path():
+    """Test that iter_path_2d returns None when the end cannot be reached."""
+    array = np.ones((5, 5), dtype=np.uint8)
+    array[2, :] = 0
+
+    assert pathfinding_py.iter_path_2d(array, (0, 0), (4, 4), "dijkstra", impassable=0) is None
+
+
+def test_path_cost_2d_matches_find_path_2d():
+    """Test that path_cost_2d returns the cost find_path_2d finds, for every algorithm."""
+    rng = np.random.default_rng(4)
+    array = rng.integers(0, 50, size=(20, 15), dtype=np.uint8)
+    mask = rng.random((20, 15)) < 0.1
+    mask[0, 0] = mask[19, 14] = False
+    array[0, 0] = array[19, 14] = 1
+
+    for algorithm in pathfinding_py.supported_algorithms_2d():
+        if algorithm == "jps":
+            continue
+        expected = pathfinding_py.find_path_2d(array, (0, 0), (19, 14), algorithm, impassable=0, mask=mask)
+        cost = pathfinding_py.path_cost_2d(array, (0, 0), (19, 14), algorithm, impassable=0, mask=mask)
+        assert cost == (expected and expected[1]), f"{algorithm}: the cost should match find_path_2d"
+
+    for round in range(100):
+        array = rng.integers(0, 10, size=(12, 9), dtype=np.uint8)
+        for heuristic, weight in [("manhattan", 1.0), ("octile", 1.0), ("euclidean", 2.5), ("zero", 1.0)]:
+            expected = pathfinding_py.find_path_2d(array, (0, 0), (11, 8), "astar", heuristic=heuristic, weight=weight)
+            cost = pathfinding_py.path_cost_2d(array, (0, 0), (11, 8), "astar", heuristic=heuristic, weight=weight)
+            assert cost == expected[1], f"Map {round}, {heuristic}: the cost should match find_path_2d"
+
+
+def test_path_cost_2d_no_path_and_validation():
+    """Test that path_cost_2d returns None for unreachable ends and validates like find_path_2d."""
+    array = np.ones((5, 5), dtype=np.uint8)
+    array[2, :] = 0
+
+    assert pathfinding_py.path_cost_2d(array, (0, 0), (4, 4), "dijkstra", impassable=0) is None
+    assert pathfinding_py.path_cost_2d(array, (0, 0), (4, 4), "astar", max_cost=1) is None
+
+    with pytest.raises(ValueError, match="out of bounds"):
+        pathfinding_py.path_cost_2d(array, (0, 0), (5, 0), "dijkstra")
+    with pytest.raises(ValueError, match="lies on a wall"):
+        pathfinding_py.path_cost_2d(array, (0, 0), (2, 2), "dijkstra", wall_value=0)
+
+
+def test_find_k_paths_2d_returns_distinct_paths_in_cost_order():
+    """Test that find_k_paths_2d returns alternative routes, cheapest first."""
+    array = _three_tube_map()
+
+    paths = pathfinding_py.find_k_paths_2d(array, (0, 10), (20, 10), 3, "dijkstra", impassable=0)
+
+    assert len(paths) == 3, "Each tube should give one path"
+    costs = [cost for _, cost in paths]
+    assert costs == sorted(costs), "Paths should be in increasing cost order"
+
+    first_path, first_cost = paths[0]
+    _, optimal_cost = pathfinding_py.find_path_2d(array, (0, 10), (20, 10), "dijkstra", impassable=0)
+    assert first_cost == optimal_cost, "The first path should be the optimal one"
+    assert (10, 10) in first_path, "The optimal path should use the middle tube"
+
+    tubes = {next(y for x, y in path if x == 10) for path, _ in paths}
+    assert tubes == {2, 10, 18}, "Each path should use a different tube"
+    for path, _ in paths:
+        assert path[0] == (0, 10) and path[-1] == (20, 10), "Paths should connect start and end"
+
+
+def test_find_k_paths_2d_random_maps_in_cost_order():
+    """Test that find_k_paths_2d returns paths cheapest first on random 8-connected maps."""
+    rng = np.random.default_rng(14)
+
+    for round in range(200):
+        width, height = rng.integers(2, 8, size=2)
+        array = rng.integers(1, 10, size=(width, height), dtype=np.uint8)
+        end = (int(width) - 1, int(height) - 1)
+
+        paths = pathfinding_py.find_k_paths_2d(array, (0, 0), end, 5, "dijkstra", max_shared_fraction=1.0)
+        costs = [cost for _, cost in paths]
+        assert costs == sorted(costs), f"Map {round} should return paths in increasing cost order"
+
+
+def test_find_k_paths_2d_returns_fewer_when_not_enough_exist():
+    """Test that find_k_paths_2d returns what is available when fewer than k distinct paths exist."""
+    array = _three_tube_map()
+
+    paths = pathfinding_py.find_k_paths_2d(array, (0, 10), (20, 10), 10, "astar", impassable=0)
+
+    assert len(paths) == 3, "Only three distinct paths exist"
+
+
+def test_find_k_paths_2d_max_shared_fraction():
+    """Test that max_shared_fraction limits how many edges paths may share."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+
+    paths = pathfinding_py.find_k_paths_2d(
+        array, (0, 0), (9, 0), 3, "dijkstra", max_shared_fraction=1.0
+    )
+    assert len(paths) == 3, "With no overlap limit any alternative is accepted"
+
+    for path, _ in paths[1:]:
+        edges = {frozenset(step) for step in zip(path, path[1:])}
+        first_edges = {frozenset(step) for step in zip(paths[0][0], paths[0][0][1:])}
+        assert edges != first_edges, "Alternatives should differ from the optimal path"
+
+
+def test_find_k_paths_2d_no_path():
+    """Test that find_k_paths_2d returns an empty list when no path exists."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+    array[5, :] = 0
+
+    paths = pathfinding_py.find_k_paths_2d(array, (0, 0), (9, 9), 3, "astar", impassable=0)
+
+    assert paths == [], "No paths should be returned"
+
+
+def test_find_k_paths_2d_rejects_theta():
+    """Test that find_k_paths_2d raises ValueError for any-angle pathfinders."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+
+    with pytest.raises(ValueError):
+        pathfinding_py.find_k_paths_2d(array, (0, 0), (9, 9), 3, "theta")
+
+
+def test_find_disjoint_path_2d_shares_no_cell_or_step():
+    """Test that node-disjoint paths share only their endpoints and edge-disjoint paths share no step."""
+    rng = np.random.default_rng(11)
+    array = rng.integers(1, 100, size=(20, 15), dtype=np.uint8)
+    start, end = (0, 7), (19, 7)
+
+    main, _ = pathfinding_py.find_path_2d(array, start, end, "dijkstra")
+    main_steps = {frozenset(step) for step in zip(main, main[1:])}
+
+    backup, _ = pathfinding_py.find_disjoint_path_2d(array, start, end, main, "astar")
+    assert backup[0] == start and backup[-1] == end, "The backup should join the same endpoints"
+    assert not set(backup[1:-1]) & set(main), "A node-disjoint path should share no inner cell"
+
+    crossing, _ = pathfinding_py.find_disjoint_path_2d(array, start, end, main, "dijkstra", disjoint="edges")
+    assert not {frozenset(step) for step in zip(crossing, crossing[1:])} & main_steps, "No step should be reused"
+
+
+def test_find_disjoint_path_2d_no_route_and_validation():
+    """Test that None is returned when the avoided path is the only route, and that bad input is rejected."""
+    array = np.ones((5, 3), dtype=np.uint8)
+    array[:, [0, 2]] = 0  # A single corridor along y = 1
+    corridor = [(x, 1) for x in range(5)]
+
+    assert pathfinding_py.find_disjoint_path_2d(array, (0, 1), (4, 1), corridor, "astar", impassable=0) is None
+
+    with pytest.raises(ValueError, match="adjacent cells"):
+        pathfinding_py.find_disjoint_path_2d(array, (0, 1), (4, 1), [(0, 1), (4, 1)], "astar")
+    with pytest.raises(ValueError, match="jps"):
+        pathfinding_py.find_disjoint_path_2d(array, (0, 1), (4, 1), corridor, "jps", disjoint="edges")
+
+
+def test_find_disjoint_path_2d_jps_avoids_direct_step():
+    """Test that jps never returns the avoided step between adjacent start and end cells."""
+    array = np.ones((4, 4), dtype=np.uint8)
+    avoid = [(1, 1), (2, 1)]
+
+    expected = pathfinding_py.find_disjoint_path_2d(array, (1, 1), (2, 1), avoid, "astar")
+    result = pathfinding_py.find_disjoint_path_2d(array, (1, 1), (2, 1), avoid, "jps")
+
+    assert result[0] != avoid, "The avoided step should not be taken"
+    assert len(result[0]) == 3, "The detour should go around the avoided step"
+    assert result[1] == expected[1], "jps should cost as much as astar"
+
+
+def test_compute_distance_field_2d_matches_find_path_costs():
+    """Test that each cell of the distance field holds the optimal path cost from the source."""
+    rng = np.random.default_rng(3)
+    array = rng.integers(1, 256, size=(12, 9), dtype=np.uint8)
+
+    field = pathfinding_py.compute_distance_field_2d(array, (2, 3))
+
+    assert field.shape == array.shape, "Field should have the same shape as the heatmap"
+    assert field.dtype == np.uint32, "Field should be uint32"
+    assert field[2, 3] == 0, "The source should cost nothing to reach"
+    for end in [(0, 0), (11, 8), (7, 4)]:
+        _, cost = pathfinding_py.find_path_2d(array, (2, 3), end, "dijkstra")
+        assert field[end] == cost, f"Field should hold the optimal cost to {end}"
+
+
+def test_compute_distance_field_2d_unreachable_sentinel():
+    """Test that unreachable cells hold the uint32 maximum."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+    array[5, :] = 0
+
+    field = pathfinding_py.compute_distance_field_2d(array, (0, 0), impassable=0)
+
+    unreachable = np.iinfo(np.uint32).max
+    assert (field[6:, :] == unreachable).all(), "Cells behind the wall should be unreachable"
+    assert (field[5, :] == unreachable).all(), "Impassable cells should be unreachable"
+    assert (field[:5, :] < unreachable).all(), "Cells before the wall should be reachable"
+
+
+def test_compute_distance_field_2d_invalid_source():
+    """Test that an out-of-bounds source raises ValueError."""
+    array = np.ones((5, 5), dtype=np.uint8) * 10
+
+    with pytest.raises(ValueError):
+        pathfinding_py.compute_distance_field_2d(array, (5, 0))
+
+
+def test_compute_flow_field_2d_leads_to_goal():
+    """Test that following the flow field from any cell reaches the goal at the optimal cost."""
+    rng = np.random.default_rng(4)
+    array = rng.integers(1, 256, size=(10, 8), dtype=np.uint8)
+    goal = (6, 2)
+    steps = {0: (-1, 0), 1: (1, 0), 2: (0, -1), 3: (0, 1)}
+
+    field = pathfinding_py.compute_flow_field_2d(array, goal)
+
+    assert field.shape == array.shape, "Field should have the same shape as the heatmap"
+    assert field.dtype == np.uint8, "Field should be uint8"
+    assert field[goal] == 255, "The goal should have no direction"
+    for start in [(0, 0), (9, 7), (3, 5)]:
+        pos, cost = start, 0
+        while pos != goal:
+            dx, dy = steps[int(field[pos])]
+            pos = (pos[0] + dx, pos[1] + dy)
+            cost += int(array[pos])
+        _, expected = pathfinding_py.find_path_2d(array, start, goal, "dijkstra", connectivity=4)
+        assert cost == expected, f"Following the field from {start} should be optimal"
+
+
+def test_compute_flow_field_2d_unreachable_sentinel():
+    """Test that cells that cannot reach the goal hold 255."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+    array[5, :] = 0
+
+    field = pathfinding_py.compute_flow_field_2d(array, (0, 0), impassable=0)
+
+    assert (field[5:, :] == 255).all(), "Cells behind and on the wall should have no direction"
+    assert (field[:5, :] < 4).sum() == 49, "Every other cell before the wall should have a direction"
+
+
+def test_compute_flow_field_2d_invalid_goal():
+    """Test that an out-of-bounds goal raises ValueError."""
+    array = np.ones((5, 5), dtype=np.uint8) * 10
+
+    with pytest.raises(ValueError):
+        pathfinding_py.compute_flow_field_2d(array, (0, 5))
+
+
+def test_find_path_to_region_2d_reaches_nearest_goal():
+    """Test that the path ends on the goal cell that is cheapest to reach."""
+    array = np.ones((20, 20), dtype=np.uint8)
+    goal_mask = np.zeros((20, 20), dtype=bool)
+    goal_mask[15:, 15:] = True
+    goal_mask[5, 0] = True
+
+    result = pathfinding_py.find_path_to_region_2d(array, (0, 0), goal_mask, connectivity=4)
+
+    assert result is not None, "A goal should be reached"
+    path, cost, reached = result
+    assert reached == (5, 0), "The nearest goal cell should be reached"
+    assert path[0] == (0, 0) and path[-1] == reached, "Path should run from the start to the goal"
+    assert cost == 5, "Cost should be that of the path to the nearest goal"
+
+
+def test_find_path_to_region_2d_matches_find_path_2d():
+    """Test that the cost matches find_path_2d with every goal cell as a candidate end."""
+    rng = np.random.default_rng(7)
+    array = rng.integers(1, 256, size=(25, 25), dtype=np.uint8)
+    goal_mask = np.zeros((25, 25), dtype=bool)
+    goal_mask[20:, 3:9] = True
+    ends = [(int(x), int(y)) for x, y in zip(*np.nonzero(goal_mask))]
+
+    _, cost, _ = pathfinding_py.find_path_to_region_2d(array, (2, 20), goal_mask)
+    _, expected_cost, _, _ = pathfinding_py.find_path_2d(array, (2, 20), ends, "dijkstra")
+
+    assert cost == expected_cost, "Region search should find the same cost as searching every goal"
+
+
+def test_find_path_to_region_2d_unreachable_and_invalid():
+    """Test that an unreachable or empty region returns None and a mismatched mask raises."""
+    array = np.ones((5, 5), dtype=np.uint8)
+    array[2, :] = 0
+    goal_mask = np.zeros((5, 5), dtype=bool)
+
+    assert pathfinding_py.find_path_to_region_2d(array, (0, 0), goal_mask) is None, "An empty region is never reached"
+
+    goal_mask[4, 4] = True
+    assert pathfinding_py.find_path_to_region_2d(array, (0, 0), goal_mask, impassable=0) is None
+
+    with pytest.raises(ValueError, match="Goal mask shape"):
+        pathfinding_py.find_path_to_region_2d(array, (0, 0), np.zeros((4, 5), dtype=bool))
+
+
+def test_find_path_float_2d_keeps_fractional_costs():
+    """Test that find_path_float_2d follows gradients that quantizing to uint8 would erase."""
+    array = np.full((3, 5), 0.5, dtype=np.float32)
+    array[1, :] = 0.001  # A corridor only slightly cheaper than its surroundings
+
+    path, cost = pathfinding_py.find_path_float_2d(array, (1, 0), (1, 4), connectivity=4)
+    assert path == [(1, y) for y in range(5)], "The path should follow the cheaper corridor"
+    assert isinstance(cost, float) and cost == pytest.approx(0.004), "The cost should be a float sum"
+
+    ints, _ = pathfinding_py.find_path_2d(array.astype(np.uint8), (1, 0), (1, 4), "dijkstra", connectivity=4)
+    assert ints[0] == (1, 0) and ints[-1] == (1, 4), "The quantized surface still has a path, with all costs 0"
+
+    array[1, 2] = np.inf
+    path, _ = pathfinding_py.find_path_float_2d(array, (1, 0), (1, 4), connectivity=4)
+    assert (1, 2) not in path, "Infinite cells should be impassable"
+
+
+def test_find_path_float_2d_rejects_nan_and_negative_costs():
+    """Test that NaN or negative costs raise a ValueError naming the cell."""
+    array = np.ones((4, 4), dtype=np.float32)
+
+    array[2, 3] = np.nan
+    with pytest.raises(ValueError, match=r"\(2, 3\)"):
+        pathfinding_py.find_path_float_2d(array, (0, 0), (3, 3))
+
+    array[2, 3] = -1.0
+    with pytest.raises(ValueError, match="non-negative"):
+        pathfinding_py.find_path_float_2d(array, (0, 0), (3, 3))
+
+
+def test_find_paths_batch_2d_matches_single_calls():
+    """Test that find_paths_batch_2d searches each slice on its own, like find_path_2d per slice."""
+    stack = np.random.default_rng(2).integers(1, 50, size=(12, 10, 5), dtype=np.uint8)
+    stack[6, :, 3] = 0  # Wall off the end in one slice
+
+    results = pathfinding_py.find_paths_batch_2d(stack, (0, 0), (11, 9), "astar", impassable=0)
+    assert len(results) == 5, "There should be one result per slice"
+    for i, result in enumerate(results):
+        expected = pathfinding_py.find_path_2d(stack[:, :, i], (0, 0), (11, 9), "astar", impassable=0)
+        assert result == expected, f"Slice {i} should match a single find_path_2d call"
+    assert results[3] is None, "A slice without a path should give None"
+
+
+def test_find_paths_batch_2d_validates_endpoints():
+    """Test that an out-of-bounds start raises a ValueError before any search runs."""
+    stack = np.ones((5, 5, 3), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="out of bounds"):
+        pathfinding_py.find_paths_batch_2d(stack, (5, 0), (4, 4), "dijkstra")
+
+
+def test_is_reachable_2d_matches_find_path_2d():
+    """Test that is_reachable_2d agrees with whether find_path_2d finds a path."""
+    rng = np.random.default_rng(5)
+    for connectivity in [4, 8]:
+        for _ in range(20):
+            array = rng.integers(0, 3, size=(12, 12), dtype=np.uint8)
+            array[0, 0] = array[11, 11] = 1
+            found = pathfinding_py.find_path_2d(
+                array, (0, 0), (11, 11), "dijkstra", impassable=0, connectivity=connectivity
+            )
+            reachable = pathfinding_py.is_reachable_2d(
+                array, (0, 0), (11, 11), impassable=0, connectivity=connectivity
+            )
+            assert reachable == (found is not None), "is_reachable_2d should agree with find_path_2d"
+
+
+def test_is_reachable_2d_respects_walls_and_mask():
+    """Test that is_reachable_2d honours wall_value and mask like find_path_2d."""
+    array = np.ones((5, 5), dtype=np.uint8)
+    array[2, :] = 9
+
+    assert pathfinding_py.is_reachable_2d(array, (0, 0), (4, 4)) is True, "Without walls the end is reachable"
+    assert pathfinding_py.is_reachable_2d(array, (0, 0), (4, 4), wall_value=9) is False, "The wall splits the map"
+
+    mask = np.zeros((5, 5), dtype=bool)
+    mask[:, 2] = True
+    assert pathfinding_py.is_reachable_2d(array, (0, 0), (0, 4), mask=mask) is False, "The mask splits the map"
+    assert pathfinding_py.is_reachable_2d(array, (0, 0), (0, 4), mask=mask, wrap="y") is True, "Wrapping goes around"
+
+    with pytest.raises(ValueError, match="wall"):
+        pathfinding_py.is_reachable_2d(array, (2, 0), (4, 4), wall_value=9)
+
+def test_find_path_hierarchical_2d_close_to_optimal():
+    """Test that the hierarchical path is a valid path costing no less than the optimal one."""
+    rng = np.random.default_rng(8)
+    array = rng.integers(1, 200, size=(120, 100), dtype=np.uint8)
+
+    _, best = pathfinding_py.find_path_2d(array, (0, 0), (119, 99), "astar")
+    path, cost = pathfinding_py.find_path_hierarchical_2d(array, (0, 0), (119, 99), "astar", factor=8, corridor=2)
+
+    assert path[0] == (0, 0) and path[-1] == (119, 99), "Path should join the start and end"
+    assert all(max(abs(a[0] - b[0]), abs(a[1] - b[1])) == 1 for a, b in zip(path, path[1:])), (
+        "Path should be made of adjacent cells"
+    )
+    assert best <= cost <= best * 1.5, "Hierarchical cost should be close to, but not below, the optimum"
+
+
+def test_find_path_hierarchical_2d_finds_narrow_gap():
+    """Test that a gap hidden inside a mostly blocked coarse cell is still found."""
+    array = np.ones((64, 64), dtype=np.uint8) * 10
+    array[30, :] = 0
+    array[30, 5] = 10
+
+    result = pathfinding_py.find_path_hierarchical_2d(
+        array, (0, 60), (63, 60), "dijkstra", factor=8, corridor=0, impassable=0
+    )
+    assert result is not None, "A path through the gap should be found"
+    assert (30, 5) in result[0], "The path should use the gap"
+
+    array[30, 5] = 0
+    assert (
+        pathfinding_py.find_path_hierarchical_2d(array, (0, 60), (63, 60), "dijkstra", impassable=0) is None
+    ), "No path should be found through a closed wall"
+
+
+def test_find_path_hierarchical_2d_invalid_arguments():
+    """Test that unsupported algorithms and a zero factor raise ValueError."""
+    array = np.ones((16, 16), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="does not support jps"):
+        pathfinding_py.find_path_hierarchical_2d(array, (0, 0), (15, 15), "jps")
+
+    with pytest.raises(ValueError, match="factor"):
+        pathfinding_py.find_path_hierarchical_2d(array, (0, 0), (15, 15), "astar", factor=0)
+
+
+def test_find_path_2d_simplify_returns_corner_points():
+    """Test that simplify reduces a path to its corners while keeping the grid cost."""
+    array = np.ones((20, 20), dtype=np.uint8) * 200
+    array[:, 0] = 1
+    array[19, :] = 1
+
+    full_path, full_cost = pathfinding_py.find_path_2d(array, (0, 0), (19, 19), "dijkstra", connectivity=4)
+    path, cost = pathfinding_py.find_path_2d(
+        array, (0, 0), (19, 19), "dijkstra", connectivity=4, simplify=0.5
+    )
+
+    assert path == [(0, 0), (19, 0), (19, 19)], "Only the corner points should remain"
+    assert cost == full_cost, "The cost should be the full grid-traversal cost"
+    assert len(full_path) == 39, "Without simplify every cell should be listed"
+
+
+def test_find_path_2d_simplify_zero_returns_unmodified_path():
+    """Test that a simplify tolerance of 0 returns the unmodified path."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+
+    expected = pathfinding_py.find_path_2d(array, (0, 0), (9, 5), "astar")
+    result = pathfinding_py.find_path_2d(array, (0, 0), (9, 5), "astar", simplify=0.0)
+
+    assert result == expected, "A tolerance of 0 should not modify the path"
+
+
+def test_find_path_2d_simplify_invalid_tolerance():
+    """Test that a negative simplify tolerance raises ValueError."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+
+    with pytest.raises(ValueError):
+        pathfinding_py.find_path_2d(array, (0, 0), (9, 9), "astar", simplify=-1.0)
+
+
+def test_find_path_2d_drop_collinear_is_lossless():
+    """Test that drop_collinear only drops points on straight runs, so redrawing the lines gives the full path."""
+    array = np.random.default_rng(2).integers(1, 4, size=(30, 30), dtype=np.uint8)
+
+    full_path, full_cost = pathfinding_py.find_path_2d(array, (0, 0), (29, 17), "dijkstra")
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (29, 17), "dijkstra", drop_collinear=True)
+    assert cost == full_cost, "Dropping points should not change the cost"
+    assert len(path) < len(full_path), "Some points of straight runs should be dropped"
+
+    redrawn = [path[0]]
+    for (x0, y0), (x1, y1) in zip(path, path[1:]):
+        steps = math.gcd(x1 - x0, y1 - y0)
+        dx, dy = (x1 - x0) // steps, (y1 - y0) // steps
+        redrawn += [(x0 + dx * i, y0 + dy * i) for i in range(1, steps + 1)]
+    assert redrawn == full_path, "Redrawing the straight lines should trace exactly the full path"
+
+
+def test_find_path_2d_drop_collinear_segment_costs():
+    """Test that drop_collinear keeps the corners and merges the segment costs of each straight run."""
+    array = np.ones((20, 20), dtype=np.uint8) * 200
+    array[:, 0] = 1
+    array[19, :] = 1
+
+    path, cost, segment_costs = pathfinding_py.find_path_2d(
+        array, (0, 0), (19, 19), "dijkstra", connectivity=4, drop_collinear=True, return_segment_costs=True
+    )
+    assert path == [(0, 0), (19, 0), (19, 19)], "Only the ends of the two straight runs should remain"
+    assert segment_costs == [19, 19], "Each run should cost the sum of its steps"
+    assert sum(segment_costs) == cost, "The merged segment costs should add up to the total cost"
+
+
+def test_find_path_2d_timeout_raises():
+    """Test that a search exceeding timeout_ms raises TimeoutError."""
+    array = np.random.default_rng(0).integers(1, 255, size=(2000, 2000), dtype=np.uint8)
+
+    with pytest.raises(TimeoutError):
+        pathfinding_py.find_path_2d(array, (0, 0), (1999, 1999), "dijkstra", timeout_ms=1)
+
+
+def test_find_path_2d_generous_timeout_finds_path():
+    """Test that a search finishing within timeout_ms returns the usual result."""
+    array = np.ones((20, 20), dtype=np.uint8) * 10
+
+    expected = pathfinding_py.find_path_2d(array, (0, 0), (19, 19), "astar")
+    result = pathfinding_py.find_path_2d(array, (0, 0), (19, 19), "astar", timeout_ms=60_000)
+
+    assert result == expected, "A generous timeout should not affect the result"
+
+
+def test_find_path_2d_max_nodes():
+    """Test that max_nodes aborts a search expanding too many cells and leaves smaller ones alone."""
+    array = np.ones((50, 50), dtype=np.uint8)
+
+    with pytest.raises(RuntimeError, match="100 expanded nodes"):
+        pathfinding_py.find_path_2d(array, (0, 0), (49, 49), "dijkstra", max_nodes=100)
+
+    expected = pathfinding_py.find_path_2d(array, (0, 0), (49, 49), "dijkstra")
+    *result, stats = pathfinding_py.find_path_2d(
+        array, (0, 0), (49, 49), "dijkstra", max_nodes=10_000, return_stats=True
+    )
+    assert tuple(result) == expected, "A generous cap should not affect the result"
+    assert stats["nodes_expanded"] <= 10_000, "The search should stay within the cap"
+
+
+def test_find_path_2d_return_partial():
+    """Test that return_partial=True returns the path to the reachable cell closest to an unreachable end."""
+    array = np.ones((5, 5), dtype=np.uint8)
+    array[2, :] = 0
+
+    assert pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", impassable=0) is None
+    path, _, partial = pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", impassable=0, return_partial=True)
+    assert partial is True, "The path should be flagged as partial"
+    assert path[0] == (0, 0) and path[-1] == (1, 4), "The path should stop at the reachable cell closest to the end"
+    assert all(x < 2 for x, _ in path), "The path should stay on the reachable side of the wall"
+
+
+def test_find_path_2d_return_partial_reachable():
+    """Test that return_partial=True leaves reachable ends alone, only appending a False flag."""
+    array = np.random.default_rng(8).integers(1, 20, size=(10, 10), dtype=np.uint8)
+
+    expected = pathfinding_py.find_path_2d(array, (0, 0), (9, 9), "dijkstra")
+    *result, partial = pathfinding_py.find_path_2d(array, (0, 0), (9, 9), "dijkstra", return_partial=True)
+    assert tuple(result) == expected, "A full path should be returned unchanged"
+    assert partial is False, "A full path should not be flagged as partial"
+
+
+def test_find_path_2d_verify_optimal():
+    """Test that verify_optimal=True raises when weighted A* returns a costlier path than Dijkstra."""
+    array = np.ones((20, 20), dtype=np.uint8)
+    array[10, :19] = 100
+
+    with pytest.raises(RuntimeError, match="optimal cost found by dijkstra is 38"):
+        pathfinding_py.find_path_2d(array, (0, 0), (19, 0), "astar", weight=5.0, verify_optimal=True)
+
+    _, cost = pathfinding_py.find_path_2d(array, (0, 0), (19, 0), "astar", verify_optimal=True)
+    assert cost == 38, "Plain A* should find the optimal path and pass the check"
+
+
+def test_find_path_2d_verify_optimal_theta():
+    """Test that verify_optimal=True is rejected for theta's any-angle paths."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="not supported by theta"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "theta", verify_optimal=True)
+
+
+def test_find_path_2d_prefer_breaks_ties():
+    """Test that prefer picks among equally cheap paths without changing the cost."""
+    array = np.zeros((9, 9), dtype=np.uint8)
+
+    path, cost, length = pathfinding_py.find_path_2d(
+        array, (0, 0), (8, 0), "dijkstra", prefer="shortest_euclidean", return_length=True
+    )
+    assert path == [(x, 0) for x in range(9)], "The straight path should be the shortest of the free paths"
+    assert (cost, length) == (0, 8.0), "Preferring short paths should not change the cost"
+
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (8, 0), "dijkstra", prefer="fewest_steps")
+    assert len(path) == 9, "The path should take the fewest possible steps"
+    assert cost == 0, "Preferring few steps should not change the cost"
+
+
+def test_find_path_2d_prefer_validation():
+    """Test that unknown preferences, unsupported algorithms, and turn penalties are rejected."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="Unknown prefer"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "dijkstra", prefer="straightest")
+    with pytest.raises(ValueError, match="not supported by theta"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "theta", prefer="fewest_steps")
+    with pytest.raises(ValueError, match="turn_penalty"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", prefer="fewest_steps", turn_penalty=5)
+
+
+def test_find_path_2d_clamp_cost():
+    """Test that clamp_cost caps the cost of bright cells, so they no longer force a long detour."""
+    array = np.full((5, 5), 10, dtype=np.uint8)
+    array[2, :4] = 250
+
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (4, 0), "dijkstra", connectivity=4)
+    assert (2, 4) in path, "Without a cap, the path should detour around the bright wall"
+
+    path, cost = pathfinding_py.find_path_2d(array, (0, 0), (4, 0), "dijkstra", connectivity=4, clamp_cost=20)
+    assert path == [(x, 0) for x in range(5)], "With a cap, the path should cross the bright wall"
+    assert cost == 10 + 20 + 10 + 10, "The bright cell should cost the cap"
+
+
+def test_find_path_2d_clamp_cost_after_cost_fn():
+    """Test that clamp_cost applies after cost_fn and leaves free_value cells alone."""
+    array = np.full((1, 4), 3, dtype=np.uint8)
+    array[0, 3] = 7
+
+    _, cost = pathfinding_py.find_path_2d(
+        array, (0, 0), (0, 3), "dijkstra", cost_fn=lambda v: v * 100, clamp_cost=150
+    )
+    assert cost == 3 * 150, "Every cost_fn cost above the cap should be capped"
+
+    _, cost = pathfinding_py.find_path_2d(
+        array, (0, 0), (0, 3), "dijkstra", clamp_cost=5, free_value=7, free_cost=9
+    )
+    assert cost == 3 + 3 + 9, "free_cost should not be capped"
+
+
+def test_find_path_2d_non_contiguous_arrays():
+    """Test that transposed and strided arrays are read through their strides, like contiguous copies."""
+    rng = np.random.default_rng(3)
+    base = rng.integers(1, 255, size=(20, 16), dtype=np.uint8)
+
+    for array in [base.T, base[::2, ::-1], base.T[3:, ::3]]:
+        assert not array.flags["C_CONTIGUOUS"], "The test array should not be C-contiguous"
+        copy = np.ascontiguousarray(array)
+        end = (array.shape[0] - 1, array.shape[1] - 1)
+        mask = np.zeros(base.shape, dtype=bool).T[: array.shape[0], : array.shape[1]]
+        mask[0, end[1]] = True
+
+        for algorithm in ["astar", "dijkstra"]:
+            expected = pathfinding_py.find_path_2d(copy, (0, 0), end, algorithm, mask=np.ascontiguousarray(mask))
+            result = pathfinding_py.find_path_2d(array, (0, 0), end, algorithm, mask=mask)
+            assert result == expected, f"{algorithm} should find the same path as on a contiguous copy"
+
+
+def test_find_path_2d_astar_zero_heuristic_matches_dijkstra():
+    """Test that A* with the zero heuristic finds the same costs as Dijkstra."""
+    rng = np.random.default_rng(7)
+
+    for _ in range(20):
+        array = rng.integers(1, 255, size=(15, 12), dtype=np.uint8)
+        start = (int(rng.integers(0, 15)), int(rng.integers(0, 12)))
+        end = (int(rng.integers(0, 15)), int(rng.integers(0, 12)))
+
+        _, astar_cost = pathfinding_py.find_path_2d(array, start, end, "astar", heuristic="zero")
+        _, dijkstra_cost = pathfinding_py.find_path_2d(array, start, end, "dijkstra")
+        assert astar_cost == dijkstra_cost, f"Zero-heuristic A* from {start} to {end} should match Dijkstra"
+
+
+def test_find_path_2d_astar_heuristics():
+    """Test that every supported heuristic finds a path, and that unknown ones are rejected."""
+    array = np.ones((10, 10), dtype=np.uint8) * 20
+
+    for heuristic in ["manhattan", "euclidean", "chebyshev", "octile", "zero"]:
+        result = pathfinding_py.find_path_2d(array, (0, 0), (9, 9), "astar", heuristic=heuristic)
+        assert result is not None, f"A path should be found with the {heuristic} heuristic"
+        assert result[0][-1] == (9, 9), f"The {heuristic} path should end at the end position"
+
+    with pytest.raises(ValueError, match="chebyshev"):
+        pathfinding_py.find_path_2d(array, (0, 0), (9, 9), "astar", heuristic="cosine")
+
+
+def test_find_path_2d_astar_octile_heuristic_matches_dijkstra():
+    """Test that A* with the octile heuristic finds the same costs as Dijkstra on 8-connected maps."""
+    rng = np.random.default_rng(11)
+
+    for low in [1, 40]:
+        for _ in range(20):
+            array = rng.integers(low, 255, size=(15, 12), dtype=np.uint8)
+            start = (int(rng.integers(0, 15)), int(rng.integers(0, 12)))
+            end = (int(rng.integers(0, 15)), int(rng.integers(0, 12)))
+
+            _, astar_cost = pathfinding_py.find_path_2d(array, start, end, "astar", heuristic="octile")
+            _, dijkstra_cost = pathfinding_py.find_path_2d(array, start, end, "dijkstra")
+            assert astar_cost == dijkstra_cost, f"Octile A* from {start} to {end} should match Dijkstra"
+
+    uniform = np.full((20, 20), 40, dtype=np.uint8)
+    _, cost = pathfinding_py.find_path_2d(uniform, (0, 0), (19, 12), "astar", heuristic="octile")
+    _, dijkstra_cost = pathfinding_py.find_path_2d(uniform, (0, 0), (19, 12), "dijkstra")
+    assert cost == dijkstra_cost, "Octile A* should stay optimal on a uniform map, where it is exact"
+
+
+def test_find_path_through_waypoints_2d():
+    """Test that a waypoint path chains the legs, keeping each joint once and summing the costs."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+    points = [(0, 0), (9, 0), (9, 9), (0, 9)]
+
+    path, cost = pathfinding_py.find_path_through_waypoints_2d(array, points, "dijkstra")
+
+    for point in points:
+        assert path.count(point) == 1, f"Waypoint {point} should appear exactly once"
+    indices = [path.index(point) for point in points]
+    assert indices == sorted(indices), "Waypoints should be visited in order"
+    assert path[0] == points[0] and path[-1] == points[-1], "Path should run from the first to the last point"
+
+    leg_costs = [
+        pathfinding_py.find_path_2d(array, a, b, "dijkstra")[1] for a, b in zip(points, points[1:])
+    ]
+    assert cost == sum(leg_costs), "Cost should be the sum of the leg costs"
+
+
+def test_find_path_through_waypoints_2d_unreachable_leg():
+    """Test that an unreachable leg raises an error naming it."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+    array[5, :] = 255
+
+    with pytest.raises(ValueError, match=r"Leg 1 from \(4, 0\) to \(8, 8\)"):
+        pathfinding_py.find_path_through_waypoints_2d(
+            array, [(0, 0), (4, 0), (8, 8)], "astar", impassable=255
+        )
+
+    with pytest.raises(ValueError, match="At least two points"):
+        pathfinding_py.find_path_through_waypoints_2d(array, [(0, 0)], "astar")
+
+
+def test_find_path_2d_wall_value():
+    """Test that cells equal to wall_value are never crossed, even when cheap to cross otherwise."""
+    array = np.ones((7, 7), dtype=np.uint8) * 50
+    array[3, :6] = 0
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (6, 0), "dijkstra", wall_value=0)
+
+    assert result is not None, "A path around the wall should be found"
+    path, _ = result
+    assert all(array[x, y] != 0 for x, y in path), "The path should never cross a wall cell"
+    assert (3, 6) in path, "The path should go around the end of the wall"
+
+    array[3, 6] = 0
+    result = pathfinding_py.find_path_2d(array, (0, 0), (6, 0), "dijkstra", wall_value=0)
+    assert result is None, "No path should be found through a closed wall"
+
+
+def test_find_path_2d_wall_value_on_start_or_end():
+    """Test that a start or end on a wall raises, and that impassable and wall_value must agree."""
+    array = np.ones((5, 5), dtype=np.uint8) * 50
+    array[0, 0] = 255
+    array[4, 4] = 255
+
+    with pytest.raises(ValueError, match="Start position"):
+        pathfinding_py.find_path_2d(array, (0, 0), (2, 2), "astar", wall_value=255)
+
+    with pytest.raises(ValueError, match="End position"):
+        pathfinding_py.find_path_2d(array, (2, 2), (4, 4), "astar", wall_value=255)
+
+    with pytest.raises(ValueError, match="must be equal"):
+        pathfinding_py.find_path_2d(array, (1, 1), (2, 2), "astar", impassable=0, wall_value=255)
+
+
+def test_find_path_2d_max_cost():
+    """Test that max_cost is an inclusive budget: paths above it return None."""
+    array = np.ones((10, 10), dtype=np.uint8) * 10
+
+    for algorithm in ["astar", "dijkstra", "dijkstra_bi"]:
+        _, cost = pathfinding_py.find_path_2d(array, (0, 0), (9, 9), algorithm)
+
+        result = pathfinding_py.find_path_2d(array, (0, 0), (9, 9), algorithm, max_cost=cost)
+        assert result is not None, f"{algorithm}: a path costing exactly max_cost should be returned"
+        assert result[1] == cost, f"{algorithm}: the budgeted path should still be optimal"
+
+        result = pathfinding_py.find_path_2d(array, (0, 0), (9, 9), algorithm, max_cost=cost - 1)
+        assert result is None, f"{algorithm}: no path should fit below the optimal cost"
+
+
+def test_find_path_2d_max_cost_prunes_neighbours():
+    """Test that neighbours beyond the budget are not generated."""
+    array = np.ones((60, 60), dtype=np.uint8) * 10
+
+    _, cost, unbounded = pathfinding_py.find_path_2d(array, (0, 0), (59, 59), "dijkstra", return_stats=True)
+    _, budget_cost, bounded = pathfinding_py.find_path_2d(
+        array, (0, 0), (59, 59), "dijkstra", max_cost=cost, return_stats=True
+    )
+
+    assert budget_cost == cost, "The budgeted path should be the optimal one"
+    assert bounded["nodes_generated"] < unbounded["nodes_generated"], "The budget should prune neighbours"
+
+
+def test_find_path_2d_segment_costs():
+    """Test that return_segment_costs returns one cost per edge, summing to the total."""
+    rng = np.random.default_rng(3)
+    array = rng.integers(1, 100, size=(15, 15), dtype=np.uint8)
+
+    for algorithm in ["astar", "dijkstra", "dijkstra_bi", "fringe", "theta"]:
+        path, cost, segment_costs = pathfinding_py.find_path_2d(
+            array, (0, 0), (14, 9), algorithm, return_segment_costs=True
+        )
+        assert len(segment_costs) == len(path) - 1, f"{algorithm} should return one cost per edge"
+        assert sum(segment_costs) == cost, f"{algorithm} segment costs should sum to the total"
+
+
+def test_find_path_2d_segment_costs_values():
+    """Test that segment costs are the step costs along the path, before the stats dict."""
+    array = np.array([[1, 1, 1], [5, 9, 9], [1, 1, 1]], dtype=np.uint8)
+
+    path, cost, segment_costs, stats = pathfinding_py.find_path_2d(
+        array, (0, 0), (2, 0), "dijkstra", connectivity=4, return_segment_costs=True, return_stats=True
+    )
+
+    assert path == [(0, 0), (1, 0), (2, 0)], "Path should go straight through the cheap cell"
+    assert segment_costs == [5, 1], "Each edge should cost the value of the cell it enters"
+    assert "nodes_expanded" in stats, "Stats should come last"
+
+
+def test_find_path_2d_segment_costs_simplified():
+    """Test that simplified paths get one summed cost per simplified segment."""
+    array = np.ones((10, 10), dtype=np.uint8)
+
+    path, cost, segment_costs = pathfinding_py.find_path_2d(
+        array, (0, 0), (9, 0), "dijkstra", connectivity=4, simplify=0.5, return_segment_costs=True
+    )
+
+    assert path == [(0, 0), (9, 0)], "Straight path should simplify to its endpoints"
+    assert segment_costs == [9], "The single segment should carry the whole cost"
+
+
+def test_find_path_2d_wrap_crosses_seam():
+    """Test that wrap="x" lets a path cross from the right border to the left one."""
+    array = np.ones((10, 3), dtype=np.uint8)
+
+    path, cost = pathfinding_py.find_path_2d(
+        array, (1, 1), (8, 1), "dijkstra", connectivity=4, wrap="x"
+    )
+
+    assert path == [(1, 1), (0, 1), (9, 1), (8, 1)], "Path should cross the left/right seam"
+    assert cost == 3, "Crossing the seam should take three steps"
+
+    path, cost = pathfinding_py.find_path_2d(array, (1, 1), (8, 1), "dijkstra", connectivity=4)
+    assert cost == 7, "Without wrap the borders should be walls"
+
+
+def test_find_path_2d_wrap_axes():
+    """Test that each wrap mode only joins the borders of its axes."""
+    array = np.ones((8, 8), dtype=np.uint8)
+    corner_to_corner = {"none": 14, "x": 8, "y": 8, "xy": 2}
+
+    for wrap, expected in corner_to_corner.items():
+        for algorithm in ["astar", "dijkstra", "dijkstra_bi", "fringe", "theta"]:
+            path, cost = pathfinding_py.find_path_2d(
+                array, (0, 0), (7, 7), algorithm, connectivity=4, wrap=wrap
+            )
+            assert cost == expected, f"{algorithm} with wrap={wrap} should cost {expected}"
+            assert all(0 <= x < 8 and 0 <= y < 8 for x, y in path), "Coordinates should stay in bounds"
+
+
+def test_find_path_2d_wrap_invalid():
+    """Test that an unknown wrap mode, or jps with wrapping, raises a ValueError."""
+    array = np.ones((5, 5), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match="Unknown wrap"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", wrap="z")
+
+    with pytest.raises(ValueError, match="jps"):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "jps", wrap="xy")
+
+
+def test_path_finder_2d_matches_find_path_2d():
+    """Test that PathFinder2D.find returns the same results as find_path_2d."""
+    rng = np.random.default_rng(7)
+    array = rng.integers(1, 50, size=(20, 20), dtype=np.uint8)
+    array[5:15, 10] = 255
+
+    finder = pathfinding_py.PathFinder2D(array, impassable=255, connectivity=4)
+    queries = [((0, 0), (19, 19)), ((3, 12), (17, 2)), ([(0, 19), (19, 0)], (10, 10))]
+
+    for algorithm in ["astar", "dijkstra", "dijkstra_bi", "fringe", "theta"]:
+        for start, end in queries:
+            expected = pathfinding_py.find_path_2d(
+                array, start, end, algorithm, impassable=255, connectivity=4, return_stats=True
+            )
+            result = finder.find(start, end, algorithm, return_stats=True)
+            assert result == expected, f"{algorithm} from {start} to {end} should match find_path_2d"
+
+
+def test_path_finder_2d_forwards_every_option():
+    """Test that PathFinder2D accepts the movement, speed, and result options of find_path_2d."""
+    rng = np.random.default_rng(32)
+    array = rng.integers(1, 50, size=(15, 15), dtype=np.uint8)
+    speed = rng.integers(1, 4, size=(15, 15), dtype=np.uint8)
+    knight = [(1, 2), (2, 1), (-1, 2), (-2, 1), (1, -2), (2, -1), (-1, -2), (-2, -1)]
+
+    graphs = [
+        {"neighbors": knight},
+        {"reach": 3, "speed": speed, "clamp_cost": 20},
+    ]
+    queries = [
+        {"prefer": "fewest_steps", "drop_collinear": True},
+        {"return_partial": True, "verify_optimal": True},
+    ]
+    for graph in graphs:
+        finder = pathfinding_py.PathFinder2D(array, **graph)
+        for query in queries:
+            expected = pathfinding_py.find_path_2d(array, (0, 0), (14, 13), "dijkstra", **graph, **query)
+            result = finder.find((0, 0), (14, 13), "dijkstra", **query)
+            assert result == expected, f"{graph.keys()} with {query.keys()} should match find_path_2d"
+
+
+def test_path_finder_2d_cost_fn_evaluated_once():
+    """Test that PathFinder2D evaluates cost_fn once on construction, not per query."""
+    calls = []
+
+    def cost_fn(value):
+        calls.append(value)
+        return value + 1
+
+    array = np.ones((10, 10), dtype=np.uint8)
+    finder = pathfinding_py.PathFinder2D(array, cost_fn=cost_fn)
+    for end in [(9, 9), (0, 9), (9, 0)]:
+        assert finder.find((0, 0), end, "dijkstra") is not None, "Path should be found"
+
+    assert sorted(calls) == list(range(256)), "cost_fn should be called once for each uint8 value"
+
+
+def test_path_finder_2d_validates_arguments():
+    """Test that PathFinder2D rejects bad graph arguments up front and bad endpoints per query."""
+    array = np.ones((5, 5), dtype=np.uint8)
+    array[2, 2] = 9
+
+    with pytest.raises(ValueError, match="connectivity"):
+        pathfinding_py.PathFinder2D(array, connectivity=6)
+
+    with pytest.raises(ValueError, match="Mask shape"):
+        pathfinding_py.PathFinder2D(array, mask=np.zeros((4, 4), dtype=bool))
+
+    finder = pathfinding_py.PathFinder2D(array, wall_value=9)
+    with pytest.raises(ValueError, match="wall"):
+        finder.find((2, 2), (4, 4), "astar")
+
+    with pytest.raises(ValueError, match="out of bounds"):
+        finder.find((0, 0), (5, 0), "astar")
+
+
+def test_path_finder_2d_outlives_deleted_array():
+    """Test that PathFinder2D still answers queries once the source array and mask are deleted."""
+    rng = np.random.default_rng(13)
+    array = rng.integers(1, 256, size=(20, 15), dtype=np.uint8)
+    mask = np.zeros((20, 15), dtype=bool)
+    mask[10, :12] = True
+    expected = pathfinding_py.find_path_2d(array, (0, 0), (19, 14), "astar", mask=mask)
+
+    finder = pathfinding_py.PathFinder2D(array, mask=mask)
+    del array, mask
+    gc.collect()
+
+    assert finder.find((0, 0), (19, 14), "astar") == expected, "The finder should search its own copy"
+
+
+def test_path_finder_2d_ignores_later_changes():
+    """Test that modifying the source array after construction does not change the results."""
+    array = np.full((10, 10), 10, dtype=np.uint16)
+    finder = pathfinding_py.PathFinder2D(array, impassable=0)
+    expected = finder.find((0, 0), (9, 9), "dijkstra")
+
+    array[:, 5] = 0
+    assert finder.find((0, 0), (9, 9), "dijkstra") == expected, "The finder should keep the heatmap it was built with"
+
+
+def test_d_star_lite_2d_matches_dijkstra_after_updates():
+    """Test that every DStarLite2D replan costs the same as a fresh search of the updated map."""
+    rng = np.random.default_rng(11)
+    array = rng.integers(1, 20, size=(30, 30), dtype=np.uint8)
+    planner = pathfinding_py.DStarLite2D(impassable=0)
+    planner.initialize(array, (0, 0), (29, 29))
+    start = (0, 0)
+
+    for round in range(10):
+        expected = pathfinding_py.find_path_2d(array, start, (29, 29), "dijkstra", impassable=0)
+        result = planner.replan(start=start)
+        assert result[1] == expected[1], f"Replan {round} should cost as much as dijkstra"
+        assert result[0][0] == start and result[0][-1] == (29, 29), "Path should join start and end"
+
+        for x, y in rng.integers(0, 29, size=(5, 2)):
+            value = int(rng.integers(0, 20))
+            array[x, y] = value
+            planner.update_cell(int(x), int(y), value)
+        start = result[0][min(3, len(result[0]) - 1)]
+
+
+def test_d_star_lite_2d_replan_is_incremental():
+    """Test that DStarLite2D repairs a local change with far fewer expansions than the first plan."""
+    array = np.ones((100, 100), dtype=np.uint8)
+    planner = pathfinding_py.DStarLite2D(impassable=0)
+    planner.initialize(array, (0, 50), (99, 50))
+
+    path, cost = planner.replan()
+    first = planner.nodes_expanded
+    planner.update_cell(*path[len(path) // 2], 0)
+    new_path, new_cost = planner.replan()
+
+    assert path[len(path) // 2] not in new_path, "The new path should avoid the blocked cell"
+    assert new_cost >= cost, "Blocking a cell should not make the path cheaper"
+    assert planner.nodes_expanded < first / 2, "The repair should reuse most of the first search"
+
+
+def test_d_star_lite_2d_invalid_usage():
+    """Test that DStarLite2D rejects calls before initialize, out of bounds cells, and bad values."""
+    planner = pathfinding_py.DStarLite2D(impassable=0)
+    with pytest.raises(ValueError, match="initialize"):
+        planner.replan()
+
+    planner.initialize(np.ones((5, 5), dtype=np.uint8), (0, 0), (4, 4))
+    with pytest.raises(ValueError, match="out of bounds"):
+        planner.update_cell(5, 0, 1)
+    with pytest.raises(ValueError, match="does not fit"):
+        planner.update_cell(0, 0, 300)
+
+    planner.update_cell(1, 0, 0)
+    planner.update_cell(0, 1, 0)
+    planner.update_cell(1, 1, 0)
+    assert planner.replan() is None, "A walled-in start should have no path"
+
+
+def test_d_star_lite_2d_zero_cost_cells():
+    """Test that DStarLite2D repairs plateaus of zero-cost cells when nothing is impassable."""
+    array = np.array([[9, 9, 0, 0, 5], [5, 9, 5, 5, 1], [5, 9, 9, 1, 5]], dtype=np.uint8)
+    planner = pathfinding_py.DStarLite2D(connectivity=4)
+    planner.initialize(array, (2, 3), (1, 0))
+    planner.replan()
+
+    array[1, 0] = 9
+    planner.update_cell(1, 0, 9)
+    path, cost = planner.replan()
+    expected = pathfinding_py.find_path_2d(array, (2, 3), (1, 0), "dijkstra", connectivity=4)
+    assert cost == expected[1], "The repaired plan should cost as much as dijkstra"
+    assert path[0] == (2, 3) and path[-1] == (1, 0), "Path should join start and end"
+
+    rng = np.random.default_rng(5)
+    array = rng.integers(0, 4, size=(20, 20), dtype=np.uint8)
+    planner = pathfinding_py.DStarLite2D()
+    planner.initialize(array, (0, 0), (19, 19))
+    for round in range(10):
+        expected = pathfinding_py.find_path_2d(array, (0, 0), (19, 19), "dijkstra")
+        assert planner.replan()[1] == expected[1], f"Replan {round} should cost as much as dijkstra"
+
+        for x, y in rng.integers(0, 20, size=(5, 2)):
+            value = int(rng.integers(0, 4))
+            array[x, y] = value
+            planner.update_cell(int(x), int(y), value)
+
+
+def test_find_path_2d_start_equals_end():
+    """Test that every algorithm returns a single-point, zero-cost path when start equals end."""
+    array = np.ones((5, 5), dtype=np.uint8) * 9
+
+    for algorithm in pathfinding_py.supported_algorithms_2d():
+        result = pathfinding_py.find_path_2d(array, (2, 3), (2, 3), algorithm)
+        assert result == ([(2, 3)], 0), f"{algorithm} should return ([start], 0)"
+
+
+def test_find_path_2d_start_equals_end_extras():
+    """Test the trivial path with the array output and segment costs."""
+    array = np.ones((5, 5), dtype=np.uint8) * 9
+
+    path, cost, segment_costs = pathfinding_py.find_path_2d(
+        array, (1, 1), (1, 1), "astar", as_array=True, return_segment_costs=True
+    )
+
+    assert path.tolist() == [[1, 1]], "Array path should hold the single point"
+    assert cost == 0, "Trivial path should cost nothing"
+    assert segment_costs == [], "Trivial path should have no edges"
+
+
+def test_validate_point_2d():
+    """Test that validate_point_2d accepts points inside the shape and reports the others like find_path_2d."""
+    array = np.ones((5, 4), dtype=np.uint8)
+
+    assert pathfinding_py.validate_point_2d(array.shape, (4, 3)) is True
+    with pytest.raises(ValueError, match=r"^point \(5, 0\) is out of bounds for array of shape 5x4$"):
+        pathfinding_py.validate_point_2d(array.shape, (5, 0))
+    with pytest.raises(ValueError, match=r"point \(0, -1\) is out of bounds"):
+        pathfinding_py.validate_point_2d(array.shape, (0, -1))
+
+    with pytest.raises(ValueError) as search_error:
+        pathfinding_py.find_path_2d(array, (0, 0), (5, 0), "dijkstra")
+    with pytest.raises(ValueError) as validate_error:
+        pathfinding_py.validate_point_2d(array.shape, (5, 0), name="end")
+    assert str(validate_error.value) == str(search_error.value), "The messages should match find_path_2d"
+
+
+def test_validate_point_3d():
+    """Test that validate_point_3d checks every axis of the volume shape."""
+    shape = (5, 4, 3)
+
+    assert pathfinding_py.validate_point_3d(shape, (4, 3, 2)) is True
+    with pytest.raises(ValueError, match=r"start \(0, 0, 3\) is out of bounds for array of shape 5x4x3"):
+        pathfinding_py.validate_point_3d(shape, (0, 0, 3), name="start")
+
+
+def test_supported_algorithms_are_accepted():
+    """Test that every listed algorithm is accepted by the matching search function."""
+    array = np.ones((5, 5), dtype=np.uint8)
+    volume = np.ones((3, 3, 3), dtype=np.uint8)
+
+    assert "dijkstra_bi" in pathfinding_py.supported_algorithms_2d(), "2D list should include dijkstra_bi"
+    for algorithm in pathfinding_py.supported_algorithms_2d():
+        result = pathfinding_py.find_path_2d(array, (0, 0), (4, 4), algorithm)
+        assert result is not None, f"{algorithm} should find a path"
+
+    for algorithm in pathfinding_py.supported_algorithms_temporal():
+        result = pathfinding_py.find_route_temporal(volume, algorithm, (0, 0, 0), (2, 2, 2))
+        assert result is not None, f"{algorithm} should find a route"
+
+
+def test_unknown_algorithm_lists_supported():
+    """Test that the unknown algorithm error lists the supported algorithms."""
+    array = np.ones((5, 5), dtype=np.uint8)
+    volume = np.ones((3, 3, 3), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match=", ".join(pathfinding_py.supported_algorithms_2d())):
+        pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "bfs")
+
+    with pytest.raises(ValueError, match=", ".join(pathfinding_py.supported_algorithms_temporal())):
+        pathfinding_py.find_route_temporal(volume, "jps", (0, 0, 0), (2, 2, 2))
+
+
 def test_find_path_2d_invalid_algorithm():
     """Test that invalid algorithm raises an error."""
     array = np.ones((5, 5), dtype=np.uint8) * 50
@@ -110,6 +2243,27 @@ def test_find_path_2d_invalid_both_out_of_bounds():
         pathfinding_py.find_path_2d(array, (10, 10), (20, 20), "astar")
 
 
+def test_find_path_2d_out_of_bounds_message():
+    """Test that out-of-bounds positions raise a ValueError naming the point and array shape."""
+    array = np.ones((5, 4), dtype=np.uint8) * 50
+
+    with pytest.raises(ValueError, match=r"^start \(5, 0\) is out of bounds for array of shape 5x4$"):
+        pathfinding_py.find_path_2d(array, (5, 0), (4, 3), "astar")
+
+    with pytest.raises(ValueError, match=r"^end \(0, 4\) is out of bounds for array of shape 5x4$"):
+        pathfinding_py.find_path_2d(array, (0, 0), (0, 4), "dijkstra")
+
+
+def test_find_path_2d_unreachable_in_bounds_returns_none():
+    """Test that a valid but unreachable end still returns None rather than raising."""
+    array = np.ones((5, 5), dtype=np.uint8) * 50
+    array[2, :] = 255
+
+    result = pathfinding_py.find_path_2d(array, (0, 0), (4, 4), "astar", impassable=255)
+
+    assert result is None, "An unreachable end inside the array should return None"
+
+
 def test_find_route_temporal_astar():
     """Test temporal routing with A* algorithm."""
     volume = np.ones((10, 10, 5), dtype=np.uint8) * 150
@@ -153,6 +2307,62 @@ def test_find_route_temporal_dijkstra():
     assert times == sorted(times), "Path should move forward in time"
 
 
+def test_find_route_temporal_fringe():
+    """Test temporal routing with Fringe algorithm."""
+    volume = np.ones((6, 6, 4), dtype=np.uint8) * 100
+
+    for t in range(4):
+        volume[t, t, t] = 10
+
+    start = (0, 0, 0)
+    end = (3, 3, 3)
+
+    result = pathfinding_py.find_route_temporal(volume, "fringe", start, end)
+
+    assert result is not None, "Route should be found"
+    path, cost = result
+    assert path[0] == start, "Path should start at the start position"
+    assert path[-1] == end, "Path should end at the end position"
+    times = [pos[2] for pos in path]
+    assert times == sorted(times), "Path should move forward in time"
+
+
+def test_find_route_temporal_non_contiguous_array():
+    """Test that a transposed volume is read through its strides, like a contiguous copy."""
+    rng = np.random.default_rng(5)
+    # Stored as (t, y, x), then transposed to the (x, y, t) layout the router expects
+    volume = rng.integers(1, 255, size=(6, 7, 8), dtype=np.uint8).transpose(2, 1, 0)
+    assert not volume.flags["C_CONTIGUOUS"], "The test volume should not be C-contiguous"
+
+    for algorithm in ["astar", "dijkstra", "fringe"]:
+        expected = pathfinding_py.find_route_temporal(np.ascontiguousarray(volume), algorithm, (0, 0, 0), (5, 5, 5))
+        assert expected is not None, "The diagonal end should be reachable"
+        result = pathfinding_py.find_route_temporal(volume, algorithm, (0, 0, 0), (5, 5, 5))
+        assert result == expected, f"{algorithm} should find the same route as on a contiguous copy"
+
+
+def test_find_route_temporal_negative_axis():
+    """Test that negative axes count from the last axis, like NumPy axes."""
+    rng = np.random.default_rng(11)
+    volume = rng.integers(1, 255, size=(6, 5, 4), dtype=np.uint8)
+
+    for axis, negative in [(2, -1), (1, -2), (0, -3)]:
+        expected = pathfinding_py.find_route_temporal(volume, "dijkstra", axis=axis)
+        result = pathfinding_py.find_route_temporal(volume, "dijkstra", axis=negative)
+        assert result == expected, f"axis={negative} should route along axis {axis}"
+
+
+def test_find_route_temporal_axis_out_of_range():
+    """Test that axes out of range, negative or not, raise a ValueError."""
+    volume = np.ones((3, 3, 3), dtype=np.uint8)
+
+    for axis in [3, -4]:
+        with pytest.raises(ValueError, match="axis must be 0, 1 or 2 for a 3D array"):
+            pathfinding_py.find_route_temporal(volume, "astar", axis=axis)
+    with pytest.raises(ValueError, match="got -4"):
+        pathfinding_py.find_routes_temporal(volume, "astar", [((0, 0, 0), (2, 2, 2))], axis=-4)
+
+
 def test_find_route_temporal_with_custom_starts_ends():
     """Test temporal routing with custom start and end positions."""
     volume = np.ones((6, 6, 4), dtype=np.uint8) * 80
@@ -194,6 +2404,47 @@ def test_find_route_temporal_with_reach():
     assert len(path) > 0, "Path should contain at least one point"
 
 
+def test_find_route_temporal_one_point_per_frame():
+    """Test that a route with reach > 1 still has one point for every frame between start and end."""
+    volume = np.ones((12, 12, 6), dtype=np.uint8) * 120
+    for t in range(6):
+        volume[2 * t, 2 * t, t] = 1
+
+    for axis, start, end in ((2, (0, 0, 0), (10, 10, 5)), (0, (0, 0, 0), (5, 3, 5))):
+        result = pathfinding_py.find_route_temporal(volume, "dijkstra", start, end, reach=3, axis=axis)
+
+        assert result is not None, f"Route should be found along axis {axis}"
+        path, _ = result
+        indices = [point[axis] for point in path]
+        assert indices == list(range(start[axis], end[axis] + 1)), "Route should visit every index along the axis once"
+
+
+def test_find_route_temporal_strict_reasons():
+    """Test that strict=True raises a ValueError telling why no route was found."""
+    volume = np.ones((5, 5, 5), dtype=np.uint8) * 10
+    cases = [
+        ({"start": (0, 0, 3), "end": (0, 0, 1)}, "start not reachable along axis"),
+        ({"start": (0, 0, 0), "end": (4, 4, 1)}, "goal outside reachable set"),
+        ({"start": (0, 0, 0), "end": (0, 0, 4), "max_cost": 5}, "maximum cost"),
+    ]
+
+    for kwargs, reason in cases:
+        assert pathfinding_py.find_route_temporal(volume, "dijkstra", **kwargs) is None, "Default mode should return None"
+
+        with pytest.raises(ValueError, match=reason):
+            pathfinding_py.find_route_temporal(volume, "dijkstra", **kwargs, strict=True)
+
+
+def test_find_route_temporal_strict_finds_route():
+    """Test that strict=True still returns routes that exist."""
+    volume = np.ones((5, 5, 5), dtype=np.uint8)
+
+    result = pathfinding_py.find_route_temporal(volume, "astar", (0, 0, 0), (4, 4, 4), strict=True)
+
+    assert result is not None, "Route should be found"
+    assert result[1] == 4, "Cost should be one per frame"
+
+
 def test_find_route_temporal_invalid_algorithm():
     """Test that invalid algorithm raises an error."""
     volume = np.ones((5, 5, 3), dtype=np.uint8) * 50
@@ -260,6 +2511,461 @@ def test_find_route_temporal_invalid_both_out_of_bounds():
         pathfinding_py.find_route_temporal(volume, "astar", (10, 10, 10), (20, 20, 20))
 
 
+def test_find_route_temporal_out_of_bounds_message():
+    """Test that out-of-bounds temporal positions raise a ValueError naming the point and volume shape."""
+    volume = np.ones((10, 8, 5), dtype=np.uint8) * 100
+
+    with pytest.raises(ValueError, match=r"^end \(9, 7, 5\) is out of bounds for array of shape 10x8x5$"):
+        pathfinding_py.find_route_temporal(volume, "astar", (0, 0, 0), (9, 7, 5))
+
+
+def test_find_route_temporal_progress_fn():
+    """Test that progress_fn is called periodically with the best cost and nodes expanded."""
+    volume = np.ones((30, 30, 10), dtype=np.uint8) * 50
+    calls = []
+
+    result = pathfinding_py.find_route_temporal(
+        volume,
+        "dijkstra",
+        (0, 0, 0),
+        (20, 20, 9),
+        reach=3,
+        progress_fn=lambda best_cost, nodes_expanded: calls.append((best_cost, nodes_expanded)),
+        progress_interval=100,
+    )
+
+    assert result is not None, "Route should be found"
+    assert len(calls) > 1, "progress_fn should be called several times"
+    expanded = [nodes for _, nodes in calls]
+    assert expanded == sorted(expanded), "Nodes expanded should only grow"
+    assert all(nodes % 100 == 0 for nodes in expanded), "Calls should be throttled to the interval"
+    assert all(best is None for best, _ in calls), "No complete route is known during a single search"
+
+
+def test_find_route_temporal_progress_fn_matches_result():
+    """Test that passing progress_fn does not change the route found."""
+    volume = np.random.default_rng(5).integers(1, 255, size=(15, 15, 8), dtype=np.uint8)
+
+    expected = pathfinding_py.find_route_temporal(volume, "astar", (0, 0, 0), (14, 14, 7), reach=2)
+    result = pathfinding_py.find_route_temporal(
+        volume, "astar", (0, 0, 0), (14, 14, 7), reach=2, progress_fn=lambda *_: None, progress_interval=1
+    )
+
+    assert result == expected, "Progress reporting should not affect the result"
+
+
+def test_find_route_temporal_progress_fn_raising_aborts():
+    """Test that an exception raised by progress_fn aborts the search and propagates."""
+    volume = np.ones((30, 30, 10), dtype=np.uint8) * 50
+    calls = []
+
+    def progress_fn(best_cost, nodes_expanded):
+        calls.append(nodes_expanded)
+        raise KeyboardInterrupt("stop")
+
+    with pytest.raises(KeyboardInterrupt, match="stop"):
+        pathfinding_py.find_route_temporal(
+            volume, "fringe", (0, 0, 0), (20, 20, 9), reach=3, progress_fn=progress_fn, progress_interval=10
+        )
+
+    assert calls == [10], "The search should stop at the first raising call"
+
+
+def test_find_route_temporal_invalid_progress_interval():
+    """Test that a progress_interval of 0 raises ValueError."""
+    volume = np.ones((5, 5, 3), dtype=np.uint8) * 50
+
+    with pytest.raises(ValueError):
+        pathfinding_py.find_route_temporal(
+            volume, "astar", (0, 0, 0), (4, 4, 2), progress_fn=lambda *_: None, progress_interval=0
+        )
+
+
+def test_find_route_temporal_timeout_raises():
+    """Test that a temporal search exceeding timeout_ms raises TimeoutError."""
+    volume = np.random.default_rng(0).integers(1, 255, size=(200, 200, 100), dtype=np.uint8)
+
+    with pytest.raises(TimeoutError):
+        pathfinding_py.find_route_temporal(
+            volume, "dijkstra", (0, 0, 0), (199, 199, 99), reach=2, timeout_ms=1
+        )
+
+
+def test_find_route_temporal_max_nodes():
+    """Test that max_nodes aborts a temporal search expanding too many nodes, even when not strict."""
+    volume = np.ones((20, 20, 20), dtype=np.uint8)
+
+    with pytest.raises(RuntimeError, match="expanded nodes"):
+        pathfinding_py.find_route_temporal(volume, "dijkstra", max_nodes=50)
+
+    expected = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (5, 5, 19))
+    result = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (5, 5, 19), max_nodes=1_000_000)
+    assert result == expected, "A generous cap should not affect the result"
+
+
+def test_find_route_temporal_multiple_starts_and_ends():
+    """Test that lists of starts and ends return the cheapest route over every pair."""
+    volume = np.ones((10, 10, 5), dtype=np.uint8) * 150
+    for t in range(5):
+        volume[8, t, t] = 5
+
+    starts = [(0, 0, 0), (8, 0, 0)]
+    ends = [(0, 4, 4), (8, 4, 4)]
+    result = pathfinding_py.find_route_temporal(volume, "dijkstra", starts, ends)
+
+    assert result is not None, "A route should be found"
+    route, cost = result
+    assert route[0] == (8, 0, 0), "The route should use the start next to the cheap corridor"
+    assert route[-1] == (8, 4, 4), "The route should use the end at the cheap corridor"
+
+    for start in starts:
+        for end in ends:
+            single = pathfinding_py.find_route_temporal(volume, "dijkstra", start, end)
+            assert single is None or cost <= single[1], f"Route {start} -> {end} should not be cheaper"
+
+
+def test_find_route_temporal_empty_lists_use_axis_boundaries():
+    """Test that empty start and end lists fall back to every position on the axis boundaries."""
+    volume = np.ones((6, 6, 4), dtype=np.uint8) * 100
+    for t in range(4):
+        volume[3, 2, t] = 1
+
+    result = pathfinding_py.find_route_temporal(volume, "astar", [], [])
+
+    assert result is not None, "A route should be found"
+    route, cost = result
+    assert route[0][2] == 0, "The route should start on the first frame"
+    assert route[-1][2] == 3, "The route should end on the last frame"
+    assert cost == 3, "The route should follow the cheap column through every frame"
+
+
+def test_find_route_temporal_default_endpoints():
+    """Test that omitted start and end route between the cheapest cells of the first and last slices."""
+    volume = np.ones((6, 6, 4), dtype=np.uint8) * 100
+    for t in range(4):
+        volume[3, 2, t] = 1
+
+    route, cost = pathfinding_py.find_route_temporal(volume, "dijkstra")
+    assert (route[0], route[-1]) == ((3, 2, 0), (3, 2, 3)), "The route should follow the cheap column"
+    assert cost == 3, "The route should cost one per frame after the start"
+
+    route, _ = pathfinding_py.find_route_temporal(volume, "dijkstra", start=(2, 2, 0))
+    assert route[0] == (2, 2, 0), "An explicit start should be kept"
+    assert route[-1] == (3, 2, 3), "The reached cell on the last slice should be the route's last point"
+
+def test_find_routes_temporal_matches_single_calls():
+    """Test that find_routes_temporal returns what find_route_temporal returns for each query, in order."""
+    rng = np.random.default_rng(9)
+    volume = rng.integers(1, 100, size=(8, 8, 6), dtype=np.uint8)
+    queries = [((0, 0, 0), (7, 7, 5)), ((3, 4, 1), (2, 2, 4)), ((7, 0, 0), (0, 7, 5)), ((0, 0, 3), (0, 0, 2))]
+
+    results = pathfinding_py.find_routes_temporal(volume, "astar", queries, reach=2)
+
+    assert len(results) == len(queries), "There should be one result per query"
+    for (start, end), result in zip(queries, results):
+        expected = pathfinding_py.find_route_temporal(volume, "astar", start, end, reach=2)
+        assert result == expected, f"Query {start} -> {end} should match find_route_temporal"
+    assert results[3] is None, "An end before its start has no route"
+
+
+def test_find_routes_temporal_validates_every_query():
+    """Test that an out-of-bounds point in any query raises before searching, naming the query."""
+    volume = np.ones((4, 4, 4), dtype=np.uint8)
+
+    with pytest.raises(ValueError, match=r"end of query 1 \(4, 0, 3\) is out of bounds"):
+        pathfinding_py.find_routes_temporal(volume, "dijkstra", [((0, 0, 0), (3, 3, 3)), ((0, 0, 0), (4, 0, 3))])
+
+    assert pathfinding_py.find_routes_temporal(volume, "dijkstra", []) == [], "No queries give no results"
+
+
+def test_find_route_4d_matches_temporal():
+    """Test that a 4D volume with a single z slice gives the same cost as the 3D route along time."""
+    rng = np.random.default_rng(3)
+    volume = rng.integers(1, 50, (6, 5, 8), dtype=np.uint8)
+    stack = volume[:, :, np.newaxis, :].transpose(3, 2, 1, 0)  # (t, z, y, x)
+
+    _, cost_3d = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (5, 4, 7))
+    route, cost = pathfinding_py.find_route_4d(stack, "dijkstra", (0, 0, 0, 0), (7, 0, 4, 5), axis=0)
+
+    assert cost == cost_3d, "The 4D route should cost as much as the 3D one"
+    assert [t for t, _, _, _ in route] == list(range(8)), "The route should advance one frame per step"
+
+
+def test_find_route_4d_moves_across_depth():
+    """Test that a 4D route moves along z to avoid an expensive slice, and that bad input is rejected."""
+    stack = np.full((4, 3, 5, 5), 200, dtype=np.uint8)  # (t, z, y, x)
+    stack[:, 2] = 1
+
+    route, cost = pathfinding_py.find_route_4d(stack, "astar", (0, 0, 2, 2), (3, 2, 2, 2), axis=0, reach=(2, 1, 1))
+    assert all(z == 2 for _, z, _, _ in route[1:]), "The route should jump to the cheap slice right away"
+    assert cost == 3, "Each of the three steps should enter a cheap cell"
+
+    with pytest.raises(ValueError, match="axis must be 0, 1, 2 or 3"):
+        pathfinding_py.find_route_4d(stack, "astar", axis=4)
+    with pytest.raises(ValueError, match=r"end \(4, 0, 0, 0\) is out of bounds"):
+        pathfinding_py.find_route_4d(stack, "astar", (0, 0, 0, 0), (4, 0, 0, 0), axis=0)
+
+
+def test_draw_path_overlay_draws_path(tmp_path):
+    """Test that draw_path_overlay colors the path pixels and leaves the rest of the image alone."""
+    image_path = tmp_path / "input.png"
+    out_path = tmp_path / "overlay.png"
+    Image.new("L", (20, 10), color=128).save(image_path)
+
+    pathfinding_py.draw_path_overlay(
+        str(image_path), [(2, 5), (17, 5)], str(out_path), color=(0, 255, 0), radius=1, line=True
+    )
+
+    overlay = Image.open(out_path)
+    assert overlay.mode == "RGB", "The overlay should be saved in color"
+    assert overlay.size == (20, 10), "The overlay should keep the size of the input"
+    assert overlay.getpixel((10, 5)) == (0, 255, 0), "The line should connect the two points"
+    assert overlay.getpixel((10, 0)) == (128, 128, 128), "Pixels away from the path should be unchanged"
+
+
+def test_draw_path_overlay_missing_image(tmp_path):
+    """Test that an unreadable input image raises ValueError."""
+    with pytest.raises(ValueError, match="failed to draw overlay"):
+        pathfinding_py.draw_path_overlay(str(tmp_path / "missing.png"), [(0, 0)], str(tmp_path / "out.png"))
+
+
+def test_save_distance_field_normalizes_costs(tmp_path):
+    """Test that save_distance_field spans the colormap over the reachable costs."""
+    array = np.ones((10, 6), dtype=np.uint8) * 10
+    array[5, :] = 0
+    field = pathfinding_py.compute_distance_field_2d(array, (0, 0), impassable=0)
+    out_path = tmp_path / "field.png"
+
+    pathfinding_py.save_distance_field(field, str(out_path), colormap="grayscale")
+
+    image = Image.open(out_path)
+    assert image.mode == "RGBA", "The field should be saved with an alpha channel"
+    assert image.size == (10, 6), "The image should be width by height"
+    assert image.getpixel((0, 0)) == (0, 0, 0, 255), "The source should get the start of the scale"
+    x, y = np.unravel_index(np.argmax(np.where(field == np.iinfo(np.uint32).max, 0, field)), field.shape)
+    assert image.getpixel((int(x), int(y))) == (255, 255, 255, 255), "The costliest cell should get the end of the scale"
+    assert image.getpixel((7, 3))[3] == 0, "Unreachable cells should be transparent"
+
+
+def test_save_distance_field_unreachable_color_and_colormaps(tmp_path):
+    """Test the unreachable color, the viridis default, and that unknown colormaps are rejected."""
+    field = np.array([[0, 10], [20, 2**32 - 1]], dtype=np.uint32)
+    out_path = tmp_path / "field.png"
+
+    pathfinding_py.save_distance_field(field, str(out_path), unreachable_color=(255, 0, 0))
+
+    image = Image.open(out_path)
+    assert image.getpixel((1, 1)) == (255, 0, 0, 255), "Unreachable cells should use the given color"
+    assert image.getpixel((0, 0)) == (68, 1, 84, 255), "Viridis should start dark purple"
+    assert image.getpixel((1, 0)) == (253, 231, 37, 255), "Viridis should end yellow"
+
+    with pytest.raises(ValueError, match="viridis, grayscale"):
+        pathfinding_py.save_distance_field(field, str(out_path), colormap="jet")
+
+def test_find_route_temporal_list_out_of_bounds():
+    """Test that every point of a start or end list is validated."""
+    volume = np.ones((6, 6, 4), dtype=np.uint8) * 100
+
+    with pytest.raises(ValueError, match=r"end \(6, 0, 3\) is out of bounds"):
+        pathfinding_py.find_route_temporal(volume, "astar", [(0, 0, 0)], [(5, 5, 3), (6, 0, 3)])
+
+
+def test_find_route_temporal_return_frame_costs():
+    """Test that return_frame_costs=True splits the route cost by frame, summing to the total."""
+    volume = np.full((5, 5, 6), 10, dtype=np.uint8)
+    volume[:, :, 3] = 90  # A hard frame
+
+    route, cost, frame_costs = pathfinding_py.find_route_temporal(
+        volume, "dijkstra", (0, 0, 0), (4, 4, 5), scale_diagonal=True, return_frame_costs=True
+    )
+    assert len(frame_costs) == 6, "There should be one entry per frame"
+    assert sum(frame_costs) == cost, "The frame costs should sum to the total cost"
+    assert frame_costs[0] == 0, "Nothing is paid on the start frame"
+    assert frame_costs.index(max(frame_costs)) == 3, "The hard frame should be the most expensive"
+
+
+def test_find_route_temporal_frame_costs_skipped_frames():
+    """Test that frames skipped by the axis step and frames outside the route hold no cost."""
+    volume = np.full((3, 3, 8), 200, dtype=np.uint8)
+    volume[:, :, [2, 4]] = 1
+
+    _, cost, frame_costs = pathfinding_py.find_route_temporal(
+        volume, "astar", (1, 1, 0), (1, 1, 4), axis_step_max=2, return_frame_costs=True
+    )
+    assert frame_costs == [0, 0, 1, 0, 1, 0, 0, 0], "Only the two frames entered should hold a cost"
+    assert cost == 2, "The route should skip the expensive frames"
+
+
+def test_find_route_temporal_max_cost():
+    """Test that max_cost is an inclusive budget for temporal routes."""
+    volume = np.ones((10, 10, 5), dtype=np.uint8) * 30
+
+    _, cost = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (4, 4, 4))
+
+    result = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (4, 4, 4), max_cost=cost)
+    assert result is not None and result[1] == cost, "A route costing exactly max_cost should be returned"
+
+    result = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (4, 4, 4), max_cost=cost - 1)
+    assert result is None, "No route should fit below the optimal cost"
+
+
+def test_find_route_temporal_per_dimension_reach():
+    """Test that a (reach_dim0, reach_dim1) tuple limits each non-axis dimension separately."""
+    volume = np.ones((12, 6, 5), dtype=np.uint8) * 100
+    for t in range(5):
+        volume[2 * t, 3, t] = 1
+
+    result = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 3, 0), (8, 3, 4), reach=(2, 0))
+    assert result is not None, "A reach of 2 along x should follow the corridor"
+    route, cost = result
+    assert all(y == 3 for _, y, _ in route), "A reach of 0 along y should keep y fixed"
+    assert cost == 4, "The route should follow the cheap corridor"
+
+    result = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 3, 0), (8, 3, 4), reach=(1, 2))
+    assert result is None, "A reach of 1 along x cannot cover 2 pixels per frame"
+
+    scalar = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 3, 0), (8, 3, 4), reach=2)
+    pair = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 3, 0), (8, 3, 4), reach=(2, 2))
+    assert scalar == pair, "A scalar reach should match the same reach along both dimensions"
+
+
+def test_find_route_temporal_scale_diagonal():
+    """Test that scale_diagonal makes in-plane diagonal steps cost sqrt(2) times more."""
+    volume = np.ones((4, 4, 3), dtype=np.uint8) * 10
+
+    _, cost = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (2, 2, 2))
+    assert cost == 20, "Diagonal steps should cost the value entered by default"
+
+    _, cost = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (2, 2, 2), scale_diagonal=True)
+    assert cost == 2 * 14, "Forced diagonal steps should cost round(10 * sqrt(2)) each"
+
+    route, cost = pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (1, 1, 2), scale_diagonal=True)
+    assert cost == 20, "Two straight steps should beat a diagonal step and a wait"
+    assert all(
+        abs(a[0] - b[0]) + abs(a[1] - b[1]) <= 1 for a, b in zip(route, route[1:])
+    ), "The route should not take diagonal steps"
+
+
+def test_find_route_temporal_axis_step_skips_frames():
+    """Test that axis_step_max lets a route skip frames, paying only for the frames it lands on."""
+    volume = np.ones((3, 3, 7), dtype=np.uint8) * 100
+    for t in [0, 3, 6]:
+        volume[1, 1, t] = 1
+
+    route, cost = pathfinding_py.find_route_temporal(volume, "dijkstra", (1, 1, 0), (1, 1, 6))
+    assert len(route) == 7 and cost > 2, "By default every frame is visited"
+
+    for algorithm in pathfinding_py.supported_algorithms_temporal():
+        result = pathfinding_py.find_route_temporal(
+            volume, algorithm, (1, 1, 0), (1, 1, 6), axis_step_max=3
+        )
+        assert result == ([(1, 1, 0), (1, 1, 3), (1, 1, 6)], 2), f"{algorithm} should jump between the cheap frames"
+
+
+def test_find_route_temporal_interpolate_fills_skipped_frames():
+    """Test that interpolate=True fills skipped frames without changing the cost or frame costs."""
+    volume = np.ones((9, 3, 7), dtype=np.uint8) * 100
+    for x, t in [(0, 0), (3, 3), (6, 6)]:
+        volume[x, 1, t] = 1
+
+    searched = pathfinding_py.find_route_temporal(
+        volume, "dijkstra", (0, 1, 0), (6, 1, 6), reach=3, axis_step_max=3, return_frame_costs=True
+    )
+    assert searched[0] == [(0, 1, 0), (3, 1, 3), (6, 1, 6)], "The search should jump between the cheap frames"
+
+    route, cost, frame_costs = pathfinding_py.find_route_temporal(
+        volume, "dijkstra", (0, 1, 0), (6, 1, 6), reach=3, axis_step_max=3, return_frame_costs=True, interpolate=True
+    )
+    assert route == [(x, 1, x) for x in range(7)], "Every skipped frame should get a linearly interpolated point"
+    assert cost == searched[1] == 2, "Filled points should not add to the cost"
+    assert frame_costs == searched[2] == [0, 0, 0, 1, 0, 0, 1], "Filled frames should hold no cost"
+
+    unskipped = pathfinding_py.find_route_temporal(volume, "astar", (0, 1, 0), (2, 1, 6), reach=3)
+    assert pathfinding_py.find_route_temporal(
+        volume, "astar", (0, 1, 0), (2, 1, 6), reach=3, interpolate=True
+    ) == unskipped, "A route without skipped frames should be unchanged"
+
+
+def test_find_route_temporal_axis_step_range():
+    """Test that axis_step_min is respected and that an invalid range raises."""
+    volume = np.ones((3, 3, 6), dtype=np.uint8)
+
+    route, _ = pathfinding_py.find_route_temporal(
+        volume, "astar", (1, 1, 0), (1, 1, 4), axis_step_min=2, axis_step_max=2
+    )
+    assert [t for _, _, t in route] == [0, 2, 4], "Every step should advance by exactly 2 frames"
+
+    with pytest.raises(ValueError, match="reach"):
+        pathfinding_py.find_route_temporal(
+            volume, "astar", (1, 1, 0), (1, 1, 5), axis_step_min=2, axis_step_max=2, strict=True
+        )
+
+    with pytest.raises(ValueError, match="axis_step_min"):
+        pathfinding_py.find_route_temporal(volume, "astar", (1, 1, 0), (1, 1, 5), axis_step_min=0)
+    with pytest.raises(ValueError, match="axis_step_min"):
+        pathfinding_py.find_route_temporal(volume, "astar", (1, 1, 0), (1, 1, 5), axis_step_min=3, axis_step_max=2)
+
+def test_find_route_temporal_end_before_last_frame():
+    """Test that routes can end in a middle frame, with every algorithm."""
+    volume = np.ones((4, 4, 6), dtype=np.uint8)
+
+    for algorithm in pathfinding_py.supported_algorithms_temporal():
+        result = pathfinding_py.find_route_temporal(volume, algorithm, (0, 0, 1), (2, 2, 3))
+        assert result is not None, f"{algorithm} should reach a middle frame"
+        path, cost = result
+        assert path[0] == (0, 0, 1) and path[-1] == (2, 2, 3), "Route should join the given points"
+
+
+def test_find_path_2d_equal_cost_ties_are_reproducible():
+    """Test that the same query returns the same path every time, even among equal-cost paths."""
+    array = np.ones((30, 30), dtype=np.uint8) * 10  # Countless paths share the optimal cost
+
+    for algorithm in pathfinding_py.supported_algorithms_2d():
+        first = pathfinding_py.find_path_2d(array, (0, 0), (29, 17), algorithm)
+        second = pathfinding_py.find_path_2d(array, (0, 0), (29, 17), algorithm)
+        finder = pathfinding_py.PathFinder2D(array).find((0, 0), (29, 17), algorithm)
+
+        assert first == second, f"{algorithm} should return the same path on every call"
+        assert finder == first, f"{algorithm} should return the same path through PathFinder2D"
+
+
+def test_find_path_2d_reproducible_across_processes():
+    """Test that a fresh interpreter, with its own hash seeds, returns the same path."""
+    script = (
+        "import numpy as np, pathfinding_py; "
+        "print(pathfinding_py.find_path_2d(np.ones((30, 30), dtype=np.uint8), (0, 0), (29, 17), 'dijkstra_bi'))"
+    )
+    runs = [subprocess.run([sys.executable, "-c", script], capture_output=True, text=True, check=True) for _ in range(2)]
+
+    expected = pathfinding_py.find_path_2d(np.ones((30, 30), dtype=np.uint8), (0, 0), (29, 17), "dijkstra_bi")
+    assert runs[0].stdout == runs[1].stdout == f"{expected}\n", "Every process should return the same path"
+
+
+def test_concurrent_searches_from_threads():
+    """Test that searches run from a thread pool give the same results as serial calls."""
+    rng = np.random.default_rng(3)
+    arrays = [rng.integers(1, 255, size=(60, 60), dtype=np.uint8) for _ in range(4)]
+    volume = rng.integers(1, 255, size=(20, 20, 10), dtype=np.uint8)
+
+    def search_2d(array):
+        return pathfinding_py.find_path_2d(array, (0, 0), (59, 59), "dijkstra")
+
+    def search_temporal(_):
+        return pathfinding_py.find_route_temporal(volume, "dijkstra", (0, 0, 0), (19, 19, 9), reach=2)
+
+    serial_2d = [search_2d(a) for a in arrays]
+    serial_temporal = search_temporal(None)
+
+    with ThreadPoolExecutor(max_workers=4) as pool:
+        threaded_2d = list(pool.map(search_2d, arrays))
+        threaded_temporal = list(pool.map(search_temporal, range(4)))
+
+    assert threaded_2d == serial_2d, "Threaded 2D searches should match serial results"
+    assert all(r == serial_temporal for r in threaded_temporal), (
+        "Threaded temporal searches should match serial results"
+    )
+
+
 def test_2d_pathfinding_on_real_image():
     """Test 2D pathfinding on the actual heatmap image (similar to justfile test command)."""
     image_path = ASSETS_DIR / "black-on-white-lv-like-heatmap.png"
@@ -351,3 +3057,12 @@ def test_temporal_pathfinding_on_rotating_frames():
         assert 0 <= t < num_frames, f"Route point t={t} out of bounds [0, {num_frames})"
 
     assert cost > 0, "Cost should be positive"
+
+
+def test_version_matches_dunder_version():
+    """Test that version() reports both crate versions and agrees with __version__."""
+    versions = pathfinding_py.version()
+
+    assert set(versions) == {"pathfinding_py", "image_pathfinding"}, "Both crates should be reported"
+    assert versions["pathfinding_py"] == pathfinding_py.__version__, "version() should match __version__"
+    assert all(v.count(".") == 2 for v in versions.values()), "Versions should be semantic versions"